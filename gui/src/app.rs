@@ -6,16 +6,21 @@ use std::{
 };
 
 use egui::{
-    FontData, FontDefinitions, FontFamily, Key, Pos2, Rect, Ui, UserData, ViewportCommand, vec2,
+    Color32, FontData, FontDefinitions, FontFamily, Key, Pos2, Rect, Ui, UserData, ViewportCommand,
+    vec2,
 };
 use egui_extras::install_image_loaders;
 use image::{ExtendedColorType, save_buffer};
 use rand::random;
 use ritm_core::{
+    turing_formats::parse_graph_by_extension,
     turing_graph::TuringMachineGraph,
+    turing_json::GraphMetadata,
+    turing_linter::{LintFinding, lint_graph},
     turing_machine::{Mode, TuringExecutionSteps, TuringMachines},
     turing_parser::{graph_to_string, parse_turing_graph_string},
     turing_state::{TuringDirection, TuringStateType, TuringTransition},
+    turing_test_suite::{SuiteReport, run_embedded_tests},
 };
 
 use crate::{
@@ -74,6 +79,12 @@ pub struct App {
     pub help_slide_index: usize,
 
     pub temp_state: Option<State>,
+
+    /// Results of the tests embedded in the code, like `// @accept 0110`
+    pub embedded_tests: SuiteReport,
+
+    /// Likely mistakes found in the machine by the linter
+    pub lint_findings: Vec<LintFinding>,
 }
 
 /// Keep the state of the application
@@ -135,6 +146,7 @@ impl Default for App {
         let mut turing = TuringMachines::new(graph, "".to_string(), Mode::StopAfter(500)).unwrap();
 
         let step = turing.into_iter().next().unwrap();
+        let lint_findings = lint_graph(turing.graph_ref());
 
         let mut sf = Self {
             turing: turing,
@@ -158,6 +170,8 @@ impl Default for App {
             },
             help_slide_index: 0,
             temp_state: None,
+            embedded_tests: SuiteReport { results: vec![] },
+            lint_findings,
         };
 
         // Update the graph data with the turing data at initialization
@@ -324,6 +338,8 @@ impl App {
         self.selected_transition = None;
         self.event.need_recenter = true;
         self.turing_to_graph();
+        self.embedded_tests = run_embedded_tests(self.turing.graph_ref());
+        self.lint_findings = lint_graph(self.turing.graph_ref());
         self.reset();
     }
 
@@ -396,7 +412,26 @@ impl App {
     }
 
     pub fn graph_to_code(&mut self) {
+        // Keep the layout of the graph, so that it is restored when loading the code
+        for state in self.states.values() {
+            let metadata = self
+                .turing
+                .graph_mut()
+                .get_state_metadata_mut(&state.name)
+                .unwrap();
+            metadata.position = Some((state.position.x, state.position.y));
+            metadata.color = (state.color != Color32::WHITE).then(|| {
+                format!(
+                    "#{:02x}{:02x}{:02x}",
+                    state.color.r(),
+                    state.color.g(),
+                    state.color.b()
+                )
+            });
+        }
         self.code = graph_to_string(self.turing.graph_ref());
+        self.embedded_tests = run_embedded_tests(self.turing.graph_ref());
+        self.lint_findings = lint_graph(self.turing.graph_ref());
     }
 
     pub fn code_to_graph(&mut self) {
@@ -406,6 +441,9 @@ impl App {
                     TuringMachines::new(graph, self.input.to_string(), Mode::StopFirstReject)
                         .unwrap();
                 self.turing_to_graph();
+                self.apply_metadata();
+                self.embedded_tests = run_embedded_tests(self.turing.graph_ref());
+                self.lint_findings = lint_graph(self.turing.graph_ref());
             }
             Err(e) => {
                 println!("{:?}", e);
@@ -414,6 +452,37 @@ impl App {
         self.event.need_recenter = true;
     }
 
+    /// Replace the code with a machine imported from a JFLAP or JSON file, keeping the positions and colors of its states
+    pub fn file_to_graph(&mut self, name: &str, content: &str) {
+        match parse_graph_by_extension(Path::new(name), content.to_string()) {
+            Ok(graph) => {
+                // The layout is stored in the graph, and written in the code as annotations
+                self.code = graph_to_string(&graph);
+                self.code_to_graph();
+            }
+            Err(e) => {
+                println!("{:?}", e);
+            }
+        }
+    }
+
+    /// Restore the positions and colors stored in the metadata of the graph
+    fn apply_metadata(&mut self) {
+        let graph = self.turing.graph_ref();
+        for state in self.states.values_mut() {
+            let Some(metadata) = graph.get_state_metadata(&state.name) else {
+                continue;
+            };
+            if let Some((x, y)) = metadata.position {
+                state.position = Pos2::new(x, y);
+                state.is_pinned = true;
+            }
+            if let Some(Ok(color)) = metadata.color.as_deref().map(Color32::from_hex) {
+                state.color = color;
+            }
+        }
+    }
+
     /// Create a graphical representation of a turing machine by copying each states and transitions information into GUI-oriented struct
     pub fn turing_to_graph(&mut self) {
         self.states = HashMap::new();
@@ -479,6 +548,33 @@ impl App {
         }
     }
 
+    /// Returns the position of every state, using their name
+    pub fn state_positions(&self) -> HashMap<String, (f32, f32)> {
+        self.states
+            .values()
+            .map(|state| (state.name.clone(), (state.position.x, state.position.y)))
+            .collect()
+    }
+
+    /// Returns the layout of the states, to be saved along with the graph
+    pub fn graph_metadata(&self) -> GraphMetadata {
+        GraphMetadata {
+            positions: self.state_positions(),
+            colors: self
+                .states
+                .values()
+                .map(|state| {
+                    let color = state.color;
+                    (
+                        state.name.clone(),
+                        format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b()),
+                    )
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
     /// Unpin all states
     pub fn unpin(&mut self) {
         for (_, state) in &mut self.states {
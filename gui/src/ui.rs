@@ -9,6 +9,7 @@ pub mod control;
 pub mod edit;
 pub mod font;
 pub mod graph;
+pub mod lint;
 pub mod popup;
 pub mod ribbon;
 pub mod settings;
@@ -121,6 +122,19 @@ pub fn show(app: &mut App, ctx: &egui::Context) {
                                 settings::show(app, ui);
                             });
 
+                        if !app.lint_findings.is_empty() {
+                            TopBottomPanel::bottom("lint")
+                                .frame(Frame {
+                                    fill: app.theme.code,
+                                    inner_margin: 5.into(),
+                                    ..Default::default()
+                                })
+                                .resizable(false)
+                                .show_inside(ui, |ui| {
+                                    lint::show(app, ui);
+                                });
+                        }
+
                         CentralPanel::default()
                             .frame(Frame {
                                 outer_margin: Margin::same(0),
@@ -0,0 +1,26 @@
+use egui::{Color32, Label, RichText, ScrollArea, Ui};
+use ritm_core::turing_linter::LintSeverity;
+
+use crate::{App, ui::font::Font};
+
+/// Display the likely mistakes found in the current turing machine
+pub fn show(app: &App, ui: &mut Ui) {
+    ScrollArea::vertical().max_height(100.0).show(ui, |ui| {
+        ui.set_width(ui.available_width());
+        for finding in &app.lint_findings {
+            let color = match finding.severity {
+                LintSeverity::Error => app.theme.invalid,
+                LintSeverity::Warning => Color32::from_rgb(230, 140, 0),
+                LintSeverity::Info => app.theme.gray,
+            };
+            ui.add(
+                Label::new(
+                    RichText::new(finding.to_string())
+                        .color(color)
+                        .font(Font::default_small()),
+                )
+                .wrap(),
+            );
+        }
+    });
+}
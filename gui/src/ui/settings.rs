@@ -1,9 +1,13 @@
 use egui::{
-    AtomExt, Button, Image, ImageButton, Popup, PopupCloseBehavior, RectAlign, RichText, Separator,
-    Stroke, Ui, Vec2, include_image, vec2,
+    AtomExt, Button, Image, ImageButton, Label, Popup, PopupCloseBehavior, RectAlign, RichText,
+    Separator, Stroke, Ui, Vec2, include_image, vec2,
 };
 use egui_flex::{Flex, FlexAlign, FlexAlignContent, FlexDirection, item};
 use include_directory::{Dir, include_directory};
+use ritm_core::{
+    turing_formats::GraphFormat, turing_json::graph_to_json, turing_latex::graph_to_tikz,
+};
+use std::path::Path;
 
 use crate::{
     App,
@@ -106,7 +110,11 @@ pub fn show(app: &mut App, ui: &mut Ui) {
             })
             .close_behavior(PopupCloseBehavior::CloseOnClick)
             .show(|ui| {
-                for example in EXAMPLES.files() {
+                // The test suites shipped next to the examples are not machines
+                for example in EXAMPLES
+                    .files()
+                    .filter(|file| file.path().extension().is_some_and(|ext| ext == "tm"))
+                {
                     let button = Button::new(
                         RichText::new(example.path().file_stem().unwrap().to_str().unwrap())
                             .font(Font::default_small())
@@ -138,10 +146,45 @@ pub fn show(app: &mut App, ui: &mut Ui) {
                 {
                     app.file.open();
                 }
+
+                if ui
+                    .add(
+                        Button::new(
+                            RichText::new("Export TikZ")
+                                .font(Font::default_small())
+                                .color(app.theme.gray),
+                        )
+                        .frame(false),
+                    )
+                    .clicked()
+                {
+                    let tikz = graph_to_tikz(app.turing.graph_ref(), &app.state_positions());
+                    app.file.save("machine.tex", tikz.into_bytes());
+                }
+
+                if ui
+                    .add(
+                        Button::new(
+                            RichText::new("Export JSON")
+                                .font(Font::default_small())
+                                .color(app.theme.gray),
+                        )
+                        .frame(false),
+                    )
+                    .clicked()
+                {
+                    let json = graph_to_json(app.turing.graph_ref(), &app.graph_metadata());
+                    app.file.save("machine.json", json.into_bytes());
+                }
             });
 
-        if let Some(file) = app.file.get() {
-            app.code = std::str::from_utf8(&file).unwrap().to_string()
+        if let Some((name, file)) = app.file.get() {
+            let content = std::str::from_utf8(&file).unwrap();
+            match GraphFormat::from_path(Path::new(&name)) {
+                // The code is loaded as it is written, keeping its comments
+                GraphFormat::Tm => app.code = content.to_string(),
+                _ => app.file_to_graph(&name, content),
+            }
         }
 
         if ui
@@ -175,6 +218,28 @@ pub fn show(app: &mut App, ui: &mut Ui) {
                 app.code_to_graph();
             }
 
+            // Badge showing the results of the tests embedded in the code
+            let report = &app.embedded_tests;
+            if !report.results.is_empty() {
+                let (text, color) = if report.is_success() {
+                    (
+                        format!("✔ {}/{}", report.passed(), report.results.len()),
+                        app.theme.valid,
+                    )
+                } else {
+                    (
+                        format!("✖ {}/{}", report.passed(), report.results.len()),
+                        app.theme.invalid,
+                    )
+                };
+                ui.add(
+                    item(),
+                    Label::new(RichText::new(text).font(Font::default_small()).color(color))
+                        .selectable(false),
+                )
+                .on_hover_text(report.to_string());
+            }
+
             if ui
                 .add(
                     item(),
@@ -32,6 +32,8 @@ pub fn direction(p1: Pos2, p2: Pos2) -> Vec2 {
 }
 
 type FileData = Vec<u8>;
+/// The name of an opened file, along with its content
+type NamedFile = (String, FileData);
 
 // wasm
 #[cfg(target_arch = "wasm32")]
@@ -45,8 +47,8 @@ use web_sys::{File, FileReader, HtmlInputElement, Url, window};
 
 #[cfg(target_arch = "wasm32")]
 pub struct FileDialog {
-    tx: std::sync::mpsc::Sender<FileData>,
-    rx: std::sync::mpsc::Receiver<FileData>,
+    tx: std::sync::mpsc::Sender<NamedFile>,
+    rx: std::sync::mpsc::Receiver<NamedFile>,
     input: HtmlInputElement,
     closure: Option<Closure<dyn FnMut()>>,
 }
@@ -101,6 +103,7 @@ impl FileDialog {
 
         let closure = Closure::once(move || {
             if let Some(file) = input_clone.files().and_then(|files| files.get(0)) {
+                let name = file.name();
                 let reader = FileReader::new().unwrap();
                 let reader_clone = reader.clone();
                 let onload_closure = Closure::once(Box::new(move || {
@@ -110,7 +113,7 @@ impl FileDialog {
                         .dyn_into::<ArrayBuffer>()
                         .unwrap();
                     let buffer = Uint8Array::new(&array_buffer).to_vec();
-                    tx.send(buffer).ok();
+                    tx.send((name, buffer)).ok();
                 }));
 
                 reader.set_onload(Some(onload_closure.as_ref().unchecked_ref()));
@@ -126,7 +129,7 @@ impl FileDialog {
         self.input.click();
     }
 
-    pub fn get(&self) -> Option<Vec<u8>> {
+    pub fn get(&self) -> Option<NamedFile> {
         if let Ok(file) = self.rx.try_recv() {
             Some(file)
         } else {
@@ -159,7 +162,7 @@ use rfd;
 
 #[cfg(not(target_arch = "wasm32"))]
 pub struct FileDialog {
-    file: Option<FileData>,
+    file: Option<NamedFile>,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -174,11 +177,15 @@ impl FileDialog {
     pub fn open(&mut self) {
         let path = rfd::FileDialog::new().pick_file();
         if let Some(path) = path {
-            self.file = std::fs::read(path).ok();
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            self.file = std::fs::read(path).ok().map(|data| (name, data));
         }
     }
 
-    pub fn get(&mut self) -> Option<FileData> {
+    pub fn get(&mut self) -> Option<NamedFile> {
         std::mem::replace(&mut self.file, None)
     }
 
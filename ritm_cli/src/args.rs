@@ -0,0 +1,63 @@
+use std::collections::{HashMap, HashSet};
+
+/// The arguments given to a command : the positional ones, the flags like `--json` and the options like `--word 0101` (or `--word=0101`).
+pub struct Args {
+    pub positionals: Vec<String>,
+    flags: HashSet<String>,
+    options: HashMap<String, String>,
+}
+
+impl Args {
+    /// Sorts the given arguments, returning the reason if one of them is unknown or if an option has no value.
+    pub fn parse(args: &[String], flags: &[&str], options: &[&str]) -> Result<Self, String> {
+        let mut res = Args {
+            positionals: vec![],
+            flags: HashSet::new(),
+            options: HashMap::new(),
+        };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if !arg.starts_with("--") {
+                res.positionals.push(arg.clone());
+                continue;
+            }
+            let (name, value) = match arg.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (arg.as_str(), None),
+            };
+            if flags.contains(&name) && value.is_none() {
+                res.flags.insert(name.to_string());
+            } else if options.contains(&name) {
+                match value.or_else(|| args.next().cloned()) {
+                    Some(value) => {
+                        res.options.insert(name.to_string(), value);
+                    }
+                    None => return Err(format!("{name} expects a value")),
+                }
+            } else {
+                return Err(format!("Unknown option \"{arg}\""));
+            }
+        }
+        Ok(res)
+    }
+
+    /// Returns true if the given flag was set.
+    pub fn flag(&self, name: &str) -> bool {
+        self.flags.contains(name)
+    }
+
+    /// Returns the value given to the option, if any.
+    pub fn option(&self, name: &str) -> Option<&String> {
+        self.options.get(name)
+    }
+
+    /// Returns the number given to the option, or the default value if none was given.
+    pub fn number(&self, name: &str, default: usize) -> Result<usize, String> {
+        match self.option(name) {
+            Some(value) => value
+                .parse()
+                .map_err(|_| format!("{name} expects a positive number, got \"{value}\"")),
+            None => Ok(default),
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use std::{fs, path::Path, process::ExitCode};
+
+use colored::Colorize;
+use ritm_core::{
+    turing_errors::TuringParserError,
+    turing_formats::{GraphFormat, load_graph_by_extension},
+    turing_linter::{LintSeverity, lint_graph},
+    turing_parser::find_turing_graph_errors,
+};
+use serde_json::json;
+
+use crate::{
+    args::Args,
+    commands::{EXIT_FAILURE, usage_error},
+};
+
+/// Parses every machine given and reports the errors, along with the mistakes found by the linter.
+///
+/// The check fails if a machine cannot be parsed or if the linter finds an error, warnings are only reported.
+/// All the syntax errors of a machine written in the text format are reported, but only the first error is reported for the other formats.
+pub fn check_command(args: &[String]) -> ExitCode {
+    let args = match Args::parse(args, &["--json"], &[]) {
+        Ok(args) => args,
+        Err(reason) => return usage_error(reason),
+    };
+    if args.positionals.is_empty() {
+        return usage_error("No machine was given".to_string());
+    }
+    let json = args.flag("--json");
+
+    let mut success = true;
+    let mut reports = vec![];
+    for path in &args.positionals {
+        let (errors, findings) = match load_graph_by_extension(Path::new(path)) {
+            Ok(graph) => (vec![], lint_graph(&graph)),
+            Err(e) => (find_errors(Path::new(path), e), vec![]),
+        };
+        let valid = errors.is_empty()
+            && findings
+                .iter()
+                .all(|finding| finding.severity != LintSeverity::Error);
+        success &= valid;
+
+        if json {
+            let findings: Vec<_> = findings
+                .iter()
+                .map(|finding| {
+                    json!({
+                        "severity": finding.severity.to_string(),
+                        "message": finding.message,
+                    })
+                })
+                .collect();
+            reports.push(json!({
+                "path": path,
+                "valid": valid,
+                "errors": errors.iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+                "findings": findings,
+            }));
+            continue;
+        }
+
+        if !errors.is_empty() {
+            println!("{} :", path.bold());
+            for error in errors {
+                println!("  {}", error.to_string().red());
+            }
+            continue;
+        }
+        if findings.is_empty() {
+            println!("{} : {}", path.bold(), "no mistakes found".green());
+        } else {
+            println!("{} :", path.bold());
+        }
+        for finding in findings {
+            let line = format!("  {finding}");
+            match finding.severity {
+                LintSeverity::Error => println!("{}", line.red()),
+                LintSeverity::Warning => println!("{}", line.yellow()),
+                LintSeverity::Info => println!("{line}"),
+            }
+        }
+    }
+
+    if json {
+        println!("{}", json!({ "success": success, "machines": reports }));
+    }
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(EXIT_FAILURE)
+    }
+}
+
+/// Returns all the syntax errors of a machine written in the text format, the given error being the only one returned for the other formats.
+fn find_errors(path: &Path, error: TuringParserError) -> Vec<TuringParserError> {
+    match (GraphFormat::from_path(path), fs::read_to_string(path)) {
+        (GraphFormat::Tm, Ok(content)) => find_turing_graph_errors(&content),
+        _ => vec![error],
+    }
+}
@@ -0,0 +1,60 @@
+use std::{fs, path::Path, process::ExitCode};
+
+use ritm_core::{
+    turing_dot::{DotOptions, graph_to_dot},
+    turing_errors::TuringParserError,
+    turing_formats::load_graph_by_extension,
+    turing_jflap::graph_to_jflap,
+    turing_json::{GraphMetadata, graph_to_json},
+    turing_latex::graph_to_tikz,
+    turing_parser::graph_to_string,
+};
+
+use crate::{
+    args::Args,
+    commands::{file_error, usage_error},
+};
+
+/// Converts a machine to another format, printing it or writing it in the file given with `--output`.
+pub fn export_command(args: &[String]) -> ExitCode {
+    let args = match Args::parse(args, &[], &["--format", "--output"]) {
+        Ok(args) => args,
+        Err(reason) => return usage_error(reason),
+    };
+    let [machine] = args.positionals.as_slice() else {
+        return usage_error("Exactly one machine must be given".to_string());
+    };
+    let Some(format) = args.option("--format") else {
+        return usage_error("--format must be given".to_string());
+    };
+    let graph = match load_graph_by_extension(Path::new(machine)) {
+        Ok(graph) => graph,
+        Err(e) => return file_error(e),
+    };
+
+    let content = match format.as_str() {
+        "dot" => graph_to_dot(&graph, &DotOptions::default()),
+        "json" => graph_to_json(&graph, &GraphMetadata::default()),
+        "tikz" => graph_to_tikz(&graph, &graph.get_state_positions()),
+        "jff" => graph_to_jflap(&graph, &graph.get_state_positions()),
+        "tm" => graph_to_string(&graph),
+        _ => {
+            return usage_error(format!(
+                "Unknown format \"{format}\", expected dot, json, tikz, jff or tm"
+            ));
+        }
+    };
+
+    match args.option("--output") {
+        Some(path) => {
+            if let Err(e) = fs::write(path, content) {
+                return file_error(TuringParserError::FileError {
+                    given_path: path.clone(),
+                    error_reason: e.to_string(),
+                });
+            }
+        }
+        None => print!("{content}"),
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,72 @@
+use std::{fs, process::ExitCode};
+
+use colored::Colorize;
+use ritm_core::{turing_errors::TuringParserError, turing_formatter::format_turing_graph_string};
+use serde_json::json;
+
+use crate::{
+    args::Args,
+    commands::{EXIT_FAILURE, file_error, usage_error},
+};
+
+/// Formats the given `.tm` files in place, or only lists the ones that are not formatted with `--check`.
+pub fn fmt_command(args: &[String]) -> ExitCode {
+    let args = match Args::parse(args, &["--check", "--json"], &[]) {
+        Ok(args) => args,
+        Err(reason) => return usage_error(reason),
+    };
+    if args.positionals.is_empty() {
+        return usage_error("No file was given".to_string());
+    }
+    let check = args.flag("--check");
+    let json = args.flag("--json");
+
+    let mut unformatted = vec![];
+    let mut files = vec![];
+    for path in &args.positionals {
+        let source = match fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(e) => {
+                return file_error(TuringParserError::FileError {
+                    given_path: path.clone(),
+                    error_reason: e.to_string(),
+                });
+            }
+        };
+        let formatted = match format_turing_graph_string(source.clone()) {
+            Ok(formatted) => formatted,
+            Err(e) => {
+                eprintln!("{} : {}", path.bold(), e.to_string().red());
+                return ExitCode::from(EXIT_FAILURE);
+            }
+        };
+        let changed = formatted != source;
+        files.push(json!({ "path": path, "changed": changed }));
+        if !changed {
+            continue;
+        }
+        unformatted.push(path);
+
+        if check {
+            if !json {
+                println!("{} is not formatted", path.bold());
+            }
+        } else if let Err(e) = fs::write(path, formatted) {
+            return file_error(TuringParserError::FileError {
+                given_path: path.clone(),
+                error_reason: e.to_string(),
+            });
+        } else if !json {
+            println!("Formatted {}", path.bold());
+        }
+    }
+
+    if json {
+        println!("{}", json!({ "check": check, "files": files }));
+    }
+    if check && !unformatted.is_empty() {
+        ExitCode::from(EXIT_FAILURE)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
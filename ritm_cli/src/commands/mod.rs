@@ -0,0 +1,39 @@
+use std::process::ExitCode;
+
+use colored::Colorize;
+use ritm_core::{turing_equivalence::RunOutcome, turing_errors::TuringParserError};
+
+use crate::USAGE;
+
+pub mod check;
+pub mod export;
+pub mod fmt;
+pub mod profile;
+pub mod run;
+pub mod test;
+
+/// Returned when the word was rejected, or when a check or a test failed
+pub const EXIT_FAILURE: u8 = 1;
+/// Returned when the arguments or the given files could not be used
+pub const EXIT_USAGE: u8 = 2;
+/// Returned when the execution was stopped before giving an answer
+pub const EXIT_TIMEOUT: u8 = 3;
+
+/// Returns the name of the outcome used in the outputs.
+pub fn outcome_name(outcome: RunOutcome) -> &'static str {
+    match outcome {
+        RunOutcome::Accepted => "accepted",
+        RunOutcome::Rejected => "rejected",
+        RunOutcome::OutOfBudget => "timeout",
+    }
+}
+
+pub fn file_error(error: TuringParserError) -> ExitCode {
+    eprintln!("{}", error.to_string().red());
+    ExitCode::from(EXIT_USAGE)
+}
+
+pub fn usage_error(reason: String) -> ExitCode {
+    eprintln!("{}\n\n{USAGE}", reason.red());
+    ExitCode::from(EXIT_USAGE)
+}
@@ -0,0 +1,145 @@
+use std::{fs, path::Path, process::ExitCode};
+
+use colored::Colorize;
+use ritm_core::{
+    turing_errors::TuringParserError,
+    turing_formats::load_graph_by_extension,
+    turing_profiler::{ComplexityProfile, Fit, InputGenerator, ProfileOptions, profile_complexity},
+};
+use serde_json::{Value, json};
+
+use crate::{
+    args::Args,
+    commands::{EXIT_USAGE, file_error, usage_error},
+};
+
+/// Profiles a machine and prints the measures, the plot being written in a SVG file if asked.
+pub fn profile_command(args: &[String]) -> ExitCode {
+    let args = match Args::parse(
+        args,
+        &["--json"],
+        &[
+            "--min",
+            "--max",
+            "--alphabet",
+            "--random",
+            "--seed",
+            "--words",
+            "--budget",
+            "--svg",
+        ],
+    ) {
+        Ok(args) => args,
+        Err(reason) => return usage_error(reason),
+    };
+    let [machine] = args.positionals.as_slice() else {
+        return usage_error("Exactly one machine must be given".to_string());
+    };
+    let numbers = (|| {
+        Ok::<_, String>((
+            args.number("--min", 0)?,
+            args.number("--max", 10)?,
+            args.number("--seed", 0)?,
+            args.number("--budget", 100_000)?,
+        ))
+    })();
+    let (min_len, max_len, seed, step_budget) = match numbers {
+        Ok(numbers) => numbers,
+        Err(reason) => return usage_error(reason),
+    };
+    let graph = match load_graph_by_extension(Path::new(machine)) {
+        Ok(graph) => graph,
+        Err(e) => return file_error(e),
+    };
+
+    let generator = match (args.option("--words"), args.option("--random")) {
+        (Some(path), _) => match fs::read_to_string(path) {
+            Ok(content) => InputGenerator::from_words(
+                content
+                    .lines()
+                    .map(|word| word.trim().to_string())
+                    .filter(|word| !word.is_empty())
+                    .collect(),
+            ),
+            Err(e) => {
+                return file_error(TuringParserError::FileError {
+                    given_path: path.clone(),
+                    error_reason: e.to_string(),
+                });
+            }
+        },
+        (None, Some(_)) => match args.number("--random", 0) {
+            Ok(samples) => InputGenerator::Random {
+                samples,
+                seed: seed as u64,
+            },
+            Err(reason) => return usage_error(reason),
+        },
+        (None, None) => InputGenerator::All,
+    };
+    let options = ProfileOptions {
+        alphabet: args
+            .option("--alphabet")
+            .map(|alphabet| alphabet.chars().filter(|c| !c.is_whitespace()).collect()),
+        lengths: (min_len..=max_len).collect(),
+        generator,
+        step_budget,
+    };
+    let profile = match profile_complexity(&graph, &options) {
+        Ok(profile) => profile,
+        Err(e) => {
+            eprintln!("{}", e.to_string().red());
+            return ExitCode::from(EXIT_USAGE);
+        }
+    };
+
+    if let Some(path) = args.option("--svg")
+        && let Err(e) = fs::write(path, profile.to_svg())
+    {
+        return file_error(TuringParserError::FileError {
+            given_path: path.clone(),
+            error_reason: e.to_string(),
+        });
+    }
+    if args.flag("--json") {
+        println!("{}", profile_to_json(&profile));
+    } else {
+        print!("{}", profile.to_table());
+        if let Some(path) = args.option("--svg") {
+            println!("\nSaved the plot at {path}");
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn profile_to_json(profile: &ComplexityProfile) -> Value {
+    let fit_to_json = |fit: &Fit| {
+        json!({
+            "model": fit.model.to_string(),
+            "intercept": fit.intercept,
+            "slope": fit.slope,
+            "r_squared": fit.r_squared,
+        })
+    };
+    let lengths: Vec<Value> = profile
+        .lengths
+        .iter()
+        .map(|length| {
+            json!({
+                "n": length.n,
+                "samples": length.samples,
+                "worst_steps": length.worst_steps,
+                "average_steps": length.average_steps,
+                "peak_tape_length": length.peak_tape_length,
+                "out_of_budget": length.out_of_budget,
+            })
+        })
+        .collect();
+    json!({
+        "lengths": lengths,
+        "time": profile.best_time_fit().map(fit_to_json),
+        "space": profile.best_space_fit().map(fit_to_json),
+        "time_fits": profile.time_fits.iter().map(fit_to_json).collect::<Vec<_>>(),
+        "space_fits": profile.space_fits.iter().map(fit_to_json).collect::<Vec<_>>(),
+    })
+}
@@ -0,0 +1,124 @@
+use std::{path::Path, process::ExitCode};
+
+use colored::Colorize;
+use ritm_core::{
+    turing_equivalence::{RunOutcome, run_inspecting},
+    turing_formats::load_graph_by_extension,
+    turing_machine::Mode,
+    turing_tape::TuringTape,
+};
+use serde_json::json;
+
+use crate::{
+    args::Args,
+    commands::{EXIT_FAILURE, EXIT_TIMEOUT, EXIT_USAGE, file_error, outcome_name, usage_error},
+};
+
+/// The number of steps an execution is stopped after when no mode is given
+const DEFAULT_STEP_BUDGET: usize = 10_000;
+
+/// Runs the machine on a word, the exit code telling if it was accepted, rejected or stopped before giving an answer.
+pub fn run_command(args: &[String]) -> ExitCode {
+    let args = match Args::parse(args, &["--json"], &["--word", "--mode"]) {
+        Ok(args) => args,
+        Err(reason) => return usage_error(reason),
+    };
+    let [machine] = args.positionals.as_slice() else {
+        return usage_error("Exactly one machine must be given".to_string());
+    };
+    let word = args.option("--word").cloned().unwrap_or_default();
+    let mode = match args.option("--mode").map(|mode| parse_mode(mode)) {
+        Some(Ok(mode)) => mode,
+        Some(Err(reason)) => return usage_error(reason),
+        None => Mode::StopAfter(DEFAULT_STEP_BUDGET),
+    };
+    let graph = match load_graph_by_extension(Path::new(machine)) {
+        Ok(graph) => graph,
+        Err(e) => return file_error(e),
+    };
+
+    // The budget is counted by the run, so that an execution ending right on the budget is not mistaken for a timeout
+    let (mode, budget) = match mode {
+        Mode::StopAfter(budget) => (Mode::SaveAll, budget),
+        mode => (mode, usize::MAX),
+    };
+    let mut first_step = None;
+    let (result, last_step) = match run_inspecting(&graph, &word, mode, budget, |step| {
+        if first_step.is_none() {
+            first_step = Some(step.clone());
+        }
+    }) {
+        Ok(run) => run,
+        Err(e) => {
+            eprintln!("{}", e.to_string().red());
+            return ExitCode::from(EXIT_USAGE);
+        }
+    };
+    // The first step is always produced, even when no step can be taken
+    let last_step = last_step.or(first_step).unwrap();
+    let (outcome, steps) = (result.outcome, result.steps);
+
+    if args.flag("--json") {
+        let tapes: Vec<_> = last_step
+            .get_writing_tapes()
+            .iter()
+            .map(|tape| {
+                json!({
+                    "content": tape.get_contents().iter().collect::<String>(),
+                    "head": tape.get_pointer(),
+                })
+            })
+            .collect();
+        let reading_tape = last_step.get_reading_tape();
+        println!(
+            "{}",
+            json!({
+                "word": word,
+                "outcome": outcome_name(outcome),
+                "steps": steps,
+                "state": format!("q_{}", last_step.get_current_state().name),
+                "reading_tape": {
+                    "content": reading_tape.get_contents().iter().collect::<String>(),
+                    "head": reading_tape.get_pointer(),
+                },
+                "writing_tapes": tapes,
+            })
+        );
+    } else {
+        let summary = format!(
+            "{} after {steps} steps, in the state q_{}",
+            outcome,
+            last_step.get_current_state().name
+        );
+        match outcome {
+            RunOutcome::Accepted => println!("{}", summary.green()),
+            RunOutcome::Rejected => println!("{}", summary.red()),
+            RunOutcome::OutOfBudget => println!("{}", summary.yellow()),
+        }
+        println!("Reading tape : {}", last_step.get_reading_tape());
+        for (index, tape) in last_step.get_writing_tapes().iter().enumerate() {
+            println!("Tape {} : {tape}", index + 1);
+        }
+    }
+
+    match outcome {
+        RunOutcome::Accepted => ExitCode::SUCCESS,
+        RunOutcome::Rejected => ExitCode::from(EXIT_FAILURE),
+        RunOutcome::OutOfBudget => ExitCode::from(EXIT_TIMEOUT),
+    }
+}
+
+/// Parses an execution mode : `save-all`, `stop-first-reject` or `stop-after=<steps>`.
+fn parse_mode(mode: &str) -> Result<Mode, String> {
+    match mode.split_once('=') {
+        Some(("stop-after", steps)) => steps
+            .parse()
+            .map(Mode::StopAfter)
+            .map_err(|_| format!("stop-after expects a positive number, got \"{steps}\"")),
+        None if mode == "save-all" => Ok(Mode::SaveAll),
+        None if mode == "stop-first-reject" => Ok(Mode::StopFirstReject),
+        _ => Err(format!(
+            "Unknown mode \"{mode}\", expected save-all, stop-first-reject or stop-after=<steps>"
+        )),
+    }
+}
@@ -0,0 +1,121 @@
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use colored::Colorize;
+use ritm_core::{
+    turing_formats::load_graph_by_extension,
+    turing_test_suite::{
+        SuiteReport, parse_test_suite_file_path, run_embedded_tests, run_test_suite,
+    },
+};
+use serde_json::{Value, json};
+
+use crate::{
+    args::Args,
+    commands::{EXIT_FAILURE, file_error, outcome_name, usage_error},
+};
+
+/// Runs every suite given and prints their results, the exit code tells if all of them passed.
+///
+/// A `.tm` file given instead of a suite has its embedded tests run.
+pub fn test_command(args: &[String]) -> ExitCode {
+    let args = match Args::parse(args, &["--json"], &["--machine"]) {
+        Ok(args) => args,
+        Err(reason) => return usage_error(reason),
+    };
+    if args.positionals.is_empty() {
+        return usage_error("No test suite was given".to_string());
+    }
+    let json = args.flag("--json");
+
+    let mut success = true;
+    let mut reports = vec![];
+    for path in &args.positionals {
+        let path = PathBuf::from(path);
+        let (machine_path, report) = if path.extension().is_some_and(|ext| ext == "tm") {
+            match load_graph_by_extension(&path) {
+                Ok(graph) => (path.clone(), run_embedded_tests(&graph)),
+                Err(e) => return file_error(e),
+            }
+        } else {
+            let suite = match parse_test_suite_file_path(path.to_string_lossy().to_string()) {
+                Ok(suite) => suite,
+                Err(e) => return file_error(e),
+            };
+            let machine_path = match args.option("--machine") {
+                Some(machine) => PathBuf::from(machine),
+                None => suite.machine_path(&path),
+            };
+            match load_graph_by_extension(&machine_path) {
+                Ok(graph) => (machine_path, run_test_suite(&graph, &suite)),
+                Err(e) => return file_error(e),
+            }
+        };
+
+        success &= report.is_success();
+        if json {
+            reports.push(report_to_json(&path, &machine_path, &report));
+        } else {
+            println!("{}", format!("Running {}", path.display()).bold());
+            print_report(&report);
+        }
+    }
+
+    if json {
+        println!("{}", json!({ "success": success, "suites": reports }));
+    }
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(EXIT_FAILURE)
+    }
+}
+
+fn print_report(report: &SuiteReport) {
+    for result in &report.results {
+        let result_str = result.to_string();
+        if result.passed {
+            println!("{}", result_str.green());
+        } else {
+            println!("{}", result_str.red());
+        }
+    }
+    if report.is_success() {
+        println!("{}\n", report.summary().green());
+    } else {
+        println!("{}\n", report.summary().red());
+    }
+}
+
+fn report_to_json(path: &Path, machine_path: &Path, report: &SuiteReport) -> Value {
+    let results: Vec<Value> = report
+        .results
+        .iter()
+        .map(|result| {
+            let mut value = json!({
+                "line": result.case.line,
+                "word": result.case.word,
+                "expected": result.case.expectation.to_string(),
+                "passed": result.passed,
+            });
+            match &result.run {
+                Ok(run) => {
+                    value["outcome"] = json!(outcome_name(run.outcome));
+                    value["steps"] = json!(run.steps);
+                    value["output"] = json!(run.output);
+                }
+                Err(e) => value["error"] = json!(e),
+            }
+            value
+        })
+        .collect();
+    json!({
+        "path": path.display().to_string(),
+        "machine": machine_path.display().to_string(),
+        "passed": report.passed(),
+        "failed": report.failed(),
+        "results": results,
+    })
+}
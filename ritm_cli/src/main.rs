@@ -0,0 +1,50 @@
+use std::{env, process::ExitCode};
+
+use crate::commands::{
+    check::check_command, export::export_command, fmt::fmt_command, profile::profile_command,
+    run::run_command, test::test_command, usage_error,
+};
+
+mod args;
+mod commands;
+
+const USAGE: &str = "Usage :
+    ritm run <machine> [--word <word>] [--mode save-all|stop-first-reject|stop-after=<steps>] [--json]
+    ritm check <machine>... [--json]
+    ritm fmt <machine.tm>... [--check] [--json]
+    ritm export <machine> --format dot|json|tikz|jff|tm [--output <path>]
+    ritm test <suite.tmtest | machine.tm>... [--machine <path>] [--json]
+    ritm profile <machine> [--min <n>] [--max <n>] [--alphabet <chars>] [--random <samples>] [--seed <seed>]
+                           [--words <path>] [--budget <steps>] [--svg <path>] [--json]
+
+Commands :
+    run        Runs the machine on the word (stopped after 10000 steps by default)
+               and exits with 0 if it was accepted, 1 if it was rejected and 3 if it was stopped
+    check      Parses the machines and reports the errors, along with the mistakes found by the linter
+    fmt        Formats the machines in place, or lists the ones that are not formatted with --check
+    export     Converts the machine to another format, printed or written in the given file
+    test       Runs the given test suites, on the machine they name or on the one given with --machine,
+               or the tests embedded in the given machines
+    profile    Runs the machine on every word (or random ones, or the ones listed in a file) from the minimum
+               to the maximum length, then fits the steps and tape length used to O(n), O(n log n) and O(n²)
+
+The machines can be written in the .tm, .json or .jff formats.
+The exit code is 2 if the arguments or the files could not be used.";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(|arg| arg.as_str()) {
+        Some("run") => run_command(&args[1..]),
+        Some("check") => check_command(&args[1..]),
+        Some("fmt") => fmt_command(&args[1..]),
+        Some("export") => export_command(&args[1..]),
+        Some("test") => test_command(&args[1..]),
+        Some("profile") => profile_command(&args[1..]),
+        Some("-h") | Some("--help") | Some("help") => {
+            println!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        Some(command) => usage_error(format!("Unknown command \"{command}\"")),
+        None => usage_error("No command was given".to_string()),
+    }
+}
@@ -0,0 +1,142 @@
+use std::{
+    env, fs,
+    path::PathBuf,
+    process::{Command, Output},
+};
+
+use serde_json::Value;
+
+const PALINDROME: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../ritm_core/resources/binary_palindrome.tm"
+);
+const PALINDROME_SUITE: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../ritm_core/resources/binary_palindrome.tmtest"
+);
+
+fn ritm(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ritm"))
+        .args(args)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+fn exit_code(output: &Output) -> i32 {
+    output.status.code().unwrap()
+}
+
+fn stdout_json(output: &Output) -> Value {
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+/// Writes a machine in a temporary file, named after the test so that the tests can run in parallel.
+fn write_machine(name: &str, content: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("ritm_cli_{}_{name}.tm", std::process::id()));
+    fs::write(&path, content).unwrap();
+    path
+}
+
+#[test]
+fn run_exit_codes() {
+    let accepted = ritm(&["run", PALINDROME, "--word", "0110"]);
+    assert_eq!(exit_code(&accepted), 0);
+
+    let rejected = ritm(&["run", PALINDROME, "--word", "01"]);
+    assert_eq!(exit_code(&rejected), 1);
+
+    let timeout = ritm(&[
+        "run",
+        PALINDROME,
+        "--word",
+        "0110",
+        "--mode",
+        "stop-after=3",
+    ]);
+    assert_eq!(exit_code(&timeout), 3);
+
+    // The other modes are not stopped
+    let saved = ritm(&["run", PALINDROME, "--word", "0110", "--mode", "save-all"]);
+    assert_eq!(exit_code(&saved), 0);
+}
+
+#[test]
+fn run_json() {
+    let output = ritm(&["run", PALINDROME, "--word", "0110", "--json"]);
+    assert_eq!(exit_code(&output), 0);
+    let json = stdout_json(&output);
+    assert_eq!(json["outcome"], "accepted");
+    assert_eq!(json["state"], "q_a");
+    assert_eq!(json["word"], "0110");
+    assert!(json["steps"].as_u64().unwrap() > 0);
+
+    let output = ritm(&[
+        "run",
+        PALINDROME,
+        "--word",
+        "0110",
+        "--mode",
+        "stop-after=0",
+        "--json",
+    ]);
+    assert_eq!(exit_code(&output), 3);
+    let json = stdout_json(&output);
+    assert_eq!(json["outcome"], "timeout");
+    assert_eq!(json["steps"], 0);
+    assert_eq!(json["state"], "q_i");
+}
+
+#[test]
+fn usage_errors() {
+    for args in [
+        vec![],
+        vec!["unknown"],
+        vec!["run"],
+        vec!["run", PALINDROME, "--mode", "stop-after=never"],
+        vec!["run", "missing.tm"],
+        vec!["check"],
+        vec!["export", PALINDROME, "--format", "png"],
+    ] {
+        let output = ritm(&args);
+        assert_eq!(exit_code(&output), 2, "{args:?}");
+        assert!(!output.stderr.is_empty());
+    }
+}
+
+#[test]
+fn check_reports_every_error() {
+    let valid = ritm(&["check", PALINDROME]);
+    assert_eq!(exit_code(&valid), 0);
+
+    let path = write_machine(
+        "check",
+        "q_i {ç, ç -> R, ç, R} q_1;
+q_1 {0, _ -> R, 0, R q_1;
+q_1 {1, _ -> R, 1} q_a;
+q_1 {$, _ -> N, _, N} q_a;",
+    );
+    let output = ritm(&["check", path.to_str().unwrap(), "--json"]);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(exit_code(&output), 1);
+    let json = stdout_json(&output);
+    assert_eq!(json["success"], false);
+    let errors = json["machines"][0]["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0].as_str().unwrap().contains("2: q_1"));
+    assert!(errors[1].as_str().unwrap().contains("3: q_1"));
+}
+
+#[test]
+fn test_exit_codes() {
+    let suite = ritm(&["test", PALINDROME_SUITE]);
+    assert_eq!(exit_code(&suite), 0);
+
+    let embedded = ritm(&["test", PALINDROME]);
+    assert_eq!(exit_code(&embedded), 0);
+
+    let path = write_machine("test", "// @reject 0\nq_i {ç, ç -> R, ç, R} q_a;");
+    let failed = ritm(&["test", path.to_str().unwrap()]);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(exit_code(&failed), 1);
+}
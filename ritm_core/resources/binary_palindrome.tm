@@ -1,3 +1,10 @@
+// Accepts the binary palindromes
+// @accept
+// @accept 0110
+// @accept 10101
+// @reject 01
+// @reject 0111
+
 // Initialisation
 q_i {ç, ç -> R, ç, R} q_copy;
 
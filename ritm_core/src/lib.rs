@@ -9,3 +9,40 @@ pub mod turing_errors;
 pub mod turing_tape;
 
 pub mod turing_parser;
+
+pub mod turing_linter;
+
+pub mod turing_minimizer;
+
+pub mod turing_single_tape;
+
+pub mod turing_determinizer;
+
+pub mod turing_combinators;
+
+pub mod turing_jflap;
+
+pub mod turing_formats;
+
+pub mod turing_dot;
+
+pub mod turing_latex;
+
+pub mod turing_trace;
+
+#[cfg(feature = "serde")]
+pub mod turing_json;
+
+pub mod turing_equivalence;
+
+pub mod turing_enumeration;
+
+pub mod turing_test_suite;
+
+pub mod turing_profiler;
+
+pub mod turing_formatter;
+
+pub mod turing_history;
+
+pub mod turing_views;
@@ -0,0 +1,446 @@
+use crate::{
+    turing_determinizer::{check_input_alphabet, product},
+    turing_errors::TuringError,
+    turing_graph::TuringMachineGraph,
+    turing_state::{TuringDirection, TuringStateType, TuringTransition},
+    turing_tape::{BLANK_CHAR, END_CHAR, INIT_CHAR},
+};
+
+/// Where the tapes of a machine are placed inside of the combined machine.
+struct Placement {
+    /// Added before the name of every state of the machine, to avoid collisions
+    prefix: &'static str,
+    /// The tape of the combined machine (starting from `1`) used for each writing tape of the machine
+    tapes: Vec<usize>,
+    /// The tape of the combined machine used as the reading tape of the machine, `0` being the reading tape itself.
+    ///
+    /// When a writing tape is used, [END_CHAR] is replaced with [BLANK_CHAR] and the tape is never modified.
+    input: usize,
+}
+
+/// What to do when a machine accepts.
+enum OnAccept {
+    /// The combined machine accepts
+    Accept,
+    /// All the tapes of the machine (including its reading tape) are moved back to their first cell,
+    /// then the state with the given index is reached
+    RewindThen(usize),
+}
+
+/// Builds a machine accepting the words accepted by at least one of the given machines.
+///
+/// The combined machine starts by nondeterministically choosing which machine to run, both machines share the same tapes.
+/// The states of the first machine are prefixed with `A`, and the ones of the second machine with `B`.
+pub fn union(
+    m1: &TuringMachineGraph,
+    m2: &TuringMachineGraph,
+) -> Result<TuringMachineGraph, TuringError> {
+    let k = m1.get_k().max(m2.get_k());
+    let mut combinator = Combinator::new(k, &[m1, m2])?;
+
+    let placement1 = Placement {
+        prefix: "A",
+        tapes: (1..=m1.get_k()).collect(),
+        input: 0,
+    };
+    let placement2 = Placement {
+        prefix: "B",
+        tapes: (1..=m2.get_k()).collect(),
+        input: 0,
+    };
+    let start1 = combinator.add_machine(m1, &placement1, OnAccept::Accept)?;
+    let start2 = combinator.add_machine(m2, &placement2, OnAccept::Accept)?;
+
+    combinator.add_start(start1)?;
+    combinator.add_start(start2)?;
+    Ok(combinator.result)
+}
+
+/// Builds a machine accepting the words accepted by both of the given machines.
+///
+/// The combined machine copies the input on a new writing tape (the last one), then runs the first machine.
+/// If it accepts, its tapes are moved back to their first cell and the second machine is run, using the copy as its input.
+/// Each machine gets its own writing tapes.
+/// The states of the first machine are prefixed with `A`, and the ones of the second machine with `B`.
+///
+/// The input alphabet is inferred from the characters read by both machines on their reading tape,
+/// any word containing other characters is rejected.
+pub fn intersection(
+    m1: &TuringMachineGraph,
+    m2: &TuringMachineGraph,
+) -> Result<TuringMachineGraph, TuringError> {
+    let (k1, k2) = (m1.get_k(), m2.get_k());
+    let copy_tape = k1 + k2 + 1;
+    let mut combinator = Combinator::new(copy_tape, &[m1, m2])?;
+
+    let placement2 = Placement {
+        prefix: "B",
+        tapes: (k1 + 1..=k1 + k2).collect(),
+        input: copy_tape,
+    };
+    let start2 = combinator.add_machine(m2, &placement2, OnAccept::Accept)?;
+
+    let placement1 = Placement {
+        prefix: "A",
+        tapes: (1..=k1).collect(),
+        input: 0,
+    };
+    let start1 = combinator.add_machine(m1, &placement1, OnAccept::RewindThen(start2))?;
+
+    combinator.add_copy(copy_tape, start1)?;
+    Ok(combinator.result)
+}
+
+/// Builds a machine that runs the first machine, then runs the second one using the output of the first one as its input.
+/// It accepts when the second machine accepts.
+///
+/// The output of the first machine is the content of its first writing tape, up to the first [BLANK_CHAR].
+/// Once the first machine accepts, its tapes are moved back to their first cell and the second machine reads this tape instead of the reading tape.
+/// Each machine gets its own writing tapes.
+/// The states of the first machine are prefixed with `A`, and the ones of the second machine with `B`.
+///
+/// The input alphabet is inferred from the characters read by both machines on their reading tape,
+/// the reading tape being moved back over these characters once the first machine accepted.
+/// Unlike for [intersection], the input is not read beforehand : a word containing other characters is rejected,
+/// unless the first machine accepts before reading them.
+pub fn sequence(
+    m1: &TuringMachineGraph,
+    m2: &TuringMachineGraph,
+) -> Result<TuringMachineGraph, TuringError> {
+    let (k1, k2) = (m1.get_k(), m2.get_k());
+    let mut combinator = Combinator::new(k1 + k2, &[m1, m2])?;
+
+    let placement2 = Placement {
+        prefix: "B",
+        tapes: (k1 + 1..=k1 + k2).collect(),
+        input: 1,
+    };
+    let start2 = combinator.add_machine(m2, &placement2, OnAccept::Accept)?;
+
+    let placement1 = Placement {
+        prefix: "A",
+        tapes: (1..=k1).collect(),
+        input: 0,
+    };
+    let start1 = combinator.add_machine(m1, &placement1, OnAccept::RewindThen(start2))?;
+
+    combinator.add_start(start1)?;
+    Ok(combinator.result)
+}
+
+/// Builds a machine accepting exactly the words made of the given alphabet that are rejected by the given machine.
+///
+/// This only holds if the given machine is a *total decider* on these words : it must be deterministic and stop on every one of them.
+/// Only the first condition can be checked, a [TuringError::IllegalActionError] is returned if the machine is not deterministic.
+/// On a word where the machine never stops, the complement never stops either, so it does not accept it.
+///
+/// The accepting and rejecting states are swapped, including the ones whose type was changed (like with `@type(q_x, accepting)`),
+/// and every missing transition (an implicit reject) is replaced with a transition to `q_a`.
+/// The characters read by the given machine are always part of the alphabet, so reaching a character of the alphabet it never reads is accepted.
+///
+/// If no alphabet is given, it is inferred from the characters read by the given machine (see [TuringMachineGraph::get_input_alphabet]) :
+/// a word containing any other character is then rejected by both machines.
+/// If the alphabet contains a character that cannot be part of an input, a [TuringError::IllegalActionError] is returned.
+pub fn complement(
+    graph: &TuringMachineGraph,
+    alphabet: Option<&[char]>,
+) -> Result<TuringMachineGraph, TuringError> {
+    if !graph.is_deterministic() {
+        return Err(TuringError::IllegalActionError {
+            cause: "Only the complement of a deterministic machine can be built".to_string(),
+        });
+    }
+    let alphabet = alphabet.unwrap_or_default();
+    check_input_alphabet(alphabet)?;
+    let k = graph.get_k();
+    let mut result = TuringMachineGraph::new(k)?;
+    for state in graph.get_states().iter().skip(3) {
+        result.add_state(&state.name);
+        let state_type = match state.state_type {
+            TuringStateType::Accepting => TuringStateType::Rejecting,
+            TuringStateType::Rejecting => TuringStateType::Accepting,
+            TuringStateType::Normal => TuringStateType::Normal,
+        };
+        // The default states were skipped, so the type can be changed
+        result.set_state_type(&state.name, state_type)?;
+    }
+
+    let mut input_alphabet = graph.get_input_alphabet();
+    input_alphabet.extend(alphabet);
+    input_alphabet.extend([INIT_CHAR, END_CHAR]);
+    input_alphabet.sort();
+    input_alphabet.dedup();
+    let mut alphabets = vec![input_alphabet];
+    for tape in 0..k {
+        alphabets.push(graph.get_tape_alphabet(tape));
+    }
+    let all_reads = product(&alphabets);
+
+    for (index, state) in graph.get_states().iter().enumerate() {
+        if state.state_type != TuringStateType::Normal {
+            continue;
+        }
+        for transition in &state.transitions {
+            // The types of the other states were swapped, but q_a and q_r must keep theirs
+            let target = match transition.index_to_state.unwrap() {
+                1 => 2,
+                2 => 1,
+                target => target,
+            };
+            let mut transition = transition.clone();
+            transition.index_to_state = None;
+            result.append_rule_state(index, transition, target)?;
+        }
+        // Reaching a dead end now accepts
+        for read in &all_reads {
+            if state.transitions.iter().any(|t| t.chars_read == *read) {
+                continue;
+            }
+            let write = read[1..].iter().map(|c| (*c, TuringDirection::None));
+            result.append_rule_state(
+                index,
+                TuringTransition::new(read.clone(), TuringDirection::None, write.collect()),
+                1,
+            )?;
+        }
+    }
+    Ok(result)
+}
+
+struct Combinator {
+    k: usize,
+    /// All the characters that can be found on the reading tape, except [INIT_CHAR] and [END_CHAR]
+    input_alphabet: Vec<char>,
+    result: TuringMachineGraph,
+}
+
+impl Combinator {
+    fn new(k: usize, machines: &[&TuringMachineGraph]) -> Result<Self, TuringError> {
+        let mut input_alphabet: Vec<char> = machines
+            .iter()
+            .flat_map(|m| m.get_input_alphabet())
+            .collect();
+        input_alphabet.sort();
+        input_alphabet.dedup();
+
+        Ok(Self {
+            k,
+            input_alphabet,
+            result: TuringMachineGraph::new(k)?,
+        })
+    }
+
+    /// Adds the states and transitions of the given machine and returns the index of its initial state.
+    fn add_machine(
+        &mut self,
+        machine: &TuringMachineGraph,
+        placement: &Placement,
+        on_accept: OnAccept,
+    ) -> Result<usize, TuringError> {
+        let states = machine.get_states();
+        let indexes: Vec<usize> = states
+            .iter()
+            .map(|state| match state.state_type {
+                TuringStateType::Normal => self
+                    .result
+                    .add_state(&format!("{}{}", placement.prefix, state.name)),
+                TuringStateType::Accepting => 1,
+                TuringStateType::Rejecting => 2,
+            })
+            .collect();
+
+        for (index, state) in states.iter().enumerate() {
+            if state.state_type != TuringStateType::Normal {
+                continue;
+            }
+            for transition in &state.transitions {
+                let target = transition.index_to_state.unwrap();
+                let mut transition = self.place_transition(transition, placement);
+
+                let target = match (&states[target].state_type, &on_accept) {
+                    (TuringStateType::Accepting, OnAccept::RewindThen(next)) => {
+                        // Staying in place means knowing what is under every head once the machine accepted
+                        transition.move_read = TuringDirection::None;
+                        for (_, direction) in &mut transition.chars_write {
+                            *direction = TuringDirection::None;
+                        }
+                        self.add_rewind(&transition, machine, placement, *next)?
+                    }
+                    _ => indexes[target],
+                };
+                self.result
+                    .append_rule_state(indexes[index], transition, target)?;
+            }
+        }
+        Ok(indexes[0])
+    }
+
+    /// Translates a transition of a machine into a transition of the combined machine, every other tape staying on its first cell.
+    fn place_transition(
+        &self,
+        transition: &TuringTransition,
+        placement: &Placement,
+    ) -> TuringTransition {
+        let mut chars_read = vec![INIT_CHAR; self.k + 1];
+        let mut move_read = TuringDirection::None;
+        let mut chars_write = vec![(INIT_CHAR, TuringDirection::None); self.k];
+
+        if placement.input == 0 {
+            chars_read[0] = transition.chars_read[0];
+            move_read = transition.move_read.clone();
+        } else {
+            let c = match transition.chars_read[0] {
+                END_CHAR => BLANK_CHAR,
+                c => c,
+            };
+            chars_read[placement.input] = c;
+            chars_write[placement.input - 1] = (c, transition.move_read.clone());
+        }
+        for (i, tape) in placement.tapes.iter().enumerate() {
+            chars_read[*tape] = transition.chars_read[i + 1];
+            chars_write[*tape - 1] = transition.chars_write[i].clone();
+        }
+        TuringTransition::new(chars_read, move_read, chars_write)
+    }
+
+    /// Adds the states moving back every tape of a machine to their first cell, after it accepted by taking the given transition.
+    /// The tapes are moved back one at a time, the reading tape being the last one.
+    ///
+    /// Returns the index of the first of these states.
+    fn add_rewind(
+        &mut self,
+        accepting: &TuringTransition,
+        machine: &TuringMachineGraph,
+        placement: &Placement,
+        next: usize,
+    ) -> Result<usize, TuringError> {
+        // What is under the heads after taking the transition
+        let mut known = accepting.chars_read.clone();
+        for (tape, (c, _)) in accepting.chars_write.iter().enumerate() {
+            known[tape + 1] = *c;
+        }
+
+        let mut input_alphabet = self.input_alphabet.clone();
+        input_alphabet.push(INIT_CHAR);
+        input_alphabet.push(if placement.input == 0 {
+            END_CHAR
+        } else {
+            BLANK_CHAR
+        });
+        let mut to_rewind: Vec<(usize, Vec<char>)> = placement
+            .tapes
+            .iter()
+            .enumerate()
+            .map(|(i, tape)| (*tape, machine.get_tape_alphabet(i)))
+            .collect();
+        to_rewind.push((placement.input, input_alphabet));
+
+        let first = self.result.add_fresh_state("rewind");
+        let mut current = first;
+        for (step, (tape, alphabet)) in to_rewind.iter().enumerate() {
+            let following = if step + 1 == to_rewind.len() {
+                next
+            } else {
+                self.result.add_fresh_state("rewind")
+            };
+            for c in alphabet {
+                let mut read = known.clone();
+                read[*tape] = *c;
+                let (direction, target) = if *c == INIT_CHAR {
+                    (TuringDirection::None, following)
+                } else {
+                    (TuringDirection::Left, current)
+                };
+                let mut write: Vec<(char, TuringDirection)> = read[1..]
+                    .iter()
+                    .map(|c| (*c, TuringDirection::None))
+                    .collect();
+                let mut move_read = TuringDirection::None;
+                if *tape == 0 {
+                    move_read = direction;
+                } else {
+                    write[*tape - 1].1 = direction;
+                }
+                self.result.append_rule_state(
+                    current,
+                    TuringTransition::new(read, move_read, write),
+                    target,
+                )?;
+            }
+            known[*tape] = INIT_CHAR;
+            current = following;
+        }
+        Ok(first)
+    }
+
+    /// Adds a transition from `q_i` to the given state, without moving.
+    fn add_start(&mut self, to: usize) -> Result<(), TuringError> {
+        self.result.append_rule_state(
+            0,
+            TuringTransition::new(
+                vec![INIT_CHAR; self.k + 1],
+                TuringDirection::None,
+                vec![(INIT_CHAR, TuringDirection::None); self.k],
+            ),
+            to,
+        )
+    }
+
+    /// Adds the states copying the input on the given tape, then going back to the first cell and reaching the given state.
+    fn add_copy(&mut self, copy_tape: usize, to: usize) -> Result<(), TuringError> {
+        let copy = self.result.add_fresh_state("copy");
+        let back = self.result.add_fresh_state("copy");
+        let k = self.k;
+        // Every other tape stays on its first cell
+        let transition = |read: char, copy_read: char, move_read, write: char, direction| {
+            let mut chars_read = vec![INIT_CHAR; k + 1];
+            chars_read[0] = read;
+            chars_read[copy_tape] = copy_read;
+            let mut chars_write = vec![(INIT_CHAR, TuringDirection::None); k];
+            chars_write[copy_tape - 1] = (write, direction);
+            TuringTransition::new(chars_read, move_read, chars_write)
+        };
+
+        let start = transition(
+            INIT_CHAR,
+            INIT_CHAR,
+            TuringDirection::Right,
+            INIT_CHAR,
+            TuringDirection::Right,
+        );
+        self.result.append_rule_state(0, start, copy)?;
+
+        let end = transition(
+            END_CHAR,
+            BLANK_CHAR,
+            TuringDirection::Left,
+            BLANK_CHAR,
+            TuringDirection::Left,
+        );
+        self.result.append_rule_state(copy, end, back)?;
+
+        let done = transition(
+            INIT_CHAR,
+            INIT_CHAR,
+            TuringDirection::None,
+            INIT_CHAR,
+            TuringDirection::None,
+        );
+        self.result.append_rule_state(back, done, to)?;
+
+        for c in self.input_alphabet.clone() {
+            let forward = transition(
+                c,
+                BLANK_CHAR,
+                TuringDirection::Right,
+                c,
+                TuringDirection::Right,
+            );
+            self.result.append_rule_state(copy, forward, copy)?;
+
+            let backward = transition(c, c, TuringDirection::Left, c, TuringDirection::Left);
+            self.result.append_rule_state(back, backward, back)?;
+        }
+        Ok(())
+    }
+}
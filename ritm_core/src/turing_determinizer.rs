@@ -0,0 +1,568 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::{
+    turing_errors::TuringError,
+    turing_graph::TuringMachineGraph,
+    turing_state::{TuringDirection, TuringStateType, TuringTransition},
+    turing_tape::{BLANK_CHAR, END_CHAR, INIT_CHAR},
+};
+
+/// The characters used to write the addresses, a digit represents the index of the transition to take.
+const DIGITS: &str = "123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+/// Written on the flag cell of the address tape when a branch was cut because its address was too short.
+const FLAG_ALIVE: char = 'y';
+/// Written on the flag cell of the address tape when no branch was cut since the length of the addresses last changed.
+const FLAG_DEAD: char = 'n';
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// The different states of the generated machine.
+///
+/// `exhausted` is `true` when the branch that was just simulated was cut because its address was too short.
+enum DetState {
+    /// `q_i`, moves to the flag cell of the address tape
+    Init,
+    /// Initializes the flag cell of the address tape
+    InitFlag,
+    /// Simulates the given state of the original machine
+    Simulate(usize),
+    /// Moves the reading tape back to its first cell
+    RewindInput { exhausted: bool },
+    /// Goes to the end of a simulated tape before erasing it
+    EraseRight { tape: usize, exhausted: bool },
+    /// Erases a simulated tape while going back to its first cell
+    EraseLeft { tape: usize, exhausted: bool },
+    /// Goes to the end of the address
+    AddressEnd { exhausted: bool },
+    /// Increments the address, starting from its last digit
+    Increment { exhausted: bool },
+    /// Adds a digit at the end of the address
+    Append,
+    /// Goes back to the flag cell, updating it
+    Rewind { exhausted: bool },
+    /// Moves the address tape back to its first cell
+    RewindStart,
+    /// Moves the address tape to the first digit of the address
+    SkipFlag,
+    /// The final state with the given index
+    Final(usize),
+}
+
+/// Builds a deterministic machine that accepts exactly the same words as the given one, by simulating all its branches breadth-first.
+///
+/// As in the usual proof, the generated machine uses the same tapes as the given one, plus an *address* tape.
+/// An address is a sequence of digits, the `n`-th digit being the index of the transition to take at the `n`-th step,
+/// among the ones that can be taken. The addresses are enumerated by increasing length, and for each of them :
+/// 1. The branch described by the address is simulated from the beginning, until it accepts, dies or runs out of digits.
+/// 2. The reading tape is moved back to its first cell and the simulated tapes are erased.
+/// 3. The next address is written on the address tape.
+///
+/// The first cell of the address tape after [INIT_CHAR] is a flag remembering if a branch was cut because its address was too short.
+/// If all the addresses of a given length were tried without cutting any branch, every branch is finite and the machine rejects.
+///
+/// In order to erase the simulated tapes, a blank written by the original machine is replaced with a character that is not used on that tape.
+///
+/// ## Input alphabet
+/// Moving the reading tape back requires knowing all the characters it can contain, so the generated machine
+/// only accepts the same words as the given one among the words made of the given alphabet.
+/// The characters read by the given machine are always part of it, and a character of the alphabet it never reads ends the branch reaching it.
+///
+/// If no alphabet is given, it is inferred from the characters read by the given machine (see [TuringMachineGraph::get_input_alphabet]).
+/// The generated machine then gets stuck and rejects as soon as the reading tape is on any other character,
+/// even if another branch of the given machine would have accepted the word.
+///
+/// If the alphabet contains a character that cannot be part of an input, or if a state has too many transitions reading
+/// the same characters to be encoded by a digit, a [TuringError::IllegalActionError] is returned.
+pub fn determinize(
+    graph: &TuringMachineGraph,
+    alphabet: Option<&[char]>,
+) -> Result<TuringMachineGraph, TuringError> {
+    DeterminizerBuilder::new(graph, alphabet.unwrap_or_default())?.build()
+}
+
+struct DeterminizerBuilder<'a> {
+    graph: &'a TuringMachineGraph,
+    k: usize,
+    /// All the characters that can be found on the reading tape
+    input_chars: Vec<char>,
+    /// All the characters that can be found on each simulated tape
+    tapes_chars: Vec<Vec<char>>,
+    /// The character replacing the blanks written by the original machine, for each simulated tape
+    written_blanks: Vec<char>,
+    /// The digits used by the addresses
+    digits: Vec<char>,
+    result: TuringMachineGraph,
+    indexes: HashMap<DetState, usize>,
+    to_visit: VecDeque<DetState>,
+}
+
+impl<'a> DeterminizerBuilder<'a> {
+    fn new(graph: &'a TuringMachineGraph, alphabet: &[char]) -> Result<Self, TuringError> {
+        let k = graph.get_k();
+        check_input_alphabet(alphabet)?;
+        let mut input_chars = vec![INIT_CHAR, END_CHAR];
+        input_chars.extend(alphabet);
+        let mut tapes_chars = vec![vec![INIT_CHAR, BLANK_CHAR]; k];
+        // Characters that must not be used to represent a written blank
+        let mut used_chars = vec![vec![INIT_CHAR, BLANK_CHAR, END_CHAR]; k];
+        let mut nb_choices = 1;
+
+        for state in graph.get_states() {
+            for transition in &state.transitions {
+                input_chars.push(transition.chars_read[0]);
+                for (tape, (c, _)) in transition.chars_write.iter().enumerate() {
+                    tapes_chars[tape].push(*c);
+                    used_chars[tape].push(*c);
+                    used_chars[tape].push(transition.chars_read[tape + 1]);
+                }
+                nb_choices = nb_choices.max(
+                    state
+                        .transitions
+                        .iter()
+                        .filter(|t| t.chars_read == transition.chars_read)
+                        .count(),
+                );
+            }
+        }
+        if nb_choices > DIGITS.len() {
+            return Err(TuringError::IllegalActionError {
+                cause: format!(
+                    "Cannot determinize a machine with more than {} transitions reading the same characters in the same state",
+                    DIGITS.len()
+                ),
+            });
+        }
+
+        let mut written_blanks = vec![];
+        for (tape, chars) in tapes_chars.iter_mut().enumerate() {
+            let blank = ('A'..='Z')
+                .chain('a'..='z')
+                .chain('0'..='9')
+                .find(|c| !used_chars[tape].contains(c))
+                .ok_or(TuringError::IllegalActionError {
+                    cause: format!(
+                        "No character is left to represent the blanks of the writing tape {}",
+                        tape
+                    ),
+                })?;
+            written_blanks.push(blank);
+            chars.push(blank);
+            chars.sort();
+            chars.dedup();
+        }
+        input_chars.sort();
+        input_chars.dedup();
+
+        Ok(Self {
+            graph,
+            k,
+            input_chars,
+            tapes_chars,
+            written_blanks,
+            digits: DIGITS.chars().take(nb_choices).collect(),
+            result: TuringMachineGraph::new(k + 1)?,
+            indexes: HashMap::new(),
+            to_visit: VecDeque::new(),
+        })
+    }
+
+    fn build(mut self) -> Result<TuringMachineGraph, TuringError> {
+        let graph = self.graph;
+        let states = graph.get_states();
+
+        // Keep the names of the original states, so that the generated ones never take them
+        for state in states.iter().skip(3) {
+            self.result.add_state(&state.name);
+        }
+
+        self.indexes.insert(DetState::Init, 0);
+        self.to_visit.push_back(DetState::Init);
+        for (index, state) in states.iter().enumerate() {
+            if state.state_type == TuringStateType::Normal {
+                self.get_index(DetState::Simulate(index));
+            }
+        }
+
+        while let Some(state) = self.to_visit.pop_front() {
+            self.expand(state)?;
+        }
+        Ok(self.result)
+    }
+
+    /// Adds all the transitions leaving the given state.
+    fn expand(&mut self, state: DetState) -> Result<(), TuringError> {
+        let from = self.indexes[&state];
+        let graph = self.graph;
+        let states = graph.get_states();
+        let k = self.k;
+        let address_chars = self.address_chars();
+
+        match state {
+            DetState::Init => {
+                let mut write = vec![(INIT_CHAR, TuringDirection::None); k];
+                write.push((INIT_CHAR, TuringDirection::Right));
+                self.add_transition(
+                    from,
+                    vec![INIT_CHAR; k + 2],
+                    TuringDirection::None,
+                    write,
+                    DetState::InitFlag,
+                )?;
+            }
+            DetState::InitFlag => {
+                let mut read = vec![INIT_CHAR; k + 1];
+                read.push(BLANK_CHAR);
+                let mut write = vec![(INIT_CHAR, TuringDirection::None); k];
+                write.push((FLAG_DEAD, TuringDirection::Right));
+                self.add_transition(
+                    from,
+                    read,
+                    TuringDirection::None,
+                    write,
+                    DetState::Simulate(0),
+                )?;
+            }
+            DetState::Simulate(state) => {
+                let mut alphabets = vec![self.input_chars.clone()];
+                alphabets.extend(self.tapes_chars.clone());
+                alphabets.push(address_chars.clone());
+
+                for read in product(&alphabets) {
+                    let digit = read[k + 1];
+                    // The characters the original machine would read
+                    let original_read: Vec<char> = read[..=k]
+                        .iter()
+                        .enumerate()
+                        .map(|(tape, c)| {
+                            if tape > 0 && *c == self.written_blanks[tape - 1] {
+                                BLANK_CHAR
+                            } else {
+                                *c
+                            }
+                        })
+                        .collect();
+                    let choices: Vec<&TuringTransition> = states[state]
+                        .transitions
+                        .iter()
+                        .filter(|t| t.chars_read == original_read)
+                        .collect();
+                    let choice = self.digits.iter().position(|d| *d == digit);
+
+                    let transition = match choice.and_then(|i| choices.get(i)) {
+                        Some(transition) => *transition,
+                        None => {
+                            // Either the branch died, or it needs a longer address to continue
+                            let exhausted = !choices.is_empty() && digit == BLANK_CHAR;
+                            self.add_stay_transition(
+                                from,
+                                read,
+                                DetState::RewindInput { exhausted },
+                            )?;
+                            continue;
+                        }
+                    };
+
+                    let mut write: Vec<(char, TuringDirection)> = transition
+                        .chars_write
+                        .iter()
+                        .enumerate()
+                        .map(|(tape, (c, dir))| {
+                            if *c == BLANK_CHAR {
+                                (self.written_blanks[tape], dir.clone())
+                            } else {
+                                (*c, dir.clone())
+                            }
+                        })
+                        .collect();
+                    write.push((digit, TuringDirection::Right));
+
+                    let target = transition.index_to_state.unwrap();
+                    let next = match states[target].state_type {
+                        TuringStateType::Normal => DetState::Simulate(target),
+                        TuringStateType::Accepting => DetState::Final(1),
+                        TuringStateType::Rejecting => DetState::RewindInput { exhausted: false },
+                    };
+                    self.add_transition(from, read, transition.move_read.clone(), write, next)?;
+                }
+            }
+            DetState::RewindInput { exhausted } => {
+                let mut alphabets = vec![self.input_chars.clone()];
+                alphabets.extend(self.tapes_chars.clone());
+                alphabets.push(address_chars.clone());
+
+                for read in product(&alphabets) {
+                    if read[0] == INIT_CHAR {
+                        self.add_stay_transition(
+                            from,
+                            read,
+                            DetState::EraseRight { tape: 0, exhausted },
+                        )?;
+                    } else {
+                        let write = read[1..].iter().map(|c| (*c, TuringDirection::None));
+                        self.add_transition(
+                            from,
+                            read.clone(),
+                            TuringDirection::Left,
+                            write.collect(),
+                            DetState::RewindInput { exhausted },
+                        )?;
+                    }
+                }
+            }
+            DetState::EraseRight { tape, exhausted } | DetState::EraseLeft { tape, exhausted } => {
+                // The tapes before this one were already erased
+                let mut alphabets = vec![vec![INIT_CHAR]; tape + 1];
+                alphabets.extend(self.tapes_chars[tape..].iter().cloned());
+                alphabets.push(address_chars.clone());
+                let going_right = matches!(state, DetState::EraseRight { .. });
+
+                for read in product(&alphabets) {
+                    let c = read[tape + 1];
+                    let (write, direction, next) = match (going_right, c) {
+                        (true, BLANK_CHAR) => (
+                            BLANK_CHAR,
+                            TuringDirection::Left,
+                            DetState::EraseLeft { tape, exhausted },
+                        ),
+                        (true, _) => (c, TuringDirection::Right, state.clone()),
+                        (false, INIT_CHAR) => (
+                            INIT_CHAR,
+                            TuringDirection::None,
+                            if tape + 1 < k {
+                                DetState::EraseRight {
+                                    tape: tape + 1,
+                                    exhausted,
+                                }
+                            } else {
+                                DetState::AddressEnd { exhausted }
+                            },
+                        ),
+                        (false, _) => (BLANK_CHAR, TuringDirection::Left, state.clone()),
+                    };
+                    self.add_tape_transition(from, tape + 1, read, (write, direction), next)?;
+                }
+            }
+            DetState::AddressEnd { exhausted } => {
+                for d in address_chars {
+                    let (direction, next) = if d == BLANK_CHAR {
+                        (TuringDirection::Left, DetState::Increment { exhausted })
+                    } else {
+                        (TuringDirection::Right, DetState::AddressEnd { exhausted })
+                    };
+                    self.add_address_transition(from, d, (d, direction), next)?;
+                }
+            }
+            DetState::Increment { exhausted } => {
+                for (i, d) in self.digits.clone().into_iter().enumerate() {
+                    let (write, next) = match self.digits.get(i + 1) {
+                        Some(next_digit) => (*next_digit, DetState::Rewind { exhausted }),
+                        // Carry
+                        None => (self.digits[0], DetState::Increment { exhausted }),
+                    };
+                    self.add_address_transition(from, d, (write, TuringDirection::Left), next)?;
+                }
+                for flag in [FLAG_ALIVE, FLAG_DEAD] {
+                    // All the addresses of this length were tried
+                    if exhausted || flag == FLAG_ALIVE {
+                        self.add_address_transition(
+                            from,
+                            flag,
+                            (FLAG_DEAD, TuringDirection::Right),
+                            DetState::Append,
+                        )?;
+                    } else {
+                        self.add_address_transition(
+                            from,
+                            flag,
+                            (flag, TuringDirection::None),
+                            DetState::Final(2),
+                        )?;
+                    }
+                }
+            }
+            DetState::Append => {
+                for d in address_chars {
+                    if d == BLANK_CHAR {
+                        self.add_address_transition(
+                            from,
+                            d,
+                            (self.digits[0], TuringDirection::Left),
+                            DetState::Rewind { exhausted: false },
+                        )?;
+                    } else {
+                        self.add_address_transition(
+                            from,
+                            d,
+                            (d, TuringDirection::Right),
+                            DetState::Append,
+                        )?;
+                    }
+                }
+            }
+            DetState::Rewind { exhausted } => {
+                for d in self.digits.clone() {
+                    self.add_address_transition(
+                        from,
+                        d,
+                        (d, TuringDirection::Left),
+                        DetState::Rewind { exhausted },
+                    )?;
+                }
+                for flag in [FLAG_ALIVE, FLAG_DEAD] {
+                    let write = if exhausted { FLAG_ALIVE } else { flag };
+                    self.add_address_transition(
+                        from,
+                        flag,
+                        (write, TuringDirection::Left),
+                        DetState::RewindStart,
+                    )?;
+                }
+            }
+            DetState::RewindStart => {
+                self.add_address_transition(
+                    from,
+                    INIT_CHAR,
+                    (INIT_CHAR, TuringDirection::Right),
+                    DetState::SkipFlag,
+                )?;
+            }
+            DetState::SkipFlag => {
+                for flag in [FLAG_ALIVE, FLAG_DEAD] {
+                    self.add_address_transition(
+                        from,
+                        flag,
+                        (flag, TuringDirection::Right),
+                        DetState::Simulate(0),
+                    )?;
+                }
+            }
+            DetState::Final(_) => {}
+        }
+        Ok(())
+    }
+
+    /// The characters that can be found under the head of the address tape while simulating a branch.
+    fn address_chars(&self) -> Vec<char> {
+        let mut chars = self.digits.clone();
+        chars.push(BLANK_CHAR);
+        chars
+    }
+
+    fn add_transition(
+        &mut self,
+        from: usize,
+        read: Vec<char>,
+        move_read: TuringDirection,
+        write: Vec<(char, TuringDirection)>,
+        to: DetState,
+    ) -> Result<(), TuringError> {
+        let to = self.get_index(to);
+        self.result
+            .append_rule_state(from, TuringTransition::new(read, move_read, write), to)
+    }
+
+    /// Adds a transition that doesn't change anything.
+    fn add_stay_transition(
+        &mut self,
+        from: usize,
+        read: Vec<char>,
+        to: DetState,
+    ) -> Result<(), TuringError> {
+        let write = read[1..].iter().map(|c| (*c, TuringDirection::None));
+        self.add_transition(
+            from,
+            read.clone(),
+            TuringDirection::None,
+            write.collect(),
+            to,
+        )
+    }
+
+    /// Adds a transition that only changes the given tape (`0` being the reading tape).
+    fn add_tape_transition(
+        &mut self,
+        from: usize,
+        tape: usize,
+        read: Vec<char>,
+        tape_write: (char, TuringDirection),
+        to: DetState,
+    ) -> Result<(), TuringError> {
+        let mut write: Vec<(char, TuringDirection)> = read[1..]
+            .iter()
+            .map(|c| (*c, TuringDirection::None))
+            .collect();
+        write[tape - 1] = tape_write;
+        self.add_transition(from, read, TuringDirection::None, write, to)
+    }
+
+    /// Adds a transition that only changes the address tape, every other tape being on its first cell.
+    fn add_address_transition(
+        &mut self,
+        from: usize,
+        address_read: char,
+        address_write: (char, TuringDirection),
+        to: DetState,
+    ) -> Result<(), TuringError> {
+        let mut read = vec![INIT_CHAR; self.k + 1];
+        read.push(address_read);
+        self.add_tape_transition(from, self.k + 1, read, address_write, to)
+    }
+
+    /// Returns the index of the given state in the generated graph, creating it if needed.
+    fn get_index(&mut self, state: DetState) -> usize {
+        if let Some(index) = self.indexes.get(&state) {
+            return *index;
+        }
+        let graph = self.graph;
+        let states = graph.get_states();
+        let index = match &state {
+            DetState::Init => 0,
+            DetState::Final(index) => *index,
+            DetState::Simulate(0) => self.result.add_fresh_state("i"),
+            DetState::Simulate(index) => self.result.add_state(&states[*index].name),
+            DetState::InitFlag => self.result.add_fresh_state("flag"),
+            DetState::RewindInput { .. } => self.result.add_fresh_state("rewind"),
+            DetState::EraseRight { .. } | DetState::EraseLeft { .. } => {
+                self.result.add_fresh_state("erase")
+            }
+            DetState::AddressEnd { .. } | DetState::Append => self.result.add_fresh_state("end"),
+            DetState::Increment { .. } => self.result.add_fresh_state("next"),
+            DetState::Rewind { .. } | DetState::RewindStart | DetState::SkipFlag => {
+                self.result.add_fresh_state("back")
+            }
+        };
+        self.indexes.insert(state.clone(), index);
+        self.to_visit.push_back(state);
+        index
+    }
+}
+
+/// Returns a [TuringError::IllegalActionError] if the given input alphabet contains a character that cannot be part of an input.
+pub(crate) fn check_input_alphabet(alphabet: &[char]) -> Result<(), TuringError> {
+    match alphabet
+        .iter()
+        .find(|c| [INIT_CHAR, BLANK_CHAR, END_CHAR].contains(c))
+    {
+        Some(c) => Err(TuringError::IllegalActionError {
+            cause: format!("The character '{c}' cannot be part of an input"),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Returns every combination of characters taking one character from each alphabet.
+pub(crate) fn product(alphabets: &[Vec<char>]) -> Vec<Vec<char>> {
+    let mut combinations: Vec<Vec<char>> = vec![vec![]];
+    for chars in alphabets {
+        combinations = combinations
+            .iter()
+            .flat_map(|prev| {
+                chars.iter().map(move |c| {
+                    let mut next = prev.clone();
+                    next.push(*c);
+                    next
+                })
+            })
+            .collect();
+    }
+    combinations
+}
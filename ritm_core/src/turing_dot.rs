@@ -0,0 +1,153 @@
+use crate::{
+    turing_graph::TuringMachineGraph,
+    turing_machine::TuringExecutionSteps,
+    turing_state::{TuringStateType, TuringTransition},
+};
+
+/// The options used by [graph_to_dot] to highlight a part of the graph.
+#[derive(Debug, Clone, Default)]
+pub struct DotOptions {
+    /// The index of the state to highlight, for example the current state of an execution
+    pub current_state: Option<usize>,
+    /// The transitions to highlight, given as the index of the state they leave and their index inside of this state
+    pub path: Vec<(usize, usize)>,
+}
+
+impl DotOptions {
+    /// Creates options highlighting all the transitions taken during the given steps, along with the state reached by the last one.
+    ///
+    /// The steps must be given in the order they were taken, like the ones returned by [crate::turing_machine::TuringMachines::get_path_to_accept].
+    pub fn from_path(steps: &[TuringExecutionSteps]) -> Self {
+        let mut options = Self::default();
+        let mut state = 0;
+        for step in steps {
+            match step {
+                TuringExecutionSteps::FirstIteration { .. } => state = 0,
+                TuringExecutionSteps::TransitionTaken {
+                    state_pointer,
+                    transition_index_taken,
+                    ..
+                } => {
+                    options.path.push((state, *transition_index_taken));
+                    state = *state_pointer;
+                }
+                TuringExecutionSteps::Backtracked { state_pointer, .. } => state = *state_pointer,
+            }
+        }
+        if !steps.is_empty() {
+            options.current_state = Some(state);
+        }
+        options
+    }
+}
+
+/// Turns the given [TuringMachineGraph] into a graph written in the DOT language of Graphviz.
+///
+/// All the transitions going from one state to another are grouped into a single edge, labelled with one transition per line.
+/// Accepting states are drawn with a green double circle and rejecting states with a red double octagon.
+/// The state and the transitions given in the [DotOptions] are highlighted in blue, an edge is highlighted if one of its transitions is.
+pub fn graph_to_dot(graph: &TuringMachineGraph, options: &DotOptions) -> String {
+    let mut res = String::from("digraph turing_machine {\n");
+    res.push_str("    rankdir=LR;\n");
+    res.push_str("    node [shape=circle];\n");
+    res.push_str("    start [shape=point];\n");
+    res.push_str(&format!(
+        "    start -> {};\n",
+        node_id(&graph.get_states()[0].name)
+    ));
+
+    // The states reached by the highlighted transitions
+    let on_path: Vec<usize> = options
+        .path
+        .iter()
+        .flat_map(|(from, index)| {
+            let to = graph.get_states()[*from]
+                .transitions
+                .get(*index)
+                .and_then(|t| t.index_to_state);
+            [Some(*from), to]
+        })
+        .flatten()
+        .collect();
+
+    for (index, state) in graph.get_states().iter().enumerate() {
+        let mut attributes = vec![];
+        match state.state_type {
+            TuringStateType::Accepting => {
+                attributes.push("shape=doublecircle".to_string());
+                attributes.push("color=darkgreen".to_string());
+            }
+            TuringStateType::Rejecting => {
+                attributes.push("shape=doubleoctagon".to_string());
+                attributes.push("color=red".to_string());
+            }
+            TuringStateType::Normal => {}
+        }
+        if options.current_state == Some(index) {
+            attributes.push("style=filled".to_string());
+            attributes.push("fillcolor=lightblue".to_string());
+        }
+        if on_path.contains(&index) {
+            attributes.push("penwidth=2".to_string());
+        }
+        res.push_str(&format!(
+            "    {}{};\n",
+            node_id(&state.name),
+            to_attribute_list(&attributes)
+        ));
+    }
+
+    for (index, state) in graph.get_states().iter().enumerate() {
+        // The transitions grouped by the state they go to, in the order they appear
+        let mut edges: Vec<(usize, Vec<(usize, &TuringTransition)>)> = vec![];
+        for (transition_index, transition) in state.transitions.iter().enumerate() {
+            let to = transition.index_to_state.unwrap();
+            match edges.iter_mut().find(|(target, _)| *target == to) {
+                Some((_, transitions)) => transitions.push((transition_index, transition)),
+                None => edges.push((to, vec![(transition_index, transition)])),
+            }
+        }
+
+        for (to, transitions) in edges {
+            let label: Vec<String> = transitions
+                .iter()
+                .map(|(_, transition)| escape(&transition.to_string()))
+                .collect();
+            let mut attributes = vec![format!("label=\"{}\"", label.join("\\n"))];
+            if transitions
+                .iter()
+                .any(|(i, _)| options.path.contains(&(index, *i)))
+            {
+                attributes.push("color=blue".to_string());
+                attributes.push("fontcolor=blue".to_string());
+                attributes.push("penwidth=2".to_string());
+            }
+            res.push_str(&format!(
+                "    {} -> {}{};\n",
+                node_id(&state.name),
+                node_id(&graph.get_states()[to].name),
+                to_attribute_list(&attributes)
+            ));
+        }
+    }
+
+    res.push_str("}\n");
+    res
+}
+
+fn node_id(name: &str) -> String {
+    format!("\"q_{}\"", escape(name))
+}
+
+fn to_attribute_list(attributes: &[String]) -> String {
+    if attributes.is_empty() {
+        String::new()
+    } else {
+        format!(" [{}]", attributes.join(", "))
+    }
+}
+
+/// Escapes the characters that cannot be written as they are inside of a quoted DOT string.
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
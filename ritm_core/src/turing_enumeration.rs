@@ -0,0 +1,51 @@
+use crate::{
+    turing_equivalence::{RunOutcome, run, words_up_to},
+    turing_errors::TuringError,
+    turing_graph::TuringMachineGraph,
+};
+
+/// The words of a given length range, sorted by the answer of a machine.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LanguageEnumeration {
+    /// The characters the words are made of
+    pub alphabet: Vec<char>,
+    /// The words accepted by the machine
+    pub accepted: Vec<String>,
+    /// The words rejected by the machine
+    pub rejected: Vec<String>,
+    /// The words for which the machine ran out of steps before giving an answer
+    pub undecided: Vec<String>,
+}
+
+/// Runs the machine on every word up to the given length, and sorts them by the answer of the machine.
+///
+/// The words are made of the given alphabet, or of the characters read by the machine (see [TuringMachineGraph::get_input_alphabet]) if none is given.
+/// They are listed by length, then in lexicographic order. Each run is stopped after `step_budget` steps, backtracking included.
+///
+/// If the alphabet contains a character that cannot be part of an input, the [TuringError] raised when creating the execution is returned.
+pub fn enumerate_language(
+    graph: &TuringMachineGraph,
+    alphabet: Option<&[char]>,
+    max_len: usize,
+    step_budget: usize,
+) -> Result<LanguageEnumeration, TuringError> {
+    let mut alphabet = match alphabet {
+        Some(alphabet) => alphabet.to_vec(),
+        None => graph.get_input_alphabet(),
+    };
+    alphabet.sort();
+    alphabet.dedup();
+
+    let mut enumeration = LanguageEnumeration {
+        alphabet,
+        ..Default::default()
+    };
+    for word in words_up_to(&enumeration.alphabet, max_len) {
+        match run(graph, &word, step_budget)?.outcome {
+            RunOutcome::Accepted => enumeration.accepted.push(word),
+            RunOutcome::Rejected => enumeration.rejected.push(word),
+            RunOutcome::OutOfBudget => enumeration.undecided.push(word),
+        }
+    }
+    Ok(enumeration)
+}
@@ -0,0 +1,209 @@
+use std::{fmt::Display, thread};
+
+use crate::{
+    turing_errors::TuringError,
+    turing_graph::TuringMachineGraph,
+    turing_machine::{Mode, TuringExecutionSteps, TuringMachines},
+    turing_state::TuringStateType,
+};
+
+/// How the run of a machine on a word ended.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RunOutcome {
+    /// An accepting state was reached
+    Accepted,
+    /// Every path of the execution ended without reaching an accepting state
+    Rejected,
+    /// The execution was stopped after taking the maximum number of steps allowed
+    OutOfBudget,
+}
+
+impl Display for RunOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                RunOutcome::Accepted => "Accepted",
+                RunOutcome::Rejected => "Rejected",
+                RunOutcome::OutOfBudget => "Out of budget",
+            }
+        )
+    }
+}
+
+/// The result of the run of a machine on a word.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunResult {
+    pub outcome: RunOutcome,
+    /// The number of steps taken, backtracking included
+    pub steps: usize,
+}
+
+/// The results of both machines on a word.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordComparison {
+    pub word: String,
+    pub first: RunResult,
+    pub second: RunResult,
+}
+
+impl WordComparison {
+    /// Returns true if the machines disagree on this word, or if one of them ran out of budget.
+    pub fn is_counterexample(&self) -> bool {
+        self.first.outcome != self.second.outcome
+            || self.first.outcome == RunOutcome::OutOfBudget
+            || self.second.outcome == RunOutcome::OutOfBudget
+    }
+}
+
+/// The result of [check_equivalence].
+#[derive(Debug, Clone, PartialEq)]
+pub struct EquivalenceReport {
+    /// The number of words both machines were run on
+    pub words_checked: usize,
+    /// The words the machines disagree on, or where one of them ran out of budget, shortest words first
+    pub counterexamples: Vec<WordComparison>,
+}
+
+impl EquivalenceReport {
+    /// Returns true if both machines gave the same answer for every word checked.
+    pub fn is_equivalent(&self) -> bool {
+        self.counterexamples.is_empty()
+    }
+}
+
+/// Runs both machines on every word made of the given alphabet, up to the given length, and reports the words where they disagree.
+///
+/// Each run is stopped after `step_budget` steps, backtracking included, so machines looping forever can be checked.
+/// A word where one of the machines ran out of budget is always reported, since nothing can be said about it.
+/// The words are split between threads, the counterexamples are returned ordered by length then in the order of the alphabet.
+///
+/// If the alphabet contains a character that cannot be part of an input, the [TuringError] raised when creating the execution is returned.
+pub fn check_equivalence(
+    first: &TuringMachineGraph,
+    second: &TuringMachineGraph,
+    alphabet: &[char],
+    max_len: usize,
+    step_budget: usize,
+) -> Result<EquivalenceReport, TuringError> {
+    let words = words_up_to(alphabet, max_len);
+    let threads = thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(words.len());
+
+    // Each thread checks every `threads` word, so that long words are spread between them
+    let results: Vec<Result<Vec<(usize, WordComparison)>, TuringError>> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|offset| {
+                let words = &words;
+                scope.spawn(move || {
+                    let mut counterexamples = vec![];
+                    for (index, word) in words.iter().enumerate().skip(offset).step_by(threads) {
+                        let comparison = WordComparison {
+                            word: word.clone(),
+                            first: run(first, word, step_budget)?,
+                            second: run(second, word, step_budget)?,
+                        };
+                        if comparison.is_counterexample() {
+                            counterexamples.push((index, comparison));
+                        }
+                    }
+                    Ok(counterexamples)
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    });
+
+    let mut counterexamples = vec![];
+    for result in results {
+        counterexamples.extend(result?);
+    }
+    counterexamples.sort_by_key(|(index, _)| *index);
+
+    Ok(EquivalenceReport {
+        words_checked: words.len(),
+        counterexamples: counterexamples
+            .into_iter()
+            .map(|(_, comparison)| comparison)
+            .collect(),
+    })
+}
+
+/// Runs the machine on the given word, exploring every path until an accepting state is reached or the budget is spent.
+pub fn run(
+    graph: &TuringMachineGraph,
+    word: &str,
+    step_budget: usize,
+) -> Result<RunResult, TuringError> {
+    run_with_last_step(graph, word, step_budget).map(|(result, _)| result)
+}
+
+/// Same as [run], but also returns the last step taken, holding the content of the tapes at the end of the execution.
+pub fn run_with_last_step(
+    graph: &TuringMachineGraph,
+    word: &str,
+    step_budget: usize,
+) -> Result<(RunResult, Option<TuringExecutionSteps>), TuringError> {
+    run_inspecting(graph, word, Mode::SaveAll, step_budget, |_| {})
+}
+
+/// Same as [run_with_last_step], but the machine is executed in the given [Mode],
+/// and every step it produces is given to `inspect`, the first iteration included.
+pub fn run_inspecting(
+    graph: &TuringMachineGraph,
+    word: &str,
+    mode: Mode,
+    step_budget: usize,
+    mut inspect: impl FnMut(&TuringExecutionSteps),
+) -> Result<(RunResult, Option<TuringExecutionSteps>), TuringError> {
+    let mut tm = TuringMachines::new(graph.clone(), word.to_string(), mode)?;
+    let mut steps = 0;
+    let mut last_step = None;
+    for step in &mut tm {
+        inspect(&step);
+        if let TuringExecutionSteps::FirstIteration { .. } = step {
+            continue;
+        }
+        if steps == step_budget {
+            let result = RunResult {
+                outcome: RunOutcome::OutOfBudget,
+                steps,
+            };
+            return Ok((result, last_step));
+        }
+        steps += 1;
+        last_step = Some(step);
+    }
+    let accepted = last_step
+        .as_ref()
+        .is_some_and(|step| step.get_current_state().state_type == TuringStateType::Accepting);
+    let result = RunResult {
+        outcome: if accepted {
+            RunOutcome::Accepted
+        } else {
+            RunOutcome::Rejected
+        },
+        steps,
+    };
+    Ok((result, last_step))
+}
+
+/// Returns all the words made of the given characters up to the given length, shortest words first.
+pub fn words_up_to(alphabet: &[char], max_len: usize) -> Vec<String> {
+    let mut words = vec![String::new()];
+    let mut last = vec![String::new()];
+    for _ in 0..max_len {
+        last = last
+            .iter()
+            .flat_map(|word| alphabet.iter().map(move |c| format!("{word}{c}")))
+            .collect();
+        words.extend(last.iter().cloned());
+    }
+    words
+}
@@ -112,6 +112,8 @@ pub enum TuringParserError {
         turing_error: TuringError,
         value: String,
     },
+    /// Error when the given value is valid, but uses a construct that cannot be converted into a [crate::turing_graph::TuringMachineGraph]
+    UnsupportedConstructError { construct: String, reason: String },
 }
 
 impl Display for TuringParserError {
@@ -149,6 +151,10 @@ impl Display for TuringParserError {
                     get_arrow_under(value, line_col_pos),
                     turing_error
                 ),
+                TuringParserError::UnsupportedConstructError { construct, reason } => format!(
+                    "The following construct is not supported : \"{}\". The reason being : {}",
+                    construct, reason
+                ),
             }
         })
     }
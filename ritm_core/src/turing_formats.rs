@@ -0,0 +1,81 @@
+use std::{fs, path::Path};
+
+#[cfg(feature = "serde")]
+use crate::turing_json::{GraphMetadata, graph_to_json, parse_json_graph_string};
+use crate::{
+    turing_errors::TuringParserError,
+    turing_graph::TuringMachineGraph,
+    turing_jflap::{graph_to_jflap, parse_jflap_string},
+    turing_parser::{graph_to_string, parse_turing_graph_string},
+};
+
+/// The formats a graph can be stored in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GraphFormat {
+    /// The text format of RITM, see [crate::turing_parser]
+    Tm,
+    /// A JFLAP `.jff` file, see [crate::turing_jflap]
+    Jflap,
+    /// The JSON interchange format, see [crate::turing_json]
+    #[cfg(feature = "serde")]
+    Json,
+}
+
+impl GraphFormat {
+    /// Detects the format of a file from its extension : `.jff` for JFLAP, `.json` for JSON and the text format for any other extension.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("jff") => GraphFormat::Jflap,
+            #[cfg(feature = "serde")]
+            Some("json") => GraphFormat::Json,
+            _ => GraphFormat::Tm,
+        }
+    }
+}
+
+/// Reads the file at the given path and parses the graph it contains, the format being detected from its extension (see [GraphFormat::from_path]).
+pub fn load_graph_by_extension(path: &Path) -> Result<TuringMachineGraph, TuringParserError> {
+    match fs::read_to_string(path) {
+        Ok(content) => parse_graph_by_extension(path, content),
+        Err(e) => Err(TuringParserError::FileError {
+            given_path: path.to_string_lossy().to_string(),
+            error_reason: e.to_string(),
+        }),
+    }
+}
+
+/// Parses the content of the file at the given path, the format being detected from its extension (see [GraphFormat::from_path]).
+///
+/// The positions of the states of a JFLAP file are stored in the metadata of the states.
+pub fn parse_graph_by_extension(
+    path: &Path,
+    content: String,
+) -> Result<TuringMachineGraph, TuringParserError> {
+    match GraphFormat::from_path(path) {
+        GraphFormat::Tm => parse_turing_graph_string(content),
+        GraphFormat::Jflap => {
+            let mut machine = parse_jflap_string(content)?;
+            for (name, position) in machine.positions {
+                // The positions are given using the names of the states of the graph
+                if let Ok(metadata) = machine.graph.get_state_metadata_mut(&name) {
+                    metadata.position = Some(position);
+                }
+            }
+            Ok(machine.graph)
+        }
+        #[cfg(feature = "serde")]
+        GraphFormat::Json => parse_json_graph_string(content).map(|machine| machine.graph),
+    }
+}
+
+/// Writes the given graph in the format of the file at the given path, detected from its extension (see [GraphFormat::from_path]).
+///
+/// The positions stored in the metadata of the states are kept by every format.
+pub fn graph_to_string_by_extension(graph: &TuringMachineGraph, path: &Path) -> String {
+    match GraphFormat::from_path(path) {
+        GraphFormat::Tm => graph_to_string(graph),
+        GraphFormat::Jflap => graph_to_jflap(graph, &graph.get_state_positions()),
+        #[cfg(feature = "serde")]
+        GraphFormat::Json => graph_to_json(graph, &GraphMetadata::default()),
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{
+    turing_errors::TuringParserError,
+    turing_parser::{parse_transition_string, parse_turing_graph_string, transitions_to_string},
+};
+
+/// A piece of a source file, kept in the order it was written.
+enum Item {
+    /// A comment, embedded tests included, written without its line break
+    Comment(String),
+    /// An annotation, like `@pos(q_1, 12, 34)`
+    Annotation(String),
+    /// A transition statement, without its `;`
+    Transition(String),
+}
+
+/// Formats the source of a turing machine, written in the same format as the one read by [parse_turing_graph_string].
+///
+/// Every transition statement is written on its own, with one transition per line like in [crate::turing_parser::graph_to_string],
+/// and every annotation is written on its own line. Unlike [crate::turing_parser::graph_to_string], the order of the statements and the comments are kept.
+/// A comment written after a statement stays on its line, and multiple empty lines are merged into one.
+///
+/// If the source cannot be parsed, the error returned by the parser is returned and nothing is formatted.
+pub fn format_turing_graph_string(source: String) -> Result<String, TuringParserError> {
+    parse_turing_graph_string(source.clone())?;
+
+    let mut res = String::new();
+    // The number of line breaks read since the last item
+    let mut line_breaks = 0;
+    let mut chars = source.chars().peekable();
+    let mut items: Vec<(usize, Item)> = vec![];
+
+    while let Some(&c) = chars.peek() {
+        if c == '\n' {
+            line_breaks += 1;
+            chars.next();
+        } else if c.is_whitespace() {
+            chars.next();
+        } else if c == '/' {
+            let comment: String = chars.by_ref().take_while(|c| *c != '\n').collect();
+            items.push((line_breaks, Item::Comment(comment.trim_end().to_string())));
+            // The line break ending the comment was consumed
+            line_breaks = 1;
+        } else if c == '@' {
+            let mut annotation = String::new();
+            let mut in_string = false;
+            let mut escaped = false;
+            for c in chars.by_ref() {
+                annotation.push(c);
+                match c {
+                    _ if escaped => escaped = false,
+                    '\\' if in_string => escaped = true,
+                    '"' => in_string = !in_string,
+                    ')' if !in_string => break,
+                    _ => {}
+                }
+            }
+            items.push((line_breaks, Item::Annotation(annotation)));
+            line_breaks = 0;
+        } else {
+            // The comments written inside of a statement are moved before it
+            let mut transition = String::new();
+            let mut comments = vec![];
+            while let Some(c) = chars.next() {
+                match c {
+                    ';' => break,
+                    '/' if chars.peek() == Some(&'/') => {
+                        let comment: String = chars.by_ref().take_while(|c| *c != '\n').collect();
+                        comments.push(format!("/{}", comment.trim_end()));
+                        transition.push('\n');
+                    }
+                    c => transition.push(c),
+                }
+            }
+            for comment in comments {
+                items.push((line_breaks, Item::Comment(comment)));
+                line_breaks = 1;
+            }
+            items.push((line_breaks, Item::Transition(transition)));
+            line_breaks = 0;
+        }
+    }
+
+    for (index, (line_breaks, item)) in items.into_iter().enumerate() {
+        let text = match item {
+            Item::Comment(comment) => comment,
+            Item::Annotation(annotation) => format_annotation(&annotation),
+            Item::Transition(transition) => {
+                // The whole source was parsed, so every statement is valid
+                let (from, transitions, to) = parse_transition_string(transition).unwrap();
+                let transitions: Vec<_> = transitions.iter().collect();
+                transitions_to_string(&from, &transitions, &to)
+            }
+        };
+        if index > 0 {
+            match line_breaks {
+                // Only comments can be kept on the line of the previous item
+                0 if text.starts_with("//") => res.push(' '),
+                0 | 1 => res.push('\n'),
+                _ => res.push_str("\n\n"),
+            }
+        }
+        res.push_str(&text);
+    }
+    if !res.is_empty() {
+        res.push('\n');
+    }
+    Ok(res)
+}
+
+/// Writes the arguments of an annotation separated by `, `, leaving the strings untouched.
+fn format_annotation(annotation: &str) -> String {
+    let Some(open) = annotation.find('(') else {
+        return annotation.to_string();
+    };
+    let mut args = vec![];
+    let mut current = String::new();
+    let mut in_string = false;
+    let mut escaped = false;
+    for c in annotation[open + 1..annotation.len() - 1].chars() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            ',' if !in_string => {
+                args.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    args.push(current.trim().to_string());
+    format!("{}({})", annotation[..open].trim(), args.join(", "))
+}
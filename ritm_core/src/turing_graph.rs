@@ -1,6 +1,7 @@
 use crate::{
     turing_errors::TuringError,
     turing_state::{TuringState, TuringStateType, TuringTransition},
+    turing_tape::{BLANK_CHAR, END_CHAR, INIT_CHAR},
 };
 use std::{
     collections::HashMap,
@@ -16,6 +17,38 @@ pub struct TuringMachineGraph {
     states: Vec<TuringState>,
     /// The number of tapes this graph was made for
     k: usize,
+    /// The metadata of the states, using their name
+    metadata: HashMap<String, StateMetadata>,
+    /// The tests written next to the machine, like `// @accept 0110`
+    embedded_tests: Vec<EmbeddedTest>,
+}
+
+/// Informations about a state that have no effect on its execution, like its layout in the GUI.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StateMetadata {
+    /// The position of the state in the GUI
+    pub position: Option<(f32, f32)>,
+    /// The color of the state, as an hexadecimal `#rrggbb` string
+    pub color: Option<String>,
+    /// A description of what the state does
+    pub doc: Option<String>,
+}
+
+/// A test written next to the machine, like `// @accept 0110`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmbeddedTest {
+    pub word: String,
+    /// Whether the word must be accepted or rejected
+    pub accept: bool,
+    /// The line of the code of the machine where this test is written
+    pub line: usize,
+}
+
+impl StateMetadata {
+    /// Returns true if no metadata is set.
+    pub fn is_empty(&self) -> bool {
+        self.position.is_none() && self.color.is_none() && self.doc.is_none()
+    }
 }
 
 impl TuringMachineGraph {
@@ -46,6 +79,8 @@ impl TuringMachineGraph {
             name_index_hashmap,
             states: vec![init_state, accepting_state, rejecting_state],
             k,
+            metadata: HashMap::new(),
+            embedded_tests: vec![],
         })
     }
 
@@ -150,6 +185,20 @@ impl TuringMachineGraph {
         }
     }
 
+    /// Adds a new state whose name starts with the given prefix and is not already used, then returns its index.
+    ///
+    /// Useful when generating graphs, the prefix must only contain ASCII alphanumeric characters for the graph to remain parsable.
+    pub fn add_fresh_state(&mut self, prefix: &str) -> usize {
+        let mut suffix = self.states.len();
+        while self
+            .name_index_hashmap
+            .contains_key(&format!("{prefix}{suffix}"))
+        {
+            suffix += 1;
+        }
+        self.add_state(&format!("{prefix}{suffix}"))
+    }
+
     /// Adds a new state to the turing machine graph using variables indexes
     fn add_rule_state_ind(
         &mut self,
@@ -218,6 +267,48 @@ impl TuringMachineGraph {
         }
     }
 
+    /// Returns the metadata of the state that has the given name, if it has any.
+    pub fn get_state_metadata(&self, name: &str) -> Option<&StateMetadata> {
+        self.metadata.get(name)
+    }
+
+    /// Returns the **mutable** metadata of the state that has the given name, creating an empty one if needed.
+    ///
+    /// If the state does not exist, a [TuringError::UnknownStateError] is returned.
+    pub fn get_state_metadata_mut(
+        &mut self,
+        name: &String,
+    ) -> Result<&mut StateMetadata, TuringError> {
+        if !self.name_index_hashmap.contains_key(name) {
+            return Err(TuringError::UnknownStateError {
+                state_name: name.to_string(),
+            });
+        }
+        Ok(self.metadata.entry(name.to_string()).or_default())
+    }
+
+    /// Returns the positions stored in the metadata of the states, using the names of the states.
+    pub fn get_state_positions(&self) -> HashMap<String, (f32, f32)> {
+        self.metadata
+            .iter()
+            .filter_map(|(name, metadata)| {
+                metadata.position.map(|position| (name.clone(), position))
+            })
+            .collect()
+    }
+
+    /// Returns the tests embedded in the machine, in the order they were added.
+    pub fn get_embedded_tests(&self) -> &Vec<EmbeddedTest> {
+        &self.embedded_tests
+    }
+
+    /// Adds a test to the ones embedded in the machine.
+    ///
+    /// They are written back by [crate::turing_parser::graph_to_string] and checked by [crate::turing_test_suite::run_embedded_tests].
+    pub fn add_embedded_test(&mut self, test: EmbeddedTest) {
+        self.embedded_tests.push(test);
+    }
+
     /// Get the transition index between two nodes if it exists.
     pub fn get_transition_indexes_by_name(
         &self,
@@ -410,9 +501,155 @@ impl TuringMachineGraph {
         }
 
         self.name_index_hashmap.remove(&state_name);
+        self.metadata.remove(&state_name);
+        Ok(())
+    }
+
+    /// Renames a state, keeping its index, its transitions and its metadata.
+    ///
+    /// The default states cannot be renamed, and the new name must be unused and only contain ASCII letters and digits for the graph to remain parsable.
+    pub fn rename_state(&mut self, name: &String, new_name: &String) -> Result<(), TuringError> {
+        let index = self.get_editable_state_index(name, "rename")?;
+        if new_name.is_empty() || !new_name.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(TuringError::IllegalActionError {
+                cause: format!(
+                    "Tried to rename the state q_{name} to \"{new_name}\", that does not only contain ASCII letters and digits."
+                ),
+            });
+        }
+        if self.name_index_hashmap.contains_key(new_name) {
+            return Err(TuringError::IllegalActionError {
+                cause: format!(
+                    "Tried to rename the state q_{name} to q_{new_name}, that already exists."
+                ),
+            });
+        }
+
+        self.name_index_hashmap.remove(name);
+        self.name_index_hashmap.insert(new_name.to_string(), index);
+        self.states[index].rename(new_name);
+        if let Some(metadata) = self.metadata.remove(name) {
+            self.metadata.insert(new_name.to_string(), metadata);
+        }
+        Ok(())
+    }
+
+    /// Changes the type of a state and returns its previous type.
+    ///
+    /// The types of the default states cannot be changed.
+    pub fn set_state_type(
+        &mut self,
+        name: &String,
+        state_type: TuringStateType,
+    ) -> Result<TuringStateType, TuringError> {
+        let index = self.get_editable_state_index(name, "change the type of")?;
+        Ok(std::mem::replace(
+            &mut self.states[index].state_type,
+            state_type,
+        ))
+    }
+
+    /// Replaces the first transition of the form `from {transition} to` by `from {new_transition} new_to`, keeping its place among the transitions of `from`.
+    ///
+    /// If no such transition exists, a [TuringError::IllegalActionError] is returned.
+    pub fn replace_transition(
+        &mut self,
+        from: &String,
+        transition: &TuringTransition,
+        to: &String,
+        mut new_transition: TuringTransition,
+        new_to: &String,
+    ) -> Result<(), TuringError> {
+        if new_transition.get_number_of_affected_tapes() != (self.k + 1) {
+            return Err(TuringError::IncompatibleTransitionError {
+                expected: self.get_k(),
+                received: new_transition.get_number_of_affected_tapes() - 1,
+            });
+        }
+        let new_to_index = match self.name_index_hashmap.get(new_to) {
+            Some(index) => *index,
+            None => {
+                return Err(TuringError::UnknownStateError {
+                    state_name: new_to.to_string(),
+                });
+            }
+        };
+        let (from_state, to_index) = self.fetch_n1_state_n2_index(from, to)?;
+
+        let Some(replaced) = from_state
+            .transitions
+            .iter_mut()
+            .find(|t| *t == transition && t.index_to_state == Some(to_index))
+        else {
+            return Err(TuringError::IllegalActionError {
+                cause: format!(
+                    "Tried to replace the transition q_{from} {{{transition}}} q_{to} that does not exist."
+                ),
+            });
+        };
+        new_transition.index_to_state = Some(new_to_index);
+        *replaced = new_transition;
         Ok(())
     }
 
+    /// Returns the index of the state with the given name, if it is not one of the default states.
+    fn get_editable_state_index(&self, name: &String, action: &str) -> Result<usize, TuringError> {
+        let index = match self.name_index_hashmap.get(name) {
+            Some(index) => *index,
+            None => {
+                return Err(TuringError::UnknownStateError {
+                    state_name: name.to_string(),
+                });
+            }
+        };
+        if index <= 2 {
+            return Err(TuringError::IllegalActionError {
+                cause: format!("Tried to {action} the state q_{name}."),
+            });
+        }
+        Ok(index)
+    }
+
+    /// Checks if this graph is deterministic, meaning that no state has two transitions reading the same characters.
+    pub fn is_deterministic(&self) -> bool {
+        self.states.iter().all(|state| {
+            state.transitions.iter().enumerate().all(|(i, t1)| {
+                state.transitions[..i]
+                    .iter()
+                    .all(|t2| t1.chars_read != t2.chars_read)
+            })
+        })
+    }
+
+    /// Returns the characters read on the reading tape by the transitions of this graph, without [INIT_CHAR] and [END_CHAR].
+    ///
+    /// Since the input alphabet is not part of the graph, this is the alphabet of the words this graph can actually read.
+    pub fn get_input_alphabet(&self) -> Vec<char> {
+        let mut alphabet: Vec<char> = self
+            .states
+            .iter()
+            .flat_map(|s| s.transitions.iter().map(|t| t.chars_read[0]))
+            .filter(|c| *c != INIT_CHAR && *c != END_CHAR)
+            .collect();
+        alphabet.sort();
+        alphabet.dedup();
+        alphabet
+    }
+
+    /// Returns all the characters that can be found on the given writing tape (starting from `0`) :
+    /// the characters written on it by the transitions of this graph, along with [INIT_CHAR] and [BLANK_CHAR].
+    pub fn get_tape_alphabet(&self, tape: usize) -> Vec<char> {
+        let mut alphabet: Vec<char> = self
+            .states
+            .iter()
+            .flat_map(|s| s.transitions.iter().map(|t| t.chars_write[tape].0))
+            .chain([INIT_CHAR, BLANK_CHAR])
+            .collect();
+        alphabet.sort();
+        alphabet.dedup();
+        alphabet
+    }
+
     pub fn get_k(&self) -> usize {
         self.k
     }
@@ -426,6 +663,25 @@ impl TuringMachineGraph {
     }
 }
 
+impl PartialEq for TuringMachineGraph {
+    /// Checks if two graphs are identical, their states and transitions being in the same order.
+    ///
+    /// Unlike the comparison of two [TuringTransition]s, the states the transitions go to are compared.
+    fn eq(&self, other: &Self) -> bool {
+        self.k == other.k
+            && self.states == other.states
+            && self.states.iter().zip(&other.states).all(|(state, other)| {
+                state
+                    .transitions
+                    .iter()
+                    .zip(&other.transitions)
+                    .all(|(t1, t2)| t1.index_to_state == t2.index_to_state)
+            })
+            && self.metadata == other.metadata
+            && self.embedded_tests == other.embedded_tests
+    }
+}
+
 impl Display for TuringMachineGraph {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut res = String::from("States:\n");
@@ -0,0 +1,374 @@
+use std::fmt::Display;
+
+use crate::{
+    turing_errors::TuringError,
+    turing_graph::{StateMetadata, TuringMachineGraph},
+    turing_state::{TuringStateType, TuringTransition},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+/// A modification of a [TuringMachineGraph] that can be reverted.
+///
+/// The states are referred to using their names, so an edit stays valid when the indexes of the states change.
+pub enum GraphEdit {
+    /// Adds a state, doing nothing if it already exists.
+    AddState { name: String },
+    /// Removes a state and all the transitions going to it.
+    RemoveState { name: String },
+    /// Adds the transition `from {transition} to`.
+    AddTransition {
+        from: String,
+        transition: TuringTransition,
+        to: String,
+    },
+    /// Removes all the transitions `from {transition} to`.
+    RemoveTransition {
+        from: String,
+        transition: TuringTransition,
+        to: String,
+    },
+    /// Removes the transition at the given index among the transitions leaving the state `from`.
+    RemoveTransitionAt { from: String, index: usize },
+    /// Replaces the metadata of a state.
+    SetStateMetadata {
+        name: String,
+        metadata: StateMetadata,
+    },
+    /// Renames a state.
+    RenameState { name: String, new_name: String },
+    /// Changes the type of a state.
+    SetStateType {
+        name: String,
+        state_type: TuringStateType,
+    },
+    /// Replaces the transition `from {transition} to` by `from {new_transition} new_to`, keeping its place.
+    ReplaceTransition {
+        from: String,
+        transition: TuringTransition,
+        to: String,
+        new_transition: TuringTransition,
+        new_to: String,
+    },
+    /// Replaces the whole graph, for example by its minimized version.
+    ReplaceGraph {
+        graph: Box<TuringMachineGraph>,
+        /// What this replacement does, like `minimize the graph`
+        description: String,
+    },
+}
+
+impl GraphEdit {
+    /// Applies this edit to the given graph, and returns the edits that revert it, in the order they must be applied.
+    ///
+    /// When reverting the removal of a state, the state is added back at the end of the graph,
+    /// and the transitions going to it are added back after the other transitions of their states.
+    /// In the same way, a transition removed using its index is added back after the other transitions of its state.
+    /// The resulting graph is therefore equivalent to the original one, but its states and transitions may be ordered differently.
+    pub fn apply(&self, graph: &mut TuringMachineGraph) -> Result<Vec<GraphEdit>, TuringError> {
+        match self {
+            GraphEdit::AddState { name } => {
+                if graph.get_name_index_hashmap().contains_key(name) {
+                    return Ok(vec![]);
+                }
+                graph.add_state(name);
+                Ok(vec![GraphEdit::RemoveState { name: name.clone() }])
+            }
+            GraphEdit::RemoveState { name } => {
+                let index = match graph.get_name_index_hashmap().get(name) {
+                    Some(index) => *index,
+                    None => {
+                        return Err(TuringError::UnknownStateError {
+                            state_name: name.clone(),
+                        });
+                    }
+                };
+                let mut inverse = vec![GraphEdit::AddState { name: name.clone() }];
+                let state_type = graph.get_state(index)?.state_type.clone();
+                if state_type != TuringStateType::Normal {
+                    inverse.push(GraphEdit::SetStateType {
+                        name: name.clone(),
+                        state_type,
+                    });
+                }
+                if let Some(metadata) = graph.get_state_metadata(name) {
+                    inverse.push(GraphEdit::SetStateMetadata {
+                        name: name.clone(),
+                        metadata: metadata.clone(),
+                    });
+                }
+                // The transitions leaving the state come first to keep their order, then the ones going to it
+                let mut incoming = vec![];
+                for (from_index, state) in graph.get_states().iter().enumerate() {
+                    for transition in &state.transitions {
+                        let to_index = transition.index_to_state.unwrap();
+                        if from_index == index {
+                            inverse.push(GraphEdit::AddTransition {
+                                from: name.clone(),
+                                transition: transition.clone(),
+                                to: graph.get_state(to_index)?.name.clone(),
+                            });
+                        } else if to_index == index {
+                            incoming.push(GraphEdit::AddTransition {
+                                from: state.name.clone(),
+                                transition: transition.clone(),
+                                to: name.clone(),
+                            });
+                        }
+                    }
+                }
+                inverse.extend(incoming);
+
+                graph.remove_state_with_name(name)?;
+                Ok(inverse)
+            }
+            GraphEdit::AddTransition {
+                from,
+                transition,
+                to,
+            } => {
+                graph.append_rule_state_by_name(from, transition.clone(), to)?;
+                // Only the appended transition is removed, an identical one may already exist
+                let index = graph.get_state_from_name(from)?.transitions.len() - 1;
+                Ok(vec![GraphEdit::RemoveTransitionAt {
+                    from: from.clone(),
+                    index,
+                }])
+            }
+            GraphEdit::RemoveTransition {
+                from,
+                transition,
+                to,
+            } => {
+                let to_index = match graph.get_name_index_hashmap().get(to) {
+                    Some(index) => *index,
+                    None => {
+                        return Err(TuringError::UnknownStateError {
+                            state_name: to.clone(),
+                        });
+                    }
+                };
+                let removed = graph
+                    .get_state_from_name(from)?
+                    .transitions
+                    .iter()
+                    .filter(|t| *t == transition && t.index_to_state == Some(to_index))
+                    .count();
+                if removed == 0 {
+                    return Err(TuringError::IllegalActionError {
+                        cause: format!(
+                            "Tried to remove the transition q_{from} {{{transition}}} q_{to} that does not exist."
+                        ),
+                    });
+                }
+                graph.remove_transition(from, transition, to)?;
+                // Every identical transition was removed, so they are all added back
+                Ok(vec![
+                    GraphEdit::AddTransition {
+                        from: from.clone(),
+                        transition: transition.clone(),
+                        to: to.clone(),
+                    };
+                    removed
+                ])
+            }
+            GraphEdit::RemoveTransitionAt { from, index } => {
+                let from_index = match graph.get_name_index_hashmap().get(from) {
+                    Some(from_index) => *from_index,
+                    None => {
+                        return Err(TuringError::UnknownStateError {
+                            state_name: from.clone(),
+                        });
+                    }
+                };
+                let transition = graph
+                    .get_state_mut(from_index)?
+                    .remove_transition_with_index(*index)?;
+                let to = graph
+                    .get_state(transition.index_to_state.unwrap())?
+                    .name
+                    .clone();
+                Ok(vec![GraphEdit::AddTransition {
+                    from: from.clone(),
+                    transition,
+                    to,
+                }])
+            }
+            GraphEdit::SetStateMetadata { name, metadata } => {
+                let current = graph.get_state_metadata_mut(name)?;
+                let previous = std::mem::replace(current, metadata.clone());
+                Ok(vec![GraphEdit::SetStateMetadata {
+                    name: name.clone(),
+                    metadata: previous,
+                }])
+            }
+            GraphEdit::RenameState { name, new_name } => {
+                graph.rename_state(name, new_name)?;
+                Ok(vec![GraphEdit::RenameState {
+                    name: new_name.clone(),
+                    new_name: name.clone(),
+                }])
+            }
+            GraphEdit::SetStateType { name, state_type } => {
+                let previous = graph.set_state_type(name, state_type.clone())?;
+                Ok(vec![GraphEdit::SetStateType {
+                    name: name.clone(),
+                    state_type: previous,
+                }])
+            }
+            GraphEdit::ReplaceTransition {
+                from,
+                transition,
+                to,
+                new_transition,
+                new_to,
+            } => {
+                graph.replace_transition(from, transition, to, new_transition.clone(), new_to)?;
+                Ok(vec![GraphEdit::ReplaceTransition {
+                    from: from.clone(),
+                    transition: new_transition.clone(),
+                    to: new_to.clone(),
+                    new_transition: transition.clone(),
+                    new_to: to.clone(),
+                }])
+            }
+            GraphEdit::ReplaceGraph {
+                graph: new_graph,
+                description,
+            } => {
+                if **new_graph == *graph {
+                    return Ok(vec![]);
+                }
+                let previous = std::mem::replace(graph, (**new_graph).clone());
+                Ok(vec![GraphEdit::ReplaceGraph {
+                    graph: Box::new(previous),
+                    description: description.clone(),
+                }])
+            }
+        }
+    }
+}
+
+impl Display for GraphEdit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphEdit::AddState { name } => write!(f, "add the state q_{name}"),
+            GraphEdit::RemoveState { name } => write!(f, "remove the state q_{name}"),
+            GraphEdit::AddTransition {
+                from,
+                transition,
+                to,
+            } => write!(f, "add the transition q_{from} {{{transition}}} q_{to}"),
+            GraphEdit::RemoveTransition {
+                from,
+                transition,
+                to,
+            } => write!(f, "remove the transition q_{from} {{{transition}}} q_{to}"),
+            GraphEdit::RemoveTransitionAt { from, index } => {
+                write!(f, "remove the transition {index} of the state q_{from}")
+            }
+            GraphEdit::SetStateMetadata { name, metadata: _ } => {
+                write!(f, "change the metadata of the state q_{name}")
+            }
+            GraphEdit::RenameState { name, new_name } => {
+                write!(f, "rename the state q_{name} to q_{new_name}")
+            }
+            GraphEdit::SetStateType { name, state_type } => {
+                write!(
+                    f,
+                    "make the state q_{name} {}",
+                    state_type.to_string().to_lowercase()
+                )
+            }
+            GraphEdit::ReplaceTransition {
+                from,
+                transition,
+                to,
+                new_transition,
+                new_to,
+            } => write!(
+                f,
+                "replace the transition q_{from} {{{transition}}} q_{to} by q_{from} {{{new_transition}}} q_{new_to}"
+            ),
+            GraphEdit::ReplaceGraph {
+                graph: _,
+                description,
+            } => write!(f, "{description}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// Applies [GraphEdit]s to a graph while remembering them, so they can be undone and redone.
+pub struct GraphHistory {
+    /// The edits that were applied, along with the edits reverting them
+    undo_stack: Vec<(GraphEdit, Vec<GraphEdit>)>,
+    /// The edits that were undone, the last one being the next to redo
+    redo_stack: Vec<GraphEdit>,
+}
+
+impl GraphHistory {
+    /// Creates an empty history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies an edit to the given graph and remembers it. The edits that were undone can no longer be redone.
+    ///
+    /// Edits that change nothing (like adding a state that already exists) are not remembered.
+    pub fn apply(
+        &mut self,
+        graph: &mut TuringMachineGraph,
+        edit: GraphEdit,
+    ) -> Result<(), TuringError> {
+        let inverse = edit.apply(graph)?;
+        self.redo_stack.clear();
+        if !inverse.is_empty() {
+            self.undo_stack.push((edit, inverse));
+        }
+        Ok(())
+    }
+
+    /// Reverts the last edit applied to the given graph and returns it, or returns `None` if there is nothing to undo.
+    pub fn undo(
+        &mut self,
+        graph: &mut TuringMachineGraph,
+    ) -> Result<Option<GraphEdit>, TuringError> {
+        let Some((edit, inverse)) = self.undo_stack.pop() else {
+            return Ok(None);
+        };
+        for inverse_edit in &inverse {
+            inverse_edit.apply(graph)?;
+        }
+        self.redo_stack.push(edit.clone());
+        Ok(Some(edit))
+    }
+
+    /// Applies again the last edit that was undone and returns it, or returns `None` if there is nothing to redo.
+    pub fn redo(
+        &mut self,
+        graph: &mut TuringMachineGraph,
+    ) -> Result<Option<GraphEdit>, TuringError> {
+        let Some(edit) = self.redo_stack.pop() else {
+            return Ok(None);
+        };
+        let inverse = edit.apply(graph)?;
+        self.undo_stack.push((edit.clone(), inverse));
+        Ok(Some(edit))
+    }
+
+    /// Returns true if an edit can be undone.
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    /// Returns true if an edit can be redone.
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Forgets all the edits, for example when the graph is replaced.
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+}
@@ -0,0 +1,587 @@
+use pest::{Parser, iterators::Pair};
+use pest_derive::Parser;
+use std::{collections::HashMap, fs, str::FromStr};
+
+use crate::{
+    turing_errors::{TuringError, TuringParserError},
+    turing_graph::TuringMachineGraph,
+    turing_state::{TuringDirection, TuringStateType, TuringTransition},
+    turing_tape::{BLANK_CHAR, END_CHAR, INIT_CHAR},
+};
+
+#[derive(Parser)]
+#[grammar = "jflap.pest"]
+pub struct JflapGrammar;
+
+/// A machine imported from a JFLAP file, along with the layout of its states.
+#[derive(Debug, Clone)]
+pub struct JflapMachine {
+    pub graph: TuringMachineGraph,
+    /// The positions of the states in the JFLAP editor, using their name in the graph
+    pub positions: HashMap<String, (f32, f32)>,
+}
+
+/// Parses a JFLAP turing machine from the content of a `.jff` file.
+///
+/// See [parse_jflap_string] for more information on the conversion.
+pub fn parse_jflap_file_path(file_path: String) -> Result<JflapMachine, TuringParserError> {
+    match fs::read_to_string(&file_path) {
+        Ok(content) => parse_jflap_string(content),
+        Err(e) => Err(TuringParserError::FileError {
+            given_path: file_path,
+            error_reason: e.to_string(),
+        }),
+    }
+}
+
+/// Parses a JFLAP turing machine, with one or more tapes, from the content of a `.jff` file.
+///
+/// JFLAP machines have no reading tape and can write on their first tape. The returned graph therefore has one writing tape per JFLAP tape :
+/// it starts by copying the input on its first writing tape, then follows the JFLAP machine while its reading tape stays on [END_CHAR].
+/// * The blank symbol of JFLAP becomes [BLANK_CHAR].
+/// * JFLAP tapes are infinite on both sides. A transition reading and writing a blank without moving left can also be taken on [INIT_CHAR],
+///   which covers the usual loops going back to the start of a tape. Going further left is not possible and makes the machine reject.
+/// * The final states all become `q_a`, since JFLAP accepts as soon as a final state is reached. The transitions leaving them are dropped.
+/// * The input alphabet is inferred from the symbols read on the first tape, any other symbol of the input makes the machine reject.
+///
+/// Building blocks, other kinds of automata, wildcard symbols and symbols that cannot be written in a `.tm` file are not supported.
+pub fn parse_jflap_string(content: String) -> Result<JflapMachine, TuringParserError> {
+    let document = match JflapGrammar::parse(Rule::document, &content) {
+        Ok(mut document) => document.next().unwrap(),
+        Err(e) => {
+            return Err(TuringParserError::ParsingError {
+                line_col_pos: match e.line_col {
+                    pest::error::LineColLocation::Pos(p) => Some(p),
+                    pest::error::LineColLocation::Span(_, _) => None,
+                },
+                value: e.line().to_string(),
+                missing_value: None,
+            });
+        }
+    };
+    let root = XmlElement::from_pair(
+        document
+            .into_inner()
+            .find(|pair| pair.as_rule() == Rule::element)
+            .unwrap(), // the `document` rule always contains exactly one element
+    )?;
+
+    if root.name != "structure" {
+        return Err(root.missing("<structure>"));
+    }
+    let machine_type = root.expect_child("type")?.text.trim();
+    if machine_type != "turing" {
+        return Err(TuringParserError::UnsupportedConstructError {
+            construct: format!("<type>{machine_type}</type>"),
+            reason: "only turing machines can be converted".to_string(),
+        });
+    }
+    let k = match root.child("tapes") {
+        Some(tapes) => tapes.parse_text::<usize>()?,
+        None => 1,
+    };
+    // Older versions of JFLAP do not use the automaton element
+    let automaton = root.child("automaton").unwrap_or(&root);
+    if automaton.child("block").is_some() {
+        return Err(TuringParserError::UnsupportedConstructError {
+            construct: "<block>".to_string(),
+            reason:
+                "building blocks cannot be converted, they must be replaced by their content first"
+                    .to_string(),
+        });
+    }
+
+    let mut graph = match TuringMachineGraph::new(k) {
+        Ok(graph) => graph,
+        Err(e) => return Err(root.expect_child("tapes")?.turing_error(e)),
+    };
+    let mut positions: HashMap<String, (f32, f32)> = HashMap::new();
+    // The index in the graph of every JFLAP state, using their id
+    let mut indexes: HashMap<String, usize> = HashMap::new();
+    let mut initial = None;
+
+    for state in automaton.children_named("state") {
+        let index = if state.child("final").is_some() {
+            1
+        } else {
+            let name = state
+                .attribute("name")
+                .map(|name| to_state_name(name))
+                .unwrap_or_default();
+            if name.is_empty() || graph.get_name_index_hashmap().contains_key(&name) {
+                graph.add_fresh_state(if name.is_empty() { "s" } else { &name })
+            } else {
+                graph.add_state(&name)
+            }
+        };
+        if let (Some(x), Some(y)) = (state.child("x"), state.child("y")) {
+            let name = graph.get_state(index).unwrap().name.clone();
+            let position = (x.parse_text::<f32>()?, y.parse_text::<f32>()?);
+            positions.entry(name).or_insert(position);
+        }
+        if state.child("initial").is_some() {
+            initial = Some(index);
+        }
+        indexes.insert(state.expect_attribute("id")?.to_string(), index);
+    }
+    let initial = match initial {
+        Some(initial) => initial,
+        None => return Err(automaton.missing("<initial/>")),
+    };
+
+    let mut input_alphabet: Vec<char> = vec![];
+    for transition in automaton.children_named("transition") {
+        let index_of = |name: &str| -> Result<usize, TuringParserError> {
+            let element = transition.expect_child(name)?;
+            match indexes.get(element.text.trim()) {
+                Some(index) => Ok(*index),
+                None => Err(element.turing_error(TuringError::UnknownStateError {
+                    state_name: element.text.trim().to_string(),
+                })),
+            }
+        };
+        let from = index_of("from")?;
+        let to = index_of("to")?;
+        // JFLAP stops as soon as a final state is reached
+        if from == 1 {
+            continue;
+        }
+
+        let mut chars_read = vec![END_CHAR];
+        let mut chars_write = vec![];
+        let mut directions = vec![TuringDirection::None];
+        for tape in 1..=k {
+            chars_read.push(to_symbol(transition.expect_tape_child("read", tape)?)?);
+            chars_write.push(to_symbol(transition.expect_tape_child("write", tape)?)?);
+            directions.push(to_direction(transition.expect_tape_child("move", tape)?)?);
+        }
+        if chars_read[1] != BLANK_CHAR {
+            input_alphabet.push(chars_read[1]);
+        }
+
+        // The blanks on the left of the start of a tape are replaced by the initial char
+        let mut variants = vec![(chars_read, chars_write)];
+        for tape in 1..=k {
+            let (chars_read, chars_write) = &variants[0];
+            if chars_read[tape] != BLANK_CHAR
+                || chars_write[tape - 1] != BLANK_CHAR
+                || directions[tape] == TuringDirection::Left
+            {
+                continue;
+            }
+            variants = variants
+                .into_iter()
+                .flat_map(|(chars_read, chars_write)| {
+                    let mut init_read = chars_read.clone();
+                    let mut init_write = chars_write.clone();
+                    init_read[tape] = INIT_CHAR;
+                    init_write[tape - 1] = INIT_CHAR;
+                    [(chars_read, chars_write), (init_read, init_write)]
+                })
+                .collect();
+        }
+        for (chars_read, chars_write) in variants {
+            if let Err(e) = TuringTransition::create(chars_read, chars_write, directions.clone())
+                .and_then(|t| graph.append_rule_state(from, t, to))
+            {
+                return Err(transition.turing_error(e));
+            }
+        }
+    }
+    input_alphabet.sort();
+    input_alphabet.dedup();
+
+    add_input_copy(&mut graph, &input_alphabet, initial);
+    Ok(JflapMachine { graph, positions })
+}
+
+/// Adds the states copying the input on the first writing tape, then moving back to its start before reaching the given state.
+fn add_input_copy(graph: &mut TuringMachineGraph, input_alphabet: &[char], start: usize) {
+    let k = graph.get_k();
+    let copy = graph.add_fresh_state("copy");
+    let rewind = graph.add_fresh_state("rewind");
+    let right = TuringDirection::Right;
+    let left = TuringDirection::Left;
+    let none = TuringDirection::None;
+
+    // Every writing tape moves to its first cell
+    let transition = TuringTransition::create(
+        vec![INIT_CHAR; k + 1],
+        vec![INIT_CHAR; k],
+        vec![right.clone(); k + 1],
+    )
+    .unwrap();
+    graph.append_rule_state(0, transition, copy).unwrap();
+
+    // Only the reading tape and the first writing tape are used, the others stay on a blank
+    let mut add = |from: usize,
+                   (read, read_first, write_first): (char, char, char),
+                   dirs: [&TuringDirection; 2],
+                   to: usize| {
+        let transition = TuringTransition::create(
+            [vec![read, read_first], vec![BLANK_CHAR; k - 1]].concat(),
+            [vec![write_first], vec![BLANK_CHAR; k - 1]].concat(),
+            [
+                vec![dirs[0].clone(), dirs[1].clone()],
+                vec![none.clone(); k - 1],
+            ]
+            .concat(),
+        )
+        .expect("the copy of the input only uses valid transitions");
+        graph.append_rule_state(from, transition, to).unwrap();
+    };
+
+    for c in input_alphabet {
+        add(copy, (*c, BLANK_CHAR, *c), [&right, &right], copy);
+        add(rewind, (END_CHAR, *c, *c), [&none, &left], rewind);
+    }
+    add(
+        copy,
+        (END_CHAR, BLANK_CHAR, BLANK_CHAR),
+        [&none, &left],
+        rewind,
+    );
+    add(
+        rewind,
+        (END_CHAR, INIT_CHAR, INIT_CHAR),
+        [&none, &right],
+        start,
+    );
+}
+
+/// Turns the given [TuringMachineGraph] into the content of a JFLAP `.jff` file, using the given positions for the states when they are known.
+///
+/// The JFLAP machine has one more tape than the graph, its first tape plays the role of the reading tape and is never modified.
+/// It starts by surrounding the input with [INIT_CHAR] and [END_CHAR] and by marking the start of the other tapes with [INIT_CHAR],
+/// so that the transitions of the graph can then be used as they are. [BLANK_CHAR] becomes the blank symbol of JFLAP.
+///
+/// Note that the result cannot be imported back, since [INIT_CHAR] and [END_CHAR] are not allowed in the transitions of a JFLAP machine.
+pub fn graph_to_jflap(
+    graph: &TuringMachineGraph,
+    positions: &HashMap<String, (f32, f32)>,
+) -> String {
+    let k = graph.get_k();
+    let mut res = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?><!--Created with RITM.--><structure>\n",
+    );
+    res.push_str("\t<type>turing</type>\n");
+    res.push_str(&format!("\t<tapes>{}</tapes>\n", k + 1));
+    res.push_str("\t<automaton>\n");
+
+    // The states marking the tapes come before the states of the graph
+    let marking_states = ["mark", "scan", "back"];
+    for (id, name) in marking_states.iter().enumerate() {
+        res.push_str(&jflap_state(
+            id,
+            name,
+            (50. + 150. * id as f32, 50.),
+            id == 0,
+            false,
+        ));
+    }
+    for (index, state) in graph.get_states().iter().enumerate() {
+        let position = positions.get(&state.name).copied().unwrap_or((
+            50. + 150. * (index % 5) as f32,
+            200. + 150. * (index / 5) as f32,
+        ));
+        res.push_str(&jflap_state(
+            index + marking_states.len(),
+            &format!("q_{}", state.name),
+            position,
+            false,
+            state.state_type == TuringStateType::Accepting,
+        ));
+    }
+
+    let blanks = vec![BLANK_CHAR; k];
+    let marks = vec![INIT_CHAR; k];
+    let right = vec![TuringDirection::Right; k];
+    let left = vec![TuringDirection::Left; k];
+    let none = vec![TuringDirection::None; k];
+    let input_alphabet = graph.get_input_alphabet();
+    let with = |first: char, others: &Vec<char>| [vec![first], others.clone()].concat();
+    let moving = |first: TuringDirection, others: &Vec<TuringDirection>| {
+        [vec![first], others.clone()].concat()
+    };
+
+    for c in input_alphabet.iter().chain([&BLANK_CHAR]) {
+        res.push_str(&jflap_transition(
+            (0, 1),
+            &with(*c, &blanks),
+            &with(*c, &marks),
+            &moving(TuringDirection::None, &right),
+        ));
+    }
+    for c in &input_alphabet {
+        res.push_str(&jflap_transition(
+            (1, 1),
+            &with(*c, &blanks),
+            &with(*c, &blanks),
+            &moving(TuringDirection::Right, &none),
+        ));
+        res.push_str(&jflap_transition(
+            (2, 2),
+            &with(*c, &blanks),
+            &with(*c, &blanks),
+            &moving(TuringDirection::Left, &none),
+        ));
+    }
+    res.push_str(&jflap_transition(
+        (1, 2),
+        &with(BLANK_CHAR, &blanks),
+        &with(END_CHAR, &blanks),
+        &moving(TuringDirection::Left, &none),
+    ));
+    res.push_str(&jflap_transition(
+        (2, marking_states.len()),
+        &with(BLANK_CHAR, &blanks),
+        &with(INIT_CHAR, &blanks),
+        &moving(TuringDirection::None, &left),
+    ));
+
+    for (index, state) in graph.get_states().iter().enumerate() {
+        for transition in &state.transitions {
+            let chars_write: Vec<char> = transition.chars_write.iter().map(|(c, _)| *c).collect();
+            let directions: Vec<TuringDirection> = transition
+                .chars_write
+                .iter()
+                .map(|(_, d)| d.clone())
+                .collect();
+            res.push_str(&jflap_transition(
+                (
+                    index + marking_states.len(),
+                    transition.index_to_state.unwrap() + marking_states.len(),
+                ),
+                &transition.chars_read,
+                &with(transition.chars_read[0], &chars_write),
+                &moving(transition.move_read.clone(), &directions),
+            ));
+        }
+    }
+
+    res.push_str("\t</automaton>\n</structure>\n");
+    res
+}
+
+fn jflap_state(id: usize, name: &str, position: (f32, f32), initial: bool, fin: bool) -> String {
+    format!(
+        "\t\t<state id=\"{id}\" name=\"{name}\">\n\t\t\t<x>{:.1}</x>\n\t\t\t<y>{:.1}</y>\n{}{}\t\t</state>\n",
+        position.0,
+        position.1,
+        if initial { "\t\t\t<initial/>\n" } else { "" },
+        if fin { "\t\t\t<final/>\n" } else { "" },
+    )
+}
+
+fn jflap_transition(
+    (from, to): (usize, usize),
+    chars_read: &[char],
+    chars_write: &[char],
+    directions: &[TuringDirection],
+) -> String {
+    let symbol = |tag: &str, tape: usize, c: char| {
+        if c == BLANK_CHAR {
+            format!("\t\t\t<{tag} tape=\"{tape}\"/>\n")
+        } else {
+            format!("\t\t\t<{tag} tape=\"{tape}\">{c}</{tag}>\n")
+        }
+    };
+    let mut res = format!("\t\t<transition>\n\t\t\t<from>{from}</from>\n\t\t\t<to>{to}</to>\n");
+    for tape in 0..chars_read.len() {
+        res.push_str(&symbol("read", tape + 1, chars_read[tape]));
+        res.push_str(&symbol("write", tape + 1, chars_write[tape]));
+        res.push_str(&format!(
+            "\t\t\t<move tape=\"{}\">{}</move>\n",
+            tape + 1,
+            match directions[tape] {
+                TuringDirection::Left => "L",
+                TuringDirection::Right => "R",
+                TuringDirection::None => "S",
+            }
+        ));
+    }
+    res.push_str("\t\t</transition>\n");
+    res
+}
+
+/// Turns the name of a JFLAP state into a name that can be written in a `.tm` file, the `q` and `q_` prefixes are removed like in the parser.
+fn to_state_name(name: &str) -> String {
+    let name = name.strip_prefix('q').unwrap_or(name);
+    let name = name.strip_prefix('_').unwrap_or(name);
+    name.chars().filter(|c| c.is_ascii_alphanumeric()).collect()
+}
+
+fn to_symbol(element: &XmlElement) -> Result<char, TuringParserError> {
+    let mut chars = element.text.chars();
+    match (chars.next(), chars.next()) {
+        // An empty symbol is the blank symbol of JFLAP
+        (None, _) | (Some('□'), None) | (Some(BLANK_CHAR), None) => Ok(BLANK_CHAR),
+        (Some('~'), None) => Err(TuringParserError::UnsupportedConstructError {
+            construct: "~".to_string(),
+            reason: "the wildcard symbol of JFLAP cannot be converted, one transition must be written per symbol".to_string(),
+        }),
+        (Some(c), None) if c.is_ascii_alphanumeric() || c == '#' => Ok(c),
+        _ => Err(TuringParserError::UnsupportedConstructError {
+            construct: element.text.to_string(),
+            reason: "a symbol must be a single ASCII letter, digit or '#' to be used in a .tm file"
+                .to_string(),
+        }),
+    }
+}
+
+fn to_direction(element: &XmlElement) -> Result<TuringDirection, TuringParserError> {
+    match element.text.trim() {
+        "L" => Ok(TuringDirection::Left),
+        "R" => Ok(TuringDirection::Right),
+        "S" => Ok(TuringDirection::None),
+        other => Err(TuringParserError::UnsupportedConstructError {
+            construct: other.to_string(),
+            reason: "only the moves L, R and S can be converted".to_string(),
+        }),
+    }
+}
+
+/// An element of a XML document, only keeping what is needed to read a JFLAP file.
+struct XmlElement {
+    name: String,
+    attributes: HashMap<String, String>,
+    children: Vec<XmlElement>,
+    /// The text found directly inside of this element, with the entities replaced
+    text: String,
+    line: usize,
+}
+
+impl XmlElement {
+    fn from_pair(pair: Pair<Rule>) -> Result<Self, TuringParserError> {
+        let mut element = XmlElement {
+            name: String::new(),
+            attributes: HashMap::new(),
+            children: vec![],
+            text: String::new(),
+            line: pair.line_col().0,
+        };
+        for inner in pair.into_inner() {
+            match inner.as_rule() {
+                Rule::name if element.name.is_empty() => element.name = inner.as_str().to_string(),
+                // The name of the closing tag
+                Rule::name => {
+                    if inner.as_str() != element.name {
+                        return Err(element.missing(&format!("</{}>", element.name)));
+                    }
+                }
+                Rule::attribute => {
+                    let mut attribute = inner.into_inner();
+                    let name = attribute.next().unwrap().as_str().to_string();
+                    let value = unescape(attribute.next().unwrap().as_str());
+                    element.attributes.insert(name, value);
+                }
+                Rule::element => element.children.push(XmlElement::from_pair(inner)?),
+                Rule::text => element.text.push_str(&unescape(inner.as_str())),
+                _ => unreachable!(),
+            }
+        }
+        Ok(element)
+    }
+
+    fn child(&self, name: &str) -> Option<&XmlElement> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a XmlElement> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    fn expect_child(&self, name: &str) -> Result<&XmlElement, TuringParserError> {
+        self.child(name)
+            .ok_or_else(|| self.missing(&format!("<{name}>")))
+    }
+
+    /// Returns the child with the given name affecting the given tape (starting from `1`).
+    /// A child without a tape attribute affects the first tape, like in the files of single tape machines.
+    fn expect_tape_child(&self, name: &str, tape: usize) -> Result<&XmlElement, TuringParserError> {
+        self.children
+            .iter()
+            .find(|child| {
+                child.name == name
+                    && match child.attribute("tape") {
+                        Some(value) => value.trim() == tape.to_string(),
+                        None => tape == 1,
+                    }
+            })
+            .ok_or_else(|| self.missing(&format!("<{name} tape=\"{tape}\">")))
+    }
+
+    fn attribute(&self, name: &str) -> Option<&String> {
+        self.attributes.get(name)
+    }
+
+    fn expect_attribute(&self, name: &str) -> Result<&String, TuringParserError> {
+        self.attribute(name)
+            .ok_or_else(|| self.missing(&format!("{name}=\"\"")))
+    }
+
+    fn parse_text<T: FromStr>(&self) -> Result<T, TuringParserError> {
+        self.text
+            .trim()
+            .parse()
+            .map_err(|_| TuringParserError::ParsingError {
+                line_col_pos: None,
+                value: format!(
+                    "line {} : <{}>{}</{}>",
+                    self.line, self.name, self.text, self.name
+                ),
+                missing_value: None,
+            })
+    }
+
+    fn missing(&self, token: &str) -> TuringParserError {
+        TuringParserError::ParsingError {
+            line_col_pos: None,
+            value: format!("line {} : <{}>", self.line, self.name),
+            missing_value: Some(token.to_string()),
+        }
+    }
+
+    fn turing_error(&self, turing_error: TuringError) -> TuringParserError {
+        TuringParserError::EncounteredTuringError {
+            line_col_pos: None,
+            turing_error,
+            value: format!("line {} : <{}>", self.line, self.name),
+        }
+    }
+}
+
+/// Replaces the predefined XML entities and the character references of the given value.
+fn unescape(value: &str) -> String {
+    let mut res = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find('&') {
+        res.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest.find(';').map(|end| &rest[1..end]);
+        let c = match entity {
+            Some("lt") => Some('<'),
+            Some("gt") => Some('>'),
+            Some("amp") => Some('&'),
+            Some("quot") => Some('"'),
+            Some("apos") => Some('\''),
+            Some(e) if e.starts_with("#x") => u32::from_str_radix(&e[2..], 16)
+                .ok()
+                .and_then(char::from_u32),
+            Some(e) if e.starts_with('#') => e[1..].parse().ok().and_then(char::from_u32),
+            _ => None,
+        };
+        match (c, entity) {
+            (Some(c), Some(entity)) => {
+                res.push(c);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                res.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    res.push_str(rest);
+    res
+}
@@ -0,0 +1,431 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{collections::HashMap, fs};
+
+use crate::{
+    turing_errors::{TuringError, TuringParserError},
+    turing_graph::{EmbeddedTest, TuringMachineGraph},
+    turing_state::{TuringDirection, TuringStateType, TuringTransition},
+    turing_tape::{BLANK_CHAR, END_CHAR, INIT_CHAR},
+};
+
+/// The version of the JSON format written by [graph_to_json].
+///
+/// It must be increased every time a change of the format prevents older versions of RITM from reading the new files.
+pub const JSON_FORMAT_VERSION: u32 = 1;
+
+/// The informations that can be stored along with a graph in the JSON format, none of them are needed to load a graph.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GraphMetadata {
+    /// A description of what the machine does
+    pub description: Option<String>,
+    /// The position of the states, using their name in the graph
+    pub positions: HashMap<String, (f32, f32)>,
+    /// The color of the states as an hexadecimal `#rrggbb` string, using their name in the graph
+    pub colors: HashMap<String, String>,
+    /// The characters the input of the machine is made of, without [INIT_CHAR] and [END_CHAR]
+    pub input_alphabet: Option<Vec<char>>,
+    /// The characters that can be written on every writing tape, without [INIT_CHAR] and [BLANK_CHAR]
+    pub tape_alphabets: Option<Vec<Vec<char>>>,
+}
+
+/// A machine loaded from the JSON format, along with its metadata.
+#[derive(Debug, Clone)]
+pub struct JsonMachine {
+    pub graph: TuringMachineGraph,
+    pub metadata: GraphMetadata,
+}
+
+/// The content of a JSON file, as it is written.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GraphDocument {
+    version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    k: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    input_alphabet: Option<Vec<char>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tape_alphabets: Option<Vec<Vec<char>>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tests: Vec<TestDocument>,
+    states: Vec<StateDocument>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TestDocument {
+    word: String,
+    accept: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StateDocument {
+    name: String,
+    #[serde(rename = "type")]
+    state_type: TuringStateType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    position: Option<(f32, f32)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    doc: Option<String>,
+    #[serde(default)]
+    transitions: Vec<TransitionDocument>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TransitionDocument {
+    read: Vec<char>,
+    move_read: TuringDirection,
+    write: Vec<(char, TuringDirection)>,
+    to: String,
+}
+
+/// Turns the given [TuringMachineGraph] and its metadata into the JSON format, in its version [JSON_FORMAT_VERSION].
+///
+/// The metadata of the states that are not part of the graph are ignored.
+/// The positions and colors that are not given are taken from the metadata of the states stored in the graph.
+pub fn graph_to_json(graph: &TuringMachineGraph, metadata: &GraphMetadata) -> String {
+    // Writing a document made of strings, numbers and arrays cannot fail
+    serde_json::to_string_pretty(&to_document(graph, metadata)).unwrap()
+}
+
+/// Parses a graph and its metadata from the content of a JSON file.
+///
+/// See [parse_json_graph_string] for more information.
+pub fn parse_json_graph_file_path(file_path: String) -> Result<JsonMachine, TuringParserError> {
+    match fs::read_to_string(&file_path) {
+        Ok(content) => parse_json_graph_string(content),
+        Err(e) => Err(TuringParserError::FileError {
+            given_path: file_path,
+            error_reason: e.to_string(),
+        }),
+    }
+}
+
+/// Parses a graph and its metadata written in the JSON format.
+///
+/// The graph must follow the same rules as the text format : the types of the states `q_i`, `q_a` and `q_r` cannot be changed,
+/// the names of the states and the characters must be valid in a `.tm` file and every transition must be valid and affect `k` writing tapes.
+/// If alphabets are declared, the transitions can only read and write their characters.
+///
+/// Files written by a newer version of the format are not supported.
+pub fn parse_json_graph_string(content: String) -> Result<JsonMachine, TuringParserError> {
+    let document: GraphDocument = match serde_json::from_str(&content) {
+        Ok(document) => document,
+        Err(e) => {
+            return Err(TuringParserError::ParsingError {
+                line_col_pos: Some((e.line(), e.column())),
+                value: e.to_string(),
+                missing_value: None,
+            });
+        }
+    };
+    if document.version > JSON_FORMAT_VERSION {
+        return Err(TuringParserError::UnsupportedConstructError {
+            construct: format!("version {}", document.version),
+            reason: format!(
+                "the files can be read up to the version {JSON_FORMAT_VERSION} of the format"
+            ),
+        });
+    }
+    from_document(document).map_err(|(turing_error, value)| {
+        TuringParserError::EncounteredTuringError {
+            line_col_pos: None,
+            turing_error,
+            value,
+        }
+    })
+}
+
+impl Serialize for TuringMachineGraph {
+    /// Serializes the graph using the JSON format, without any metadata.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        to_document(self, &GraphMetadata::default()).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TuringMachineGraph {
+    /// Deserializes a graph written in the JSON format, checking it the same way as [parse_json_graph_string].
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let document = GraphDocument::deserialize(deserializer)?;
+        if document.version > JSON_FORMAT_VERSION {
+            return Err(serde::de::Error::custom(format!(
+                "unsupported version {} of the format",
+                document.version
+            )));
+        }
+        from_document(document)
+            .map(|machine| machine.graph)
+            .map_err(|(e, _)| serde::de::Error::custom(e))
+    }
+}
+
+fn to_document(graph: &TuringMachineGraph, metadata: &GraphMetadata) -> GraphDocument {
+    let states = graph.get_states();
+    GraphDocument {
+        version: JSON_FORMAT_VERSION,
+        description: metadata.description.clone(),
+        k: graph.get_k(),
+        input_alphabet: metadata.input_alphabet.clone(),
+        tape_alphabets: metadata.tape_alphabets.clone(),
+        tests: graph
+            .get_embedded_tests()
+            .iter()
+            .map(|test| TestDocument {
+                word: test.word.clone(),
+                accept: test.accept,
+            })
+            .collect(),
+        states: states
+            .iter()
+            .map(|state| {
+                let state_metadata = graph.get_state_metadata(&state.name);
+                StateDocument {
+                    name: state.name.clone(),
+                    state_type: state.state_type.clone(),
+                    position: metadata
+                        .positions
+                        .get(&state.name)
+                        .copied()
+                        .or(state_metadata.and_then(|m| m.position)),
+                    color: metadata
+                        .colors
+                        .get(&state.name)
+                        .or(state_metadata.and_then(|m| m.color.as_ref()))
+                        .cloned(),
+                    doc: state_metadata.and_then(|m| m.doc.clone()),
+                    transitions: state
+                        .transitions
+                        .iter()
+                        .map(|transition| TransitionDocument {
+                            read: transition.chars_read.clone(),
+                            move_read: transition.move_read.clone(),
+                            write: transition.chars_write.clone(),
+                            to: states[transition.index_to_state.unwrap()].name.clone(),
+                        })
+                        .collect(),
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Builds the graph described by the document, returning the [TuringError] encountered along with the value that caused it.
+fn from_document(document: GraphDocument) -> Result<JsonMachine, (TuringError, String)> {
+    let mut graph = TuringMachineGraph::new(document.k).map_err(|e| (e, "k".to_string()))?;
+    let mut metadata = GraphMetadata {
+        description: document.description,
+        input_alphabet: document.input_alphabet,
+        tape_alphabets: document.tape_alphabets,
+        ..Default::default()
+    };
+    check_alphabets(&metadata, document.k)?;
+
+    // Every state is created first, since transitions can go to states declared after them
+    let mut seen: Vec<&String> = vec![];
+    for state in &document.states {
+        if seen.contains(&&state.name) {
+            return Err((
+                TuringError::IllegalActionError {
+                    cause: format!("The state \"q_{}\" is declared more than once", state.name),
+                },
+                state.name.clone(),
+            ));
+        }
+        seen.push(&state.name);
+        check_state(&state.name, &state.state_type)?;
+        if graph.add_state(&state.name) > 2 {
+            // The state was checked, so its type can be changed
+            graph
+                .set_state_type(&state.name, state.state_type.clone())
+                .unwrap();
+        }
+
+        // The metadata is also kept in the graph, so that it is written in the text format
+        let state_metadata = graph.get_state_metadata_mut(&state.name).unwrap();
+        state_metadata.position = state.position;
+        state_metadata.color = state.color.clone();
+        state_metadata.doc = state.doc.clone();
+        if let Some(position) = state.position {
+            metadata.positions.insert(state.name.clone(), position);
+        }
+        if let Some(color) = &state.color {
+            metadata.colors.insert(state.name.clone(), color.clone());
+        }
+    }
+
+    for state in &document.states {
+        let from = graph.get_name_index_hashmap()[&state.name];
+        for transition in &state.transitions {
+            let value = format!("q_{} -> q_{}", state.name, transition.to);
+            let to = match graph.get_name_index_hashmap().get(&transition.to) {
+                Some(to) => *to,
+                None => {
+                    return Err((
+                        TuringError::UnknownStateError {
+                            state_name: transition.to.clone(),
+                        },
+                        value,
+                    ));
+                }
+            };
+            let turing_transition = TuringTransition::create(
+                transition.read.clone(),
+                transition.write.iter().map(|(c, _)| *c).collect(),
+                [transition.move_read.clone()]
+                    .into_iter()
+                    .chain(transition.write.iter().map(|(_, d)| d.clone()))
+                    .collect(),
+            )
+            .map_err(|e| (e, value.clone()))?;
+            check_transition(&turing_transition, &metadata, document.k)
+                .map_err(|e| (e, value.clone()))?;
+            graph
+                .append_rule_state(from, turing_transition, to)
+                .map_err(|e| (e, value))?;
+        }
+    }
+
+    // The tests get the lines where they are written in the text format, before the transitions
+    for (index, test) in document.tests.into_iter().enumerate() {
+        graph.add_embedded_test(EmbeddedTest {
+            word: test.word,
+            accept: test.accept,
+            line: index + 1,
+        });
+    }
+
+    Ok(JsonMachine { graph, metadata })
+}
+
+/// Checks that the name of the state can be written in a `.tm` file, and that the types of the default states were not changed.
+fn check_state(name: &str, state_type: &TuringStateType) -> Result<(), (TuringError, String)> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err((
+            TuringError::IllegalActionError {
+                cause: format!(
+                    "The name of the state \"{name}\" must only contain ASCII letters and digits"
+                ),
+            },
+            name.to_string(),
+        ));
+    }
+    let expected = match name {
+        "i" => TuringStateType::Normal,
+        "a" => TuringStateType::Accepting,
+        "r" => TuringStateType::Rejecting,
+        _ => return Ok(()),
+    };
+    if *state_type != expected {
+        return Err((
+            TuringError::IllegalActionError {
+                cause: format!(
+                    "The state \"q_{name}\" is {state_type} while it can only be {expected}"
+                ),
+            },
+            name.to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn check_alphabets(metadata: &GraphMetadata, k: usize) -> Result<(), (TuringError, String)> {
+    if let Some(alphabet) = &metadata.input_alphabet {
+        for c in alphabet {
+            if !c.is_ascii_alphanumeric() && *c != '#' {
+                return Err((
+                    TuringError::IllegalActionError {
+                        cause: format!("The input alphabet cannot contain the character '{c}'"),
+                    },
+                    "input_alphabet".to_string(),
+                ));
+            }
+        }
+    }
+    if let Some(alphabets) = &metadata.tape_alphabets {
+        if alphabets.len() != k {
+            return Err((
+                TuringError::IncompatibleTransitionError {
+                    expected: k,
+                    received: alphabets.len(),
+                },
+                "tape_alphabets".to_string(),
+            ));
+        }
+        for c in alphabets.iter().flatten() {
+            if !is_valid_char(*c) {
+                return Err((
+                    TuringError::IllegalActionError {
+                        cause: format!("The tape alphabets cannot contain the character '{c}'"),
+                    },
+                    "tape_alphabets".to_string(),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the characters of the transition can be written in a `.tm` file and belong to the declared alphabets, if any.
+fn check_transition(
+    transition: &TuringTransition,
+    metadata: &GraphMetadata,
+    k: usize,
+) -> Result<(), TuringError> {
+    if transition.chars_write.len() != k {
+        return Err(TuringError::IncompatibleTransitionError {
+            expected: k,
+            received: transition.chars_write.len(),
+        });
+    }
+    let chars = transition
+        .chars_read
+        .iter()
+        .chain(transition.chars_write.iter().map(|(c, _)| c));
+    for c in chars {
+        if !is_valid_char(*c) {
+            return Err(TuringError::IllegalActionError {
+                cause: format!("The character '{c}' cannot be used by a transition"),
+            });
+        }
+    }
+
+    if let Some(alphabet) = &metadata.input_alphabet {
+        let c = transition.chars_read[0];
+        if c != INIT_CHAR && c != END_CHAR && !alphabet.contains(&c) {
+            return Err(TuringError::IllegalActionError {
+                cause: format!(
+                    "The character '{c}' read on the reading tape is not part of the input alphabet"
+                ),
+            });
+        }
+    }
+    if let Some(alphabets) = &metadata.tape_alphabets {
+        let tapes = transition.chars_read[1..]
+            .iter()
+            .zip(&transition.chars_write)
+            .enumerate();
+        for (tape, (read, (written, _))) in tapes {
+            for c in [read, written] {
+                if *c != INIT_CHAR && *c != BLANK_CHAR && !alphabets[tape].contains(c) {
+                    return Err(TuringError::IllegalActionError {
+                        cause: format!(
+                            "The character '{c}' is not part of the alphabet of the tape {}",
+                            tape + 1
+                        ),
+                    });
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns true if the character can be written in a `.tm` file.
+fn is_valid_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || [INIT_CHAR, BLANK_CHAR, END_CHAR, '#'].contains(&c)
+}
@@ -0,0 +1,210 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::{
+    turing_graph::TuringMachineGraph,
+    turing_machine::TuringExecutionSteps,
+    turing_state::{TuringStateType, TuringTransition},
+    turing_tape::TuringTape,
+};
+
+/// The number of centimeters per unit of the positions given to [graph_to_tikz], made for the positions of the GUI.
+const POSITION_SCALE: f32 = 1. / 60.;
+
+/// Turns the given [TuringMachineGraph] into a TikZ picture drawn with the `automata` library.
+///
+/// If every state has a position in the given map (for example the positions of the GUI, where the y axis goes down), they are used to place the states.
+/// Otherwise, the states are placed in columns, based on the number of transitions needed to reach them from `q_i`.
+/// All the transitions going from one state to another are grouped into a single edge, with one transition per line.
+///
+/// The picture needs the `automata` and `arrows.meta` TikZ libraries to be loaded.
+pub fn graph_to_tikz(
+    graph: &TuringMachineGraph,
+    positions: &HashMap<String, (f32, f32)>,
+) -> String {
+    let states = graph.get_states();
+    let coordinates: Vec<(f32, f32)> = if states
+        .iter()
+        .all(|state| positions.contains_key(&state.name))
+    {
+        states
+            .iter()
+            .map(|state| {
+                let (x, y) = positions[&state.name];
+                // Subtracting avoids writing `-0.00`
+                (x * POSITION_SCALE, 0. - y * POSITION_SCALE)
+            })
+            .collect()
+    } else {
+        layered_layout(graph)
+    };
+
+    let mut res = String::from(
+        "\\begin{tikzpicture}[->, >=Stealth, auto, semithick, initial text=, rejecting/.style={double, draw=red}]\n",
+    );
+    for (index, state) in states.iter().enumerate() {
+        let mut style = vec!["state"];
+        if index == 0 {
+            style.push("initial");
+        }
+        match state.state_type {
+            TuringStateType::Accepting => style.push("accepting"),
+            TuringStateType::Rejecting => style.push("rejecting"),
+            TuringStateType::Normal => {}
+        }
+        res.push_str(&format!(
+            "    \\node[{}] (s{index}) at ({:.2}, {:.2}) {{$q_{{{}}}$}};\n",
+            style.join(", "),
+            coordinates[index].0,
+            coordinates[index].1,
+            state.name
+        ));
+    }
+
+    let edges = grouped_transitions(graph);
+    for (from, to, transitions) in &edges {
+        let bend = if from == to {
+            "[loop above]"
+        } else if edges.iter().any(|(f, t, _)| f == to && t == from) {
+            "[bend left]"
+        } else {
+            ""
+        };
+        let label: Vec<String> = transitions
+            .iter()
+            .map(|transition| format!("\\texttt{{{}}}", transition_to_latex(transition)))
+            .collect();
+        res.push_str(&format!(
+            "    \\path (s{from}) edge{bend} node[align=left] {{{}}} (s{to});\n",
+            label.join(" \\\\ ")
+        ));
+    }
+    res.push_str("\\end{tikzpicture}\n");
+    res
+}
+
+/// Turns the given steps of an execution into a LaTeX table with one configuration per line,
+/// like the steps returned by [crate::turing_machine::TuringMachines::get_path_to_accept].
+///
+/// Each line shows the iteration, the current state and the content of every tape, with the character under the head of each tape underlined.
+pub fn path_to_latex_table(steps: &[TuringExecutionSteps]) -> String {
+    let k = steps
+        .first()
+        .map(|step| step.get_writing_tapes().len())
+        .unwrap_or(0);
+
+    let mut res = format!("\\begin{{tabular}}{{r|l|l{}}}\n", "|l".repeat(k));
+    res.push_str("Iteration & State & Reading tape");
+    for i in 1..=k {
+        res.push_str(&format!(" & Tape {i}"));
+    }
+    res.push_str(" \\\\\n\\hline\n");
+
+    for step in steps {
+        let backtracked = match step {
+            TuringExecutionSteps::Backtracked { .. } => " (backtracked)",
+            _ => "",
+        };
+        res.push_str(&format!(
+            "{} & $q_{{{}}}${backtracked} & {}",
+            step.get_nb_iterations(),
+            step.get_current_state().name,
+            tape_to_latex(step.get_reading_tape())
+        ));
+        for tape in step.get_writing_tapes() {
+            res.push_str(&format!(" & {}", tape_to_latex(tape)));
+        }
+        res.push_str(" \\\\\n");
+    }
+    res.push_str("\\end{tabular}\n");
+    res
+}
+
+/// Places the states in columns, the column of a state being the smallest number of transitions needed to reach it from `q_i`.
+/// The unreachable states are placed in a last column.
+fn layered_layout(graph: &TuringMachineGraph) -> Vec<(f32, f32)> {
+    let states = graph.get_states();
+    let mut layers: Vec<Option<usize>> = vec![None; states.len()];
+    layers[0] = Some(0);
+    let mut queue = VecDeque::from([0]);
+    while let Some(index) = queue.pop_front() {
+        for transition in &states[index].transitions {
+            let to = transition.index_to_state.unwrap();
+            if layers[to].is_none() {
+                layers[to] = Some(layers[index].unwrap() + 1);
+                queue.push_back(to);
+            }
+        }
+    }
+    let last_layer = layers.iter().flatten().max().unwrap() + 1;
+
+    let mut heights: HashMap<usize, usize> = HashMap::new();
+    layers
+        .iter()
+        .map(|layer| {
+            let layer = layer.unwrap_or(last_layer);
+            let height = heights.entry(layer).or_insert(0);
+            *height += 1;
+            (3. * layer as f32, 0. - 2.5 * (*height - 1) as f32)
+        })
+        .collect()
+}
+
+/// Returns the transitions of the graph grouped by the pair of states they link, in the order they appear.
+fn grouped_transitions(graph: &TuringMachineGraph) -> Vec<(usize, usize, Vec<&TuringTransition>)> {
+    let mut edges: Vec<(usize, usize, Vec<&TuringTransition>)> = vec![];
+    for (from, state) in graph.get_states().iter().enumerate() {
+        for transition in &state.transitions {
+            let to = transition.index_to_state.unwrap();
+            match edges.iter_mut().find(|(f, t, _)| *f == from && *t == to) {
+                Some((_, _, transitions)) => transitions.push(transition),
+                None => edges.push((from, to, vec![transition])),
+            }
+        }
+    }
+    edges
+}
+
+fn transition_to_latex(transition: &TuringTransition) -> String {
+    let chars_read: Vec<String> = transition
+        .chars_read
+        .iter()
+        .map(|c| char_to_latex(*c))
+        .collect();
+    let mut written = vec![transition.move_read.to_string()];
+    for (c, direction) in &transition.chars_write {
+        written.push(char_to_latex(*c));
+        written.push(direction.to_string());
+    }
+    format!(
+        "{} $\\rightarrow$ {}",
+        chars_read.join(", "),
+        written.join(", ")
+    )
+}
+
+fn tape_to_latex<T: TuringTape>(tape: &T) -> String {
+    let content: String = tape
+        .get_contents()
+        .iter()
+        .enumerate()
+        .map(|(index, c)| {
+            if index == tape.get_pointer() {
+                format!("\\underline{{{}}}", char_to_latex(*c))
+            } else {
+                char_to_latex(*c)
+            }
+        })
+        .collect();
+    format!("\\texttt{{{content}}}")
+}
+
+/// Escapes the characters that have a special meaning in LaTeX.
+fn char_to_latex(c: char) -> String {
+    match c {
+        '_' => "\\_".to_string(),
+        '$' => "\\$".to_string(),
+        '#' => "\\#".to_string(),
+        'ç' => "\\c{c}".to_string(),
+        c => c.to_string(),
+    }
+}
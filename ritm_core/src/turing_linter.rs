@@ -0,0 +1,383 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    fmt::Display,
+};
+
+use crate::{
+    turing_graph::TuringMachineGraph,
+    turing_state::{TuringStateType, TuringTransition},
+    turing_tape::{BLANK_CHAR, INIT_CHAR},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+/// Represents how serious a [LintFinding] is.
+pub enum LintSeverity {
+    /// Harmless, but could be simplified.
+    Info,
+    /// Most likely a mistake.
+    Warning,
+    /// The machine cannot work as intended.
+    Error,
+}
+
+impl Display for LintSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                LintSeverity::Info => "info",
+                LintSeverity::Warning => "warning",
+                LintSeverity::Error => "error",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+/// Represents where a [LintFinding] was found inside of a [TuringMachineGraph].
+pub enum LintLocation {
+    /// A state, using its index in the graph.
+    State { state_index: usize },
+    /// A transition, using the index of the state it leaves and its index in the transitions of that state.
+    Transition {
+        state_index: usize,
+        transition_index: usize,
+    },
+}
+
+impl LintLocation {
+    /// Returns the index of the state related to this location.
+    pub fn get_state_index(&self) -> usize {
+        match self {
+            LintLocation::State { state_index } => *state_index,
+            LintLocation::Transition {
+                state_index,
+                transition_index: _,
+            } => *state_index,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents the different kind of mistakes that can be found by [lint_graph].
+pub enum LintKind {
+    /// The state can never be reached from the initial state.
+    UnreachableState,
+    /// No accepting state can be reached from this state.
+    CannotReachAccepting,
+    /// A normal state without any outgoing transitions, any execution reaching it is rejected.
+    ImplicitReject,
+    /// The transition can never be taken.
+    DeadTransition,
+    /// The exact same transition (including the state it goes to) is defined more than once.
+    DuplicateTransition,
+    /// The transition goes to a final state but still writes on the tapes.
+    NeedlessWrite,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A likely mistake found while analysing a [TuringMachineGraph].
+pub struct LintFinding {
+    /// How serious the finding is
+    pub severity: LintSeverity,
+    /// What was found
+    pub kind: LintKind,
+    /// Where it was found
+    pub location: LintLocation,
+    /// A human readable explanation of the finding
+    pub message: String,
+}
+
+impl Display for LintFinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}", self.severity, self.message)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Abstract position of the head of a tape : either on the first cell (containing [INIT_CHAR]) or somewhere after it.
+enum HeadPosition {
+    Start,
+    After,
+}
+
+/// Analyses the given graph and returns every likely mistake found in it, sorted from the most to the least severe.
+///
+/// The following checks are performed :
+/// * states that are unreachable from `q_i`
+/// * states that can never reach an accepting state
+/// * normal states without any outgoing transitions (implicit rejects)
+/// * transitions that can never be taken (for example reading [INIT_CHAR] on a tape whose head can never be on the first cell)
+/// * duplicated transitions
+/// * transitions going to a final state that still write something on the tapes
+pub fn lint_graph(graph: &TuringMachineGraph) -> Vec<LintFinding> {
+    let mut findings = vec![];
+    let states = graph.get_states();
+    let name = |index: usize| format!("q_{}", states[index].name);
+
+    let written_chars = compute_written_chars(graph);
+    let head_positions = compute_head_positions(graph, &written_chars);
+    let reachable: Vec<bool> = head_positions.iter().map(|p| p.is_some()).collect();
+    let co_reachable = compute_co_reachable(graph);
+
+    for (index, state) in states.iter().enumerate() {
+        if !reachable[index] {
+            // The default final states are not required to be used
+            if state.state_type == TuringStateType::Normal {
+                findings.push(LintFinding {
+                    severity: LintSeverity::Warning,
+                    kind: LintKind::UnreachableState,
+                    location: LintLocation::State { state_index: index },
+                    message: format!("{} can never be reached from q_i", name(index)),
+                });
+            }
+            // Everything else would only be noise for an unused state
+            continue;
+        }
+        if state.state_type != TuringStateType::Normal {
+            continue;
+        }
+
+        if !co_reachable[index] {
+            findings.push(LintFinding {
+                severity: if index == 0 {
+                    LintSeverity::Error
+                } else {
+                    LintSeverity::Warning
+                },
+                kind: LintKind::CannotReachAccepting,
+                location: LintLocation::State { state_index: index },
+                message: if index == 0 {
+                    "No accepting state can be reached from q_i, this machine never accepts"
+                        .to_string()
+                } else {
+                    format!("No accepting state can be reached from {}", name(index))
+                },
+            });
+        }
+
+        if state.transitions.is_empty() {
+            findings.push(LintFinding {
+                severity: LintSeverity::Warning,
+                kind: LintKind::ImplicitReject,
+                location: LintLocation::State { state_index: index },
+                message: format!(
+                    "{} has no outgoing transitions, reaching it rejects the input",
+                    name(index)
+                ),
+            });
+        }
+
+        let positions = head_positions[index].as_ref().unwrap();
+        for (t_index, transition) in state.transitions.iter().enumerate() {
+            let location = LintLocation::Transition {
+                state_index: index,
+                transition_index: t_index,
+            };
+            let target = transition.index_to_state.unwrap();
+
+            if let Some(reason) = why_cannot_fire(transition, positions, &written_chars) {
+                findings.push(LintFinding {
+                    severity: LintSeverity::Warning,
+                    kind: LintKind::DeadTransition,
+                    location: location.clone(),
+                    message: format!(
+                        "{} {{ {} }} {} can never be taken : {}",
+                        name(index),
+                        transition,
+                        name(target),
+                        reason
+                    ),
+                });
+            }
+
+            if state.transitions[..t_index]
+                .iter()
+                .any(|t| t == transition && t.index_to_state == transition.index_to_state)
+            {
+                findings.push(LintFinding {
+                    severity: LintSeverity::Warning,
+                    kind: LintKind::DuplicateTransition,
+                    location: location.clone(),
+                    message: format!(
+                        "{} {{ {} }} {} is defined more than once",
+                        name(index),
+                        transition,
+                        name(target)
+                    ),
+                });
+            }
+
+            if states[target].state_type != TuringStateType::Normal
+                && transition
+                    .chars_write
+                    .iter()
+                    .zip(transition.chars_read.iter().skip(1))
+                    .any(|((written, _), read)| written != read)
+            {
+                findings.push(LintFinding {
+                    severity: LintSeverity::Info,
+                    kind: LintKind::NeedlessWrite,
+                    location,
+                    message: format!(
+                        "{} {{ {} }} {} writes on the tapes while going to a final state, these writes are never used",
+                        name(index),
+                        transition,
+                        name(target)
+                    ),
+                });
+            }
+        }
+    }
+
+    // Most severe first, then in the order of the graph
+    findings.sort_by(|f1, f2| {
+        f2.severity
+            .cmp(&f1.severity)
+            .then(f1.location.cmp(&f2.location))
+    });
+    findings
+}
+
+/// Computes, for every state reachable from `q_i`, the set of positions that the head of each tape (reading tape first) can have when entering it.
+///
+/// Unreachable states are represented by [None]. Since the tapes are treated independently, this is an over-approximation.
+fn compute_head_positions(
+    graph: &TuringMachineGraph,
+    written_chars: &[HashSet<char>],
+) -> Vec<Option<Vec<HashSet<HeadPosition>>>> {
+    let states = graph.get_states();
+    let nb_tapes = graph.get_k() + 1;
+    let mut positions: Vec<Option<Vec<HashSet<HeadPosition>>>> = vec![None; states.len()];
+
+    // Every head starts on the first cell
+    positions[0] = Some(vec![HashSet::from([HeadPosition::Start]); nb_tapes]);
+    let mut to_visit = VecDeque::from([0]);
+
+    while let Some(index) = to_visit.pop_front() {
+        // Final states stop (or backtrack) the execution, their transitions are never taken
+        if states[index].state_type != TuringStateType::Normal {
+            continue;
+        }
+        let current = positions[index].clone().unwrap();
+
+        for transition in &states[index].transitions {
+            if why_cannot_fire(transition, &current, written_chars).is_some() {
+                continue;
+            }
+            let target = transition.index_to_state.unwrap();
+            let moves = std::iter::once(&transition.move_read)
+                .chain(transition.chars_write.iter().map(|(_, dir)| dir));
+
+            let mut changed = false;
+            let target_positions =
+                positions[target].get_or_insert_with(|| vec![HashSet::new(); nb_tapes]);
+
+            for (tape, direction) in moves.enumerate() {
+                // The character read tells us where the head is
+                let before = if transition.chars_read[tape] == INIT_CHAR {
+                    HeadPosition::Start
+                } else {
+                    HeadPosition::After
+                };
+                let after: &[HeadPosition] = match (before, direction.get_value()) {
+                    (HeadPosition::Start, 0) => &[HeadPosition::Start],
+                    (HeadPosition::Start, _) => &[HeadPosition::After],
+                    (HeadPosition::After, -1) => &[HeadPosition::Start, HeadPosition::After],
+                    (HeadPosition::After, _) => &[HeadPosition::After],
+                };
+                for position in after {
+                    changed |= target_positions[tape].insert(*position);
+                }
+            }
+
+            if changed {
+                to_visit.push_back(target);
+            }
+        }
+    }
+    positions
+}
+
+/// Computes, for every writing tape, all the characters that can be found on it.
+fn compute_written_chars(graph: &TuringMachineGraph) -> Vec<HashSet<char>> {
+    let mut written_chars = vec![HashSet::from([INIT_CHAR, BLANK_CHAR]); graph.get_k()];
+    for state in graph.get_states() {
+        for transition in &state.transitions {
+            for (tape, (char_written, _)) in transition.chars_write.iter().enumerate() {
+                written_chars[tape].insert(*char_written);
+            }
+        }
+    }
+    written_chars
+}
+
+/// Computes which states can reach an accepting state.
+fn compute_co_reachable(graph: &TuringMachineGraph) -> Vec<bool> {
+    let states = graph.get_states();
+    let mut co_reachable: Vec<bool> = states
+        .iter()
+        .map(|s| s.state_type == TuringStateType::Accepting)
+        .collect();
+
+    // Iterate until nothing changes, graphs are small enough for this to be fast
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (index, state) in states.iter().enumerate() {
+            if co_reachable[index] || state.state_type != TuringStateType::Normal {
+                continue;
+            }
+            if state
+                .transitions
+                .iter()
+                .any(|t| co_reachable[t.index_to_state.unwrap()])
+            {
+                co_reachable[index] = true;
+                changed = true;
+            }
+        }
+    }
+    co_reachable
+}
+
+/// Checks if the given transition can be taken knowing the possible positions of the heads.
+///
+/// ## Returns
+/// * [None] if the transition could be taken.
+/// * [Some] containing the reason why it can never be taken otherwise.
+fn why_cannot_fire(
+    transition: &TuringTransition,
+    positions: &[HashSet<HeadPosition>],
+    written_chars: &[HashSet<char>],
+) -> Option<String> {
+    for (tape, char_read) in transition.chars_read.iter().enumerate() {
+        let tape_name = if tape == 0 {
+            "the reading tape".to_string()
+        } else {
+            format!("the writing tape {}", tape - 1)
+        };
+
+        if *char_read == INIT_CHAR && !positions[tape].contains(&HeadPosition::Start) {
+            return Some(format!(
+                "it reads '{}' on {} but its head is never on the first cell here",
+                INIT_CHAR, tape_name
+            ));
+        }
+        if *char_read != INIT_CHAR && !positions[tape].contains(&HeadPosition::After) {
+            return Some(format!(
+                "it reads '{}' on {} but its head is always on the first cell ('{}') here",
+                char_read, tape_name, INIT_CHAR
+            ));
+        }
+        // Writing tapes only contain blanks and characters written by the machine
+        if tape != 0 && !written_chars[tape - 1].contains(char_read) {
+            return Some(format!(
+                "it reads '{}' on {} but this character is never written on it",
+                char_read, tape_name
+            ));
+        }
+    }
+    None
+}
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use crate::{
+    turing_graph::TuringMachineGraph,
+    turing_state::{TuringStateType, TuringTransition},
+};
+
+/// A hashable representation of the label of a transition (everything but the state it goes to).
+type TransitionLabel = (Vec<char>, i8, Vec<(char, i8)>);
+
+/// Merges all the equivalent states of the given graph and returns the resulting graph,
+/// along with a mapping from the names of the states of the given graph to the names of the states of the new one.
+///
+/// Two states are considered equivalent when they have the same [TuringStateType] and when, for every transition leaving one of them,
+/// the other one has a transition with the same label going to an equivalent state.
+/// This is computed by partition refinement, like for the minimization of a DFA.
+/// Since the transitions of final states are never taken, they are ignored when comparing them.
+///
+/// Every merged state keeps the name of the state with the smallest index, meaning that `q_i`, `q_a` and `q_r` are never renamed.
+/// Note that unreachable states are not removed.
+///
+/// The minimized graph recognizes the same language, so it keeps the tests embedded in the given graph.
+pub fn minimize_graph(graph: &TuringMachineGraph) -> (TuringMachineGraph, HashMap<String, String>) {
+    let states = graph.get_states();
+    let blocks = compute_equivalence_blocks(graph);
+
+    // The representative of a block is its first state
+    let mut representatives: Vec<usize> = vec![];
+    for (index, block) in blocks.iter().enumerate() {
+        if *block == representatives.len() {
+            representatives.push(index);
+        }
+    }
+
+    let mut minimized = TuringMachineGraph::new(graph.get_k()).unwrap();
+    for representative in &representatives {
+        minimized.add_state(&states[*representative].name);
+    }
+
+    for representative in &representatives {
+        let from = &states[*representative].name;
+        let mut added: Vec<(TransitionLabel, usize)> = vec![];
+
+        for transition in &states[*representative].transitions {
+            let to_block = blocks[transition.index_to_state.unwrap()];
+            let key = (get_label(transition), to_block);
+            // Transitions that became identical once merged are only added once
+            if added.contains(&key) {
+                continue;
+            }
+            added.push(key);
+
+            let mut transition = transition.clone();
+            transition.index_to_state = None;
+            // The transition was valid in the original graph, so it is valid in this one
+            minimized
+                .append_rule_state_by_name(
+                    from,
+                    transition,
+                    &states[representatives[to_block]].name,
+                )
+                .unwrap();
+        }
+    }
+
+    for test in graph.get_embedded_tests() {
+        minimized.add_embedded_test(test.clone());
+    }
+
+    let mapping = states
+        .iter()
+        .enumerate()
+        .map(|(index, state)| {
+            (
+                state.name.clone(),
+                states[representatives[blocks[index]]].name.clone(),
+            )
+        })
+        .collect();
+
+    (minimized, mapping)
+}
+
+/// Computes the coarsest partition of the states of the graph in which all the states of a block are equivalent.
+///
+/// ## Returns
+/// For each state, the index of its block. Blocks are numbered in the order of their first state.
+fn compute_equivalence_blocks(graph: &TuringMachineGraph) -> Vec<usize> {
+    let states = graph.get_states();
+
+    // Start by separating the states by type
+    let mut blocks = number_blocks(states.iter().map(|s| match s.state_type {
+        TuringStateType::Normal => 0,
+        TuringStateType::Accepting => 1,
+        TuringStateType::Rejecting => 2,
+    }));
+    let mut nb_blocks = count_blocks(&blocks);
+
+    loop {
+        let signatures = states.iter().enumerate().map(|(index, state)| {
+            let mut outgoing: Vec<(TransitionLabel, usize)> = vec![];
+            if state.state_type == TuringStateType::Normal {
+                outgoing = state
+                    .transitions
+                    .iter()
+                    .map(|t| (get_label(t), blocks[t.index_to_state.unwrap()]))
+                    .collect();
+                outgoing.sort();
+                outgoing.dedup();
+            }
+            (blocks[index], outgoing)
+        });
+        let refined = number_blocks(signatures);
+        let nb_refined = count_blocks(&refined);
+
+        blocks = refined;
+        // A refinement can only split blocks, so the partition is stable once their number stops growing
+        if nb_refined == nb_blocks {
+            return blocks;
+        }
+        nb_blocks = nb_refined;
+    }
+}
+
+/// Gives the same number to equal keys, numbering them in the order they first appear.
+fn number_blocks<K: Eq + std::hash::Hash>(keys: impl Iterator<Item = K>) -> Vec<usize> {
+    let mut numbers: HashMap<K, usize> = HashMap::new();
+    keys.map(|key| {
+        let next = numbers.len();
+        *numbers.entry(key).or_insert(next)
+    })
+    .collect()
+}
+
+fn count_blocks(blocks: &[usize]) -> usize {
+    blocks.iter().max().map_or(0, |max| max + 1)
+}
+
+fn get_label(transition: &TuringTransition) -> TransitionLabel {
+    (
+        transition.chars_read.clone(),
+        transition.move_read.get_value(),
+        transition
+            .chars_write
+            .iter()
+            .map(|(c, dir)| (*c, dir.get_value()))
+            .collect(),
+    )
+}
@@ -1,11 +1,11 @@
 use pest::{Parser, error::Error, iterators::Pair};
 use pest_derive::Parser;
-use std::fs;
+use std::{fs, ops::Range};
 
 use crate::{
     turing_errors::{TuringError, TuringParserError},
-    turing_graph::TuringMachineGraph,
-    turing_state::{TuringDirection, TuringTransition},
+    turing_graph::{EmbeddedTest, TuringMachineGraph},
+    turing_state::{TuringDirection, TuringStateType, TuringTransition},
 };
 
 #[derive(Parser)]
@@ -56,10 +56,13 @@ pub fn parse_turing_graph_string(
     let file = file.unwrap().next().unwrap(); // get and unwrap the `file` rule; never fails
 
     let mut turing_machine: Option<TuringMachineGraph> = None;
+    // The annotations are applied once all the states are known
+    let mut annotations = vec![];
+    let mut embedded_tests = vec![];
 
     for turing_machine_rule in file.into_inner() {
         let rule_cp = turing_machine_rule.clone();
-        // Inside the 'turing_machine' rule, only two things can be matched : a transition (or multiple in one), and EOI
+        // Inside the 'turing_machine' rule, only four things can be matched : a transition (or multiple in one), an annotation, an embedded test and EOI
         match turing_machine_rule.as_rule() {
             // For every rule matched :
             Rule::transition => {
@@ -105,17 +108,219 @@ pub fn parse_turing_graph_string(
                     }
                 }
             }
+            Rule::pos_annotation
+            | Rule::color_annotation
+            | Rule::doc_annotation
+            | Rule::type_annotation => {
+                annotations.push(turing_machine_rule);
+            }
+            Rule::embedded_test => {
+                embedded_tests.push(parse_embedded_test(turing_machine_rule));
+            }
             Rule::semicolon => {}
             // The file has ended, this means we reached the last matched rule
             Rule::EOI => {}
             _ => unreachable!(),
         }
     }
-    match turing_machine {
-        Some(t) => Ok(t),
-        // If no parse value was given, simply return a read only one
-        None => Ok(TuringMachineGraph::new(1).unwrap()),
+    // If no parse value was given, simply return a read only one
+    let mut turing_machine = turing_machine.unwrap_or_else(|| TuringMachineGraph::new(1).unwrap());
+    for annotation in annotations {
+        parse_annotation(&mut turing_machine, annotation)?;
+    }
+    for test in embedded_tests {
+        turing_machine.add_embedded_test(test);
+    }
+    Ok(turing_machine)
+}
+
+/// Parses the content of a `.tm` file statement by statement, to find all its syntax errors instead of only the first one.
+///
+/// Each statement ends with a `;` and is parsed on its own, so the errors that depend on several statements,
+/// like transitions affecting different numbers of tapes, are only reported when no syntax error is found.
+/// An empty vector is returned if the content can be parsed by [parse_turing_graph_string].
+pub fn find_turing_graph_errors(content: &str) -> Vec<TuringParserError> {
+    let error = match parse_turing_graph_string(content.to_string()) {
+        Ok(_) => return vec![],
+        Err(e) => e,
+    };
+    let mut errors = vec![];
+    for range in statement_ranges(content) {
+        // Blanking the rest of the content keeps the lines and columns of the errors
+        let statement: String = content
+            .char_indices()
+            .map(|(i, c)| {
+                if range.contains(&i) || c == '\n' {
+                    c
+                } else {
+                    ' '
+                }
+            })
+            .collect();
+        // The other errors are about the states declared by the other statements
+        if let Err(TuringParserError::ParsingError {
+            line_col_pos,
+            missing_value,
+            ..
+        }) = parse_turing_graph_string(statement)
+        {
+            let line = line_col_pos.map_or(0, |(line, _)| line);
+            errors.push(TuringParserError::ParsingError {
+                line_col_pos,
+                value: content
+                    .lines()
+                    .nth(line.saturating_sub(1))
+                    .unwrap_or("")
+                    .to_string(),
+                missing_value,
+            });
+        }
+    }
+    if errors.is_empty() {
+        errors.push(error);
+    }
+    errors
+}
+
+/// Returns the byte ranges of the statements of the content, each one ending with a `;`, leaving out the end of the content if it is only made of comments.
+fn statement_ranges(content: &str) -> Vec<Range<usize>> {
+    let mut ranges = vec![];
+    let mut start = 0;
+    let mut has_code = false;
+    let mut in_comment = false;
+    let mut in_string = false;
+    let mut chars = content.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if in_comment {
+            in_comment = c != '\n';
+            continue;
+        }
+        if in_string {
+            match c {
+                '\\' => {
+                    chars.next();
+                }
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '/' if chars.peek().is_some_and(|(_, next)| *next == '/') => in_comment = true,
+            '"' => {
+                in_string = true;
+                has_code = true;
+            }
+            ';' => {
+                ranges.push(start..i + 1);
+                start = i + 1;
+                has_code = false;
+            }
+            c if !c.is_whitespace() => has_code = true,
+            _ => {}
+        }
+    }
+    if has_code {
+        ranges.push(start..content.len());
+    }
+    ranges
+}
+
+/// Parses an embedded test, like `// @accept 0110`. A test without a word is about the empty word.
+fn parse_embedded_test(rule: Pair<Rule>) -> EmbeddedTest {
+    let line = rule.line_col().0;
+    let test = rule.into_inner().next().unwrap();
+    let accept = match test.as_rule() {
+        Rule::accept_test => true,
+        Rule::reject_test => false,
+        _ => unreachable!(),
+    };
+    EmbeddedTest {
+        word: test
+            .into_inner()
+            .next()
+            .map(|word| word.as_str().to_string())
+            .unwrap_or_default(),
+        accept,
+        line,
+    }
+}
+
+/// Stores the content of an annotation, like `@pos(q_1, 120, 340)`, in the metadata of its state,
+/// or changes the type of its state for `@type(q_1, accepting)`.
+///
+/// The state is created if it does not exist, since the states without transitions are only written through their annotations.
+fn parse_annotation(
+    turing_machine: &mut TuringMachineGraph,
+    rule: Pair<Rule>,
+) -> Result<(), TuringParserError> {
+    let rule_cp = rule.clone();
+    let kind = rule.as_rule();
+    let mut inner = rule.into_inner();
+    let name = parse_str_token(inner.next().unwrap());
+    let to_parser_error = |e| TuringParserError::EncounteredTuringError {
+        line_col_pos: Some(rule_cp.line_col()),
+        turing_error: e,
+        value: rule_cp.as_str().to_string(),
+    };
+    turing_machine.add_state(&name);
+
+    if kind == Rule::type_annotation {
+        let state_type = match inner.next().unwrap().as_str() {
+            "accepting" => TuringStateType::Accepting,
+            "rejecting" => TuringStateType::Rejecting,
+            _ => TuringStateType::Normal,
+        };
+        return turing_machine
+            .set_state_type(&name, state_type)
+            .map(|_| ())
+            .map_err(to_parser_error);
+    }
+
+    let metadata = turing_machine
+        .get_state_metadata_mut(&name)
+        .map_err(to_parser_error)?;
+    match kind {
+        Rule::pos_annotation => {
+            // The grammar only accepts valid numbers
+            let x = inner.next().unwrap().as_str().parse().unwrap();
+            let y = inner.next().unwrap().as_str().parse().unwrap();
+            metadata.position = Some((x, y));
+        }
+        Rule::color_annotation => {
+            metadata.color = Some(inner.next().unwrap().as_str().to_lowercase());
+        }
+        Rule::doc_annotation => {
+            let content = inner.next().unwrap().into_inner().as_str();
+            metadata.doc = Some(unescape_string(content));
+        }
+        _ => unreachable!(),
+    }
+    Ok(())
+}
+
+fn unescape_string(content: &str) -> String {
+    let mut res = String::new();
+    let mut chars = content.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            // The grammar ensures that an escaped character always follows
+            match chars.next().unwrap() {
+                'n' => res.push('\n'),
+                c => res.push(c),
+            }
+        } else {
+            res.push(c);
+        }
     }
+    res
+}
+
+fn escape_string(content: &str) -> String {
+    content
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
 }
 
 /// Parses a string containing a transition of the form :
@@ -157,6 +362,63 @@ pub fn parse_transition_content_string(
     // FIXME: test this
 }
 
+/// The kind of a [TuringToken].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TuringTokenKind {
+    /// A state name, like `q_1`
+    StateName,
+    /// A character read or written, like `0` or `ç`
+    Symbol,
+    /// A direction of a head : `L`, `R` or `N`
+    Direction,
+    /// The `->` arrow separating the characters read from the actions
+    Arrow,
+    /// A `{` or a `}`
+    Bracket,
+    /// A `,`, a `|` or a `;`
+    Separator,
+    /// Anything else, that can never be part of a transition
+    Unknown,
+}
+
+/// A piece of the source of a turing machine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TuringToken {
+    pub kind: TuringTokenKind,
+    /// The position of the token in the source, in bytes
+    pub span: Range<usize>,
+}
+
+/// Splits the given source into the tokens used by the grammar of the transitions, skipping the spaces and the comments.
+///
+/// Unlike the other parsing functions, this one never fails : the source does not need to be complete or valid,
+/// which makes it possible to color it while it is being typed. Since the tokens are read without their context,
+/// a `L`, `R` or `N` is always seen as a [TuringTokenKind::Direction].
+pub fn tokenize_turing_string(source: &str) -> Vec<TuringToken> {
+    // Every character can at least be read as an unknown token
+    let parsed = TuringGrammar::parse(Rule::tokens, source).unwrap();
+    parsed
+        .filter_map(|pair| {
+            let kind = match pair.as_rule() {
+                Rule::state_name => TuringTokenKind::StateName,
+                Rule::char | Rule::special_chars => TuringTokenKind::Symbol,
+                Rule::dir_left | Rule::dir_right | Rule::dir_none => TuringTokenKind::Direction,
+                Rule::arrow => TuringTokenKind::Arrow,
+                Rule::left_bracket | Rule::right_bracket => TuringTokenKind::Bracket,
+                Rule::separator | Rule::semicolon => TuringTokenKind::Separator,
+                Rule::unknown => TuringTokenKind::Unknown,
+                _ => return None,
+            };
+            // The spaces read after the non atomic rules are not part of the token
+            let start = pair.as_span().start();
+            Some(TuringToken {
+                kind,
+                span: start..start + pair.as_str().trim_end().len(),
+            })
+        })
+        .collect()
+}
+
 fn parse_transition(
     rule: Pair<Rule>,
 ) -> Result<(String, Vec<TuringTransition>, String), TuringParserError> {
@@ -280,6 +542,19 @@ fn get_line_col(error: &Error<Rule>) -> Option<(usize, usize)> {
     }
 }
 
+/// Writes the transitions going from one state to another, with one transition per line : `q_1 { transition_0 | ... | transition_n } q_2;`.
+pub(crate) fn transitions_to_string(
+    from: &str,
+    transitions: &[&TuringTransition],
+    to: &str,
+) -> String {
+    let contents: Vec<String> = transitions.iter().map(|t| t.to_string()).collect();
+    format!(
+        "q_{from} {{ {} }} q_{to};",
+        contents.join(&format!("\n{}| ", " ".repeat(3 + from.len())))
+    )
+}
+
 /// Turns the given [TuringMachineGraph] into its equivalent [String] value.
 /// The returned value can then be parsed by the parser to return the same graph.
 ///
@@ -287,25 +562,29 @@ fn get_line_col(error: &Error<Rule>) -> Option<(usize, usize)> {
 pub fn graph_to_string(tm: &TuringMachineGraph) -> String {
     let mut res = String::new();
 
-    // Print all transitions btw states
-    for (q1, i1) in tm.get_name_index_hashmap() {
-        for (q2, i2) in tm.get_name_index_hashmap() {
-            let transitions = tm.get_transitions_by_index(*i1, *i2).unwrap();
+    // Print the embedded tests first, as they describe what the machine does
+    for test in tm.get_embedded_tests() {
+        let kind = if test.accept { "accept" } else { "reject" };
+        if test.word.is_empty() {
+            res.push_str(&format!("// @{kind}\n"));
+        } else {
+            res.push_str(&format!("// @{kind} {}\n", test.word));
+        }
+    }
+    if !res.is_empty() {
+        res.push('\n');
+    }
+
+    // Print all transitions btw states, in the order of the states
+    let states = tm.get_states();
+    for (i1, q1) in states.iter().enumerate() {
+        for (i2, q2) in states.iter().enumerate() {
+            let transitions = tm.get_transitions_by_index(i1, i2).unwrap();
             if transitions.is_empty() {
                 continue;
             }
-            res.push_str(format!("q_{} {} ", q1, '{').as_str());
-            let spaces = 3 + q1.len();
-
-            for i in 0..transitions.len() - 1 {
-                res.push_str(
-                    format!("{} \n{}| ", transitions.get(i).unwrap(), " ".repeat(spaces)).as_str(),
-                );
-            }
-            // add last
-            res.push_str(format!("{} ", transitions.last().unwrap()).as_str());
-
-            res.push_str(format!("{} q_{};\n\n", "}", q2).as_str());
+            res.push_str(&transitions_to_string(&q1.name, &transitions, &q2.name));
+            res.push_str("\n\n");
         }
     }
     if !res.is_empty() {
@@ -313,5 +592,47 @@ pub fn graph_to_string(tm: &TuringMachineGraph) -> String {
         res.pop().unwrap();
     }
 
+    // Print the types and the metadata of the states, in the order of the states
+    let mut annotations = String::new();
+    for (index, state) in tm.get_states().iter().enumerate() {
+        // The types of the default states cannot change
+        if index > 2 && state.state_type != TuringStateType::Normal {
+            annotations.push_str(&format!(
+                "@type(q_{}, {})\n",
+                state.name,
+                state.state_type.to_string().to_lowercase()
+            ));
+        }
+        let Some(metadata) = tm.get_state_metadata(&state.name) else {
+            continue;
+        };
+        if let Some((x, y)) = metadata.position
+            && x.is_finite()
+            && y.is_finite()
+        {
+            annotations.push_str(&format!("@pos(q_{}, {x}, {y})\n", state.name));
+        }
+        if let Some(color) = &metadata.color
+            && color.len() == 7
+            && color.starts_with('#')
+            && color[1..].chars().all(|c| c.is_ascii_hexdigit())
+        {
+            annotations.push_str(&format!("@color(q_{}, {color})\n", state.name));
+        }
+        if let Some(doc) = &metadata.doc {
+            annotations.push_str(&format!(
+                "@doc(q_{}, \"{}\")\n",
+                state.name,
+                escape_string(doc)
+            ));
+        }
+    }
+    if !annotations.is_empty() {
+        if !res.is_empty() {
+            res.push('\n');
+        }
+        res.push_str(&annotations);
+    }
+
     res
 }
@@ -0,0 +1,500 @@
+use std::fmt::Display;
+
+use crate::{
+    turing_equivalence::{RunOutcome, run_inspecting, words_up_to},
+    turing_errors::TuringError,
+    turing_graph::TuringMachineGraph,
+    turing_machine::Mode,
+    turing_tape::TuringTape,
+};
+
+/// How the words given to the machine are generated for each length.
+pub enum InputGenerator {
+    /// Every word of the length
+    All,
+    /// `samples` words picked at random, the same seed always giving the same words
+    Random { samples: usize, seed: u64 },
+    /// The words returned by the function for the given length
+    Custom(Box<dyn Fn(usize) -> Vec<String>>),
+}
+
+impl InputGenerator {
+    /// Creates a generator that gives the words of the list that have the requested length.
+    pub fn from_words(words: Vec<String>) -> Self {
+        InputGenerator::Custom(Box::new(move |n| {
+            words
+                .iter()
+                .filter(|word| word.chars().count() == n)
+                .cloned()
+                .collect()
+        }))
+    }
+}
+
+/// The options of [profile_complexity].
+pub struct ProfileOptions {
+    /// The characters the generated words are made of, or the characters read by the machine if none are given
+    pub alphabet: Option<Vec<char>>,
+    /// The lengths of the words to run the machine on
+    pub lengths: Vec<usize>,
+    pub generator: InputGenerator,
+    /// The maximum number of steps of each execution, backtracking included
+    pub step_budget: usize,
+}
+
+/// The measures taken on the words of one length.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LengthProfile {
+    /// The length of the words
+    pub n: usize,
+    /// The number of words the machine was run on
+    pub samples: usize,
+    /// The highest number of steps taken on a word, backtracking included
+    pub worst_steps: usize,
+    /// The average number of steps taken on a word
+    pub average_steps: f64,
+    /// The highest number of cells used on a writing tape, [crate::turing_tape::INIT_CHAR] included
+    pub peak_tape_length: usize,
+    /// The number of executions stopped because they ran out of steps, their number of steps being the budget
+    pub out_of_budget: usize,
+}
+
+/// A function of `n` that a measure is compared to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ComplexityModel {
+    Linear,
+    NLogN,
+    Quadratic,
+}
+
+impl ComplexityModel {
+    /// The models a measure is compared to, from the slowest growing to the fastest.
+    pub const ALL: [ComplexityModel; 3] = [
+        ComplexityModel::Linear,
+        ComplexityModel::NLogN,
+        ComplexityModel::Quadratic,
+    ];
+
+    /// Returns the value of the model for `n`.
+    pub fn eval(&self, n: f64) -> f64 {
+        match self {
+            ComplexityModel::Linear => n,
+            ComplexityModel::NLogN => {
+                if n > 1. {
+                    n * n.log2()
+                } else {
+                    0.
+                }
+            }
+            ComplexityModel::Quadratic => n * n,
+        }
+    }
+}
+
+impl Display for ComplexityModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ComplexityModel::Linear => "O(n)",
+                ComplexityModel::NLogN => "O(n log n)",
+                ComplexityModel::Quadratic => "O(n²)",
+            }
+        )
+    }
+}
+
+/// A measure approximated by `intercept + slope * model(n)` using the least squares method.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fit {
+    pub model: ComplexityModel,
+    pub intercept: f64,
+    pub slope: f64,
+    /// The coefficient of determination of the fit, `1` meaning that the model explains every measure
+    pub r_squared: f64,
+}
+
+impl Fit {
+    /// Returns the value predicted by this fit for `n`.
+    pub fn predict(&self, n: f64) -> f64 {
+        self.intercept + self.slope * self.model.eval(n)
+    }
+}
+
+impl Display for Fit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} (R² = {:.4}, {:.2} + {:.2} * {})",
+            self.model,
+            self.r_squared,
+            self.intercept,
+            self.slope,
+            match self.model {
+                ComplexityModel::Linear => "n",
+                ComplexityModel::NLogN => "n log n",
+                ComplexityModel::Quadratic => "n²",
+            }
+        )
+    }
+}
+
+/// The result of [profile_complexity].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComplexityProfile {
+    /// The measures of each length that had at least one word, ordered by length
+    pub lengths: Vec<LengthProfile>,
+    /// The fits of the worst number of steps, in the order of [ComplexityModel::ALL]
+    pub time_fits: Vec<Fit>,
+    /// The fits of the peak tape length, in the order of [ComplexityModel::ALL]
+    pub space_fits: Vec<Fit>,
+}
+
+impl ComplexityProfile {
+    /// Returns the fit of the worst number of steps with the highest R², if there were enough lengths to compute one.
+    pub fn best_time_fit(&self) -> Option<&Fit> {
+        best_fit(&self.time_fits)
+    }
+
+    /// Returns the fit of the peak tape length with the highest R², if there were enough lengths to compute one.
+    pub fn best_space_fit(&self) -> Option<&Fit> {
+        best_fit(&self.space_fits)
+    }
+
+    /// Writes the measures as a text table, followed by the fits of each measure.
+    pub fn to_table(&self) -> String {
+        let mut res = format!(
+            "{:>6} | {:>8} | {:>11} | {:>13} | {:>10} | {:>13}\n",
+            "n", "samples", "worst steps", "average steps", "peak space", "out of budget"
+        );
+        res.push_str(&format!("{}\n", "-".repeat(res.chars().count() - 1)));
+        for length in &self.lengths {
+            res.push_str(&format!(
+                "{:>6} | {:>8} | {:>11} | {:>13.2} | {:>10} | {:>13}\n",
+                length.n,
+                length.samples,
+                length.worst_steps,
+                length.average_steps,
+                length.peak_tape_length,
+                length.out_of_budget
+            ));
+        }
+        for (name, fits, best) in [
+            ("Time", &self.time_fits, self.best_time_fit()),
+            ("Space", &self.space_fits, self.best_space_fit()),
+        ] {
+            res.push_str(&format!("\n{name} :\n"));
+            if fits.is_empty() {
+                res.push_str("  not enough lengths to fit a model\n");
+            }
+            for fit in fits {
+                let marker = if Some(fit) == best { "*" } else { " " };
+                res.push_str(&format!("{marker} {fit}\n"));
+            }
+        }
+        res
+    }
+
+    /// Draws the measures as an SVG picture, with the time on the left and the space on the right.
+    ///
+    /// The measures are drawn as plain lines and the best fit of each one as a dashed line.
+    pub fn to_svg(&self) -> String {
+        let ns: Vec<f64> = self.lengths.iter().map(|l| l.n as f64).collect();
+        let worst: Vec<f64> = self.lengths.iter().map(|l| l.worst_steps as f64).collect();
+        let average: Vec<f64> = self.lengths.iter().map(|l| l.average_steps).collect();
+        let space: Vec<f64> = self
+            .lengths
+            .iter()
+            .map(|l| l.peak_tape_length as f64)
+            .collect();
+
+        let mut res = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" font-family=\"sans-serif\" font-size=\"12\">\n",
+            2 * PLOT_WIDTH,
+            PLOT_HEIGHT
+        );
+        res.push_str(&svg_plot(
+            0.,
+            "Steps",
+            &ns,
+            &[
+                ("worst", "#d62728", &worst),
+                ("average", "#1f77b4", &average),
+            ],
+            self.best_time_fit(),
+        ));
+        res.push_str(&svg_plot(
+            PLOT_WIDTH as f64,
+            "Peak tape length",
+            &ns,
+            &[("peak", "#2ca02c", &space)],
+            self.best_space_fit(),
+        ));
+        res.push_str("</svg>\n");
+        res
+    }
+}
+
+/// Runs the machine on generated words of each given length, and measures the number of steps and the tape length it needs.
+///
+/// The worst number of steps and the peak tape length are then fitted against the models of [ComplexityModel::ALL].
+/// The executions running out of steps are counted with the budget as their number of steps, so the budget should be high enough for every word.
+///
+/// If a generated word contains a character that cannot be part of an input, the [TuringError] raised when creating the execution is returned.
+pub fn profile_complexity(
+    graph: &TuringMachineGraph,
+    options: &ProfileOptions,
+) -> Result<ComplexityProfile, TuringError> {
+    let mut alphabet = match &options.alphabet {
+        Some(alphabet) => alphabet.clone(),
+        None => graph.get_input_alphabet(),
+    };
+    alphabet.sort();
+    alphabet.dedup();
+
+    let mut lengths = options.lengths.clone();
+    lengths.sort();
+    lengths.dedup();
+
+    let mut rng = match options.generator {
+        InputGenerator::Random { seed, .. } => XorShift::new(seed),
+        _ => XorShift::new(0),
+    };
+
+    let mut profiles = vec![];
+    for n in lengths {
+        let words = match &options.generator {
+            InputGenerator::All => words_up_to(&alphabet, n)
+                .into_iter()
+                .filter(|word| word.chars().count() == n)
+                .collect(),
+            InputGenerator::Random { samples, .. } => {
+                if alphabet.is_empty() {
+                    vec![]
+                } else {
+                    (0..*samples)
+                        .map(|_| {
+                            (0..n)
+                                .map(|_| alphabet[rng.next() as usize % alphabet.len()])
+                                .collect()
+                        })
+                        .collect()
+                }
+            }
+            InputGenerator::Custom(generator) => generator(n),
+        };
+        if words.is_empty() {
+            continue;
+        }
+
+        let mut profile = LengthProfile {
+            n,
+            samples: words.len(),
+            worst_steps: 0,
+            average_steps: 0.,
+            peak_tape_length: 0,
+            out_of_budget: 0,
+        };
+        let mut total_steps = 0;
+        for word in &words {
+            let (steps, peak, finished) = measure(graph, word, options.step_budget)?;
+            total_steps += steps;
+            profile.worst_steps = profile.worst_steps.max(steps);
+            profile.peak_tape_length = profile.peak_tape_length.max(peak);
+            if !finished {
+                profile.out_of_budget += 1;
+            }
+        }
+        profile.average_steps = total_steps as f64 / words.len() as f64;
+        profiles.push(profile);
+    }
+
+    let ns: Vec<f64> = profiles.iter().map(|p| p.n as f64).collect();
+    let worst: Vec<f64> = profiles.iter().map(|p| p.worst_steps as f64).collect();
+    let space: Vec<f64> = profiles.iter().map(|p| p.peak_tape_length as f64).collect();
+    Ok(ComplexityProfile {
+        time_fits: fit_models(&ns, &worst),
+        space_fits: fit_models(&ns, &space),
+        lengths: profiles,
+    })
+}
+
+/// Runs the machine on the word, and returns the number of steps taken, the peak length of the writing tapes and whether the execution ended before the budget.
+fn measure(
+    graph: &TuringMachineGraph,
+    word: &str,
+    step_budget: usize,
+) -> Result<(usize, usize, bool), TuringError> {
+    let mut peak = 0;
+    let (result, _) = run_inspecting(graph, word, Mode::SaveAll, step_budget, |step| {
+        peak = step
+            .get_writing_tapes()
+            .iter()
+            .map(|tape| tape.get_contents().len())
+            .fold(peak, usize::max);
+    })?;
+    Ok((
+        result.steps,
+        peak,
+        result.outcome != RunOutcome::OutOfBudget,
+    ))
+}
+
+/// Fits the measures against every model, nothing being returned if there are less than two different lengths.
+fn fit_models(ns: &[f64], values: &[f64]) -> Vec<Fit> {
+    ComplexityModel::ALL
+        .iter()
+        .filter_map(|model| {
+            if ns.len() < 2 {
+                return None;
+            }
+            let xs: Vec<f64> = ns.iter().map(|n| model.eval(*n)).collect();
+            let count = xs.len() as f64;
+            let mean_x = xs.iter().sum::<f64>() / count;
+            let mean_y = values.iter().sum::<f64>() / count;
+            let var_x: f64 = xs.iter().map(|x| (x - mean_x).powi(2)).sum();
+            if var_x == 0. {
+                return None;
+            }
+            let cov: f64 = xs
+                .iter()
+                .zip(values)
+                .map(|(x, y)| (x - mean_x) * (y - mean_y))
+                .sum();
+            let slope = cov / var_x;
+            let intercept = mean_y - slope * mean_x;
+
+            let total: f64 = values.iter().map(|y| (y - mean_y).powi(2)).sum();
+            let residual: f64 = xs
+                .iter()
+                .zip(values)
+                .map(|(x, y)| (y - intercept - slope * x).powi(2))
+                .sum();
+            Some(Fit {
+                model: *model,
+                intercept,
+                slope,
+                // Constant measures are perfectly explained by any model
+                r_squared: if total == 0. {
+                    1.
+                } else {
+                    1. - residual / total
+                },
+            })
+        })
+        .collect()
+}
+
+/// Returns the fit with the highest R², the slowest growing model winning ties.
+fn best_fit(fits: &[Fit]) -> Option<&Fit> {
+    fits.iter()
+        .fold(None, |best: Option<&Fit>, fit| match best {
+            Some(best) if best.r_squared >= fit.r_squared - 1e-9 => Some(best),
+            _ => Some(fit),
+        })
+}
+
+/// A small pseudo-random generator, so that random profiles can be reproduced without any dependency.
+struct XorShift(u64);
+
+impl XorShift {
+    fn new(seed: u64) -> Self {
+        // The state must never be 0
+        Self(match seed ^ 0x9E37_79B9_7F4A_7C15 {
+            0 => 1,
+            state => state,
+        })
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+const PLOT_WIDTH: usize = 400;
+const PLOT_HEIGHT: usize = 300;
+const MARGIN: f64 = 45.;
+
+/// Draws one plot of the SVG picture, starting at the given horizontal offset.
+fn svg_plot(
+    offset: f64,
+    title: &str,
+    ns: &[f64],
+    series: &[(&str, &str, &[f64])],
+    fit: Option<&Fit>,
+) -> String {
+    let width = PLOT_WIDTH as f64 - 2. * MARGIN;
+    let height = PLOT_HEIGHT as f64 - 2. * MARGIN;
+    let max_n = ns.iter().cloned().fold(1., f64::max);
+    let max_y = series
+        .iter()
+        .flat_map(|(_, _, values)| values.iter().cloned())
+        .fold(1., f64::max);
+    let x = |n: f64| offset + MARGIN + n / max_n * width;
+    let y = |v: f64| MARGIN + height - v.clamp(0., max_y) / max_y * height;
+
+    let mut res = format!(
+        "  <text x=\"{:.1}\" y=\"20\" text-anchor=\"middle\" font-weight=\"bold\">{title}</text>\n",
+        offset + PLOT_WIDTH as f64 / 2.
+    );
+    // Axes along with their maximum values
+    res.push_str(&format!(
+        "  <polyline points=\"{:.1},{:.1} {:.1},{:.1} {:.1},{:.1}\" fill=\"none\" stroke=\"black\"/>\n",
+        x(0.),
+        y(max_y),
+        x(0.),
+        y(0.),
+        x(max_n),
+        y(0.)
+    ));
+    res.push_str(&format!(
+        "  <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\">{max_y}</text>\n",
+        x(0.) - 4.,
+        y(max_y) + 4.
+    ));
+    res.push_str(&format!(
+        "  <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">n = {max_n}</text>\n",
+        x(max_n),
+        y(0.) + 16.
+    ));
+
+    for (index, (name, color, values)) in series.iter().enumerate() {
+        let points: Vec<String> = ns
+            .iter()
+            .zip(values.iter())
+            .map(|(n, v)| format!("{:.1},{:.1}", x(*n), y(*v)))
+            .collect();
+        res.push_str(&format!(
+            "  <polyline points=\"{}\" fill=\"none\" stroke=\"{color}\" stroke-width=\"2\"/>\n",
+            points.join(" ")
+        ));
+        res.push_str(&format!(
+            "  <text x=\"{:.1}\" y=\"{:.1}\" fill=\"{color}\">{name}</text>\n",
+            x(0.) + 8.,
+            MARGIN + 14. * index as f64
+        ));
+    }
+    if let Some(fit) = fit {
+        let points: Vec<String> = (0..=50)
+            .map(|i| {
+                let n = max_n * i as f64 / 50.;
+                format!("{:.1},{:.1}", x(n), y(fit.predict(n)))
+            })
+            .collect();
+        res.push_str(&format!(
+            "  <polyline points=\"{}\" fill=\"none\" stroke=\"gray\" stroke-dasharray=\"4 3\"/>\n",
+            points.join(" ")
+        ));
+        res.push_str(&format!(
+            "  <text x=\"{:.1}\" y=\"{:.1}\" fill=\"gray\">{}</text>\n",
+            x(0.) + 8.,
+            MARGIN + 14. * series.len() as f64,
+            fit.model
+        ));
+    }
+    res
+}
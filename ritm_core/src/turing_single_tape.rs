@@ -0,0 +1,553 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::{
+    turing_errors::TuringError,
+    turing_graph::TuringMachineGraph,
+    turing_state::{TuringDirection, TuringStateType, TuringTransition},
+    turing_tape::{BLANK_CHAR, INIT_CHAR},
+};
+
+/// The first character used to encode the multi-track symbols (start of the Unicode *Private Use Area*).
+const FIRST_SYMBOL_CODE: u32 = 0xE000;
+/// The number of characters available in the *Private Use Area*.
+const NB_SYMBOL_CODES: u32 = 0x1900;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// A cell of the single writing tape : one cell of each of the simulated tapes (*tracks*), along with the heads that are on it.
+struct TrackSymbol {
+    /// The character of each track
+    chars: Vec<char>,
+    /// The tracks whose head is on this cell (one bit per track)
+    heads: u32,
+}
+
+impl TrackSymbol {
+    fn is_blank(&self) -> bool {
+        self.heads == 0 && self.chars.iter().all(|c| *c == BLANK_CHAR)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// The different states of the generated machine.
+enum SimState {
+    /// `q_i`, moves to the first cell of the tracks
+    Init,
+    /// Writes the first cell of the tracks, with all the heads on it
+    InitTracks,
+    /// Starts the simulation of a step of the given state of the original machine, the head is on [INIT_CHAR]
+    Start(usize),
+    /// Goes right until all heads are found, remembering the characters under the heads found so far
+    Read {
+        state: usize,
+        char_read: char,
+        seen: Vec<Option<char>>,
+    },
+    /// Goes left while applying the given transition of the given state, `pending` are the heads to place on the next cell
+    Write {
+        state: usize,
+        transition: usize,
+        pending: u32,
+    },
+    /// Places the heads moving right on the cell to the right of the one being written
+    MoveRight {
+        state: usize,
+        transition: usize,
+        pending: u32,
+        right: u32,
+    },
+    /// Comes back to the cell that was being written to continue going left
+    Back {
+        state: usize,
+        transition: usize,
+        pending: u32,
+    },
+    /// The final state with the given index
+    Final(usize),
+}
+
+/// Builds a machine with a single writing tape that accepts exactly the same words as the given one.
+///
+/// The `k` writing tapes are stored as `k` *tracks* of the single writing tape : each of its cells contains one cell of every tape,
+/// along with a marker for each head that is on it. The cell `i` of the tapes is stored on the cell `i + 1` of the writing tape,
+/// since its first cell must stay [INIT_CHAR].
+///
+/// Every step of a state of the original machine is simulated by :
+/// 1. Going right from the first cell until every head marker has been found, remembering the characters under them.
+/// 2. Choosing one of the transitions of the original state that can be taken
+///    (the reading tape is shared by both machines, so it moves like in the original one).
+/// 3. Going back to the first cell while writing the new characters and moving the head markers.
+///
+/// The states of the original machine keep their names (except for `q_i`), generated states get fresh names.
+///
+/// ## Encoding
+/// The multi-track symbols are encoded using the characters of the Unicode *Private Use Area*, starting from `U+E000`,
+/// and an empty cell is still represented by [BLANK_CHAR].
+/// Since these characters cannot be parsed, the resulting graph can be executed but not saved as a `.tm` file.
+///
+/// If there are too many symbols to encode (too many tapes or characters), a [TuringError::IllegalActionError] is returned.
+pub fn to_single_tape(graph: &TuringMachineGraph) -> Result<TuringMachineGraph, TuringError> {
+    SingleTapeBuilder::new(graph)?.build()
+}
+
+struct SingleTapeBuilder<'a> {
+    graph: &'a TuringMachineGraph,
+    /// All the characters read on the reading tape by the original machine
+    reading_chars: Vec<char>,
+    /// All the symbols that can be found on the first cell of the tracks
+    start_symbols: Vec<TrackSymbol>,
+    /// All the symbols that can be found on the other cells of the tracks
+    symbols: Vec<TrackSymbol>,
+    codes: HashMap<TrackSymbol, char>,
+    result: TuringMachineGraph,
+    indexes: HashMap<SimState, usize>,
+    to_visit: VecDeque<SimState>,
+}
+
+impl<'a> SingleTapeBuilder<'a> {
+    fn new(graph: &'a TuringMachineGraph) -> Result<Self, TuringError> {
+        let k = graph.get_k();
+
+        let mut reading_chars = vec![];
+        let mut tracks_chars = vec![vec![BLANK_CHAR]; k];
+        for state in graph.get_states() {
+            for transition in &state.transitions {
+                reading_chars.push(transition.chars_read[0]);
+                for (track, (c, _)) in transition.chars_write.iter().enumerate() {
+                    // The init char is only found on the first cell
+                    if *c != INIT_CHAR {
+                        tracks_chars[track].push(*c);
+                    }
+                }
+            }
+        }
+        reading_chars.sort();
+        reading_chars.dedup();
+
+        for chars in &mut tracks_chars {
+            chars.sort();
+            chars.dedup();
+        }
+
+        // Checked before listing the symbols, since their number grows exponentially
+        let nb_symbols = tracks_chars
+            .iter()
+            .try_fold(1usize, |nb, chars| nb.checked_mul(chars.len()))
+            .and_then(|nb| nb.checked_add(1))
+            .and_then(|nb| nb.checked_mul(1usize.checked_shl(k as u32)?));
+        if nb_symbols.is_none_or(|nb| nb > NB_SYMBOL_CODES as usize) {
+            return Err(TuringError::IllegalActionError {
+                cause: format!(
+                    "Too many symbols are needed to store {} tapes on a single one",
+                    k
+                ),
+            });
+        }
+
+        // Every combination of characters on the tracks
+        let mut combinations: Vec<Vec<char>> = vec![vec![]];
+        for chars in tracks_chars {
+            combinations = combinations
+                .iter()
+                .flat_map(|prev| {
+                    chars.iter().map(move |c| {
+                        let mut next = prev.clone();
+                        next.push(*c);
+                        next
+                    })
+                })
+                .collect();
+        }
+
+        let mut start_symbols = vec![];
+        let mut symbols = vec![];
+        for heads in 0..(1u32 << k) {
+            start_symbols.push(TrackSymbol {
+                chars: vec![INIT_CHAR; k],
+                heads,
+            });
+            for chars in &combinations {
+                symbols.push(TrackSymbol {
+                    chars: chars.clone(),
+                    heads,
+                });
+            }
+        }
+
+        Ok(Self {
+            graph,
+            reading_chars,
+            start_symbols,
+            symbols,
+            codes: HashMap::new(),
+            result: TuringMachineGraph::new(1)?,
+            indexes: HashMap::new(),
+            to_visit: VecDeque::new(),
+        })
+    }
+
+    fn build(mut self) -> Result<TuringMachineGraph, TuringError> {
+        let graph = self.graph;
+        let states = graph.get_states();
+
+        // Keep the names of the original states, so that the generated ones never take them
+        for state in states.iter().skip(3) {
+            self.result.add_state(&state.name);
+        }
+
+        self.indexes.insert(SimState::Init, 0);
+        self.to_visit.push_back(SimState::Init);
+        for (index, state) in states.iter().enumerate() {
+            if state.state_type == TuringStateType::Normal {
+                self.get_index(SimState::Start(index));
+            }
+        }
+
+        while let Some(state) = self.to_visit.pop_front() {
+            self.expand(state)?;
+        }
+        Ok(self.result)
+    }
+
+    /// Adds all the transitions leaving the given state.
+    fn expand(&mut self, state: SimState) -> Result<(), TuringError> {
+        let from = self.indexes[&state];
+        let graph = self.graph;
+        let states = graph.get_states();
+
+        match state {
+            SimState::Init => {
+                self.add_transition(
+                    from,
+                    (INIT_CHAR, INIT_CHAR),
+                    TuringDirection::None,
+                    (INIT_CHAR, TuringDirection::Right),
+                    SimState::InitTracks,
+                )?;
+            }
+            SimState::InitTracks => {
+                let first_cell = self.encode(&TrackSymbol {
+                    chars: vec![INIT_CHAR; self.graph.get_k()],
+                    heads: (1 << self.graph.get_k()) - 1,
+                });
+                self.add_transition(
+                    from,
+                    (INIT_CHAR, BLANK_CHAR),
+                    TuringDirection::None,
+                    (first_cell, TuringDirection::Left),
+                    SimState::Start(0),
+                )?;
+            }
+            SimState::Start(state) => {
+                let mut chars_read: Vec<char> = states[state]
+                    .transitions
+                    .iter()
+                    .map(|t| t.chars_read[0])
+                    .collect();
+                chars_read.sort();
+                chars_read.dedup();
+
+                for char_read in chars_read {
+                    self.add_transition(
+                        from,
+                        (char_read, INIT_CHAR),
+                        TuringDirection::None,
+                        (INIT_CHAR, TuringDirection::Right),
+                        SimState::Read {
+                            state,
+                            char_read,
+                            seen: vec![None; self.graph.get_k()],
+                        },
+                    )?;
+                }
+            }
+            SimState::Read {
+                state,
+                char_read,
+                seen,
+            } => {
+                for symbol in self.all_symbols() {
+                    // The heads are always found before reaching the blank part of the tape
+                    if symbol.is_blank() {
+                        continue;
+                    }
+                    let mut seen = seen.clone();
+                    let mut valid = true;
+                    for (track, char_seen) in seen.iter_mut().enumerate() {
+                        if symbol.heads & (1 << track) != 0 {
+                            valid &= char_seen.is_none();
+                            *char_seen = Some(symbol.chars[track]);
+                        }
+                    }
+                    if !valid {
+                        continue;
+                    }
+
+                    // Only keep going if one of the transitions can still be taken
+                    let candidates: Vec<(usize, &TuringTransition)> = states[state]
+                        .transitions
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, t)| {
+                            t.chars_read[0] == char_read
+                                && seen
+                                    .iter()
+                                    .zip(t.chars_read.iter().skip(1))
+                                    .all(|(s, c)| s.is_none_or(|s| s == *c))
+                        })
+                        .collect();
+                    if candidates.is_empty() {
+                        continue;
+                    }
+                    let code = self.encode(&symbol);
+
+                    if seen.iter().any(|s| s.is_none()) {
+                        self.add_transition(
+                            from,
+                            (char_read, code),
+                            TuringDirection::None,
+                            (code, TuringDirection::Right),
+                            SimState::Read {
+                                state,
+                                char_read,
+                                seen,
+                            },
+                        )?;
+                        continue;
+                    }
+
+                    // All the heads were found, the rightmost one is on this cell
+                    for (t_index, transition) in candidates {
+                        let target = transition.index_to_state.unwrap();
+                        if states[target].state_type != TuringStateType::Normal {
+                            // No need to update the tracks before stopping
+                            self.add_transition(
+                                from,
+                                (char_read, code),
+                                transition.move_read.clone(),
+                                (code, TuringDirection::None),
+                                SimState::Final(match states[target].state_type {
+                                    TuringStateType::Accepting => 1,
+                                    _ => 2,
+                                }),
+                            )?;
+                        } else {
+                            let (written, direction, next) =
+                                self.write_step(state, t_index, 0, &symbol);
+                            let written = self.encode(&written);
+                            self.add_transition(
+                                from,
+                                (char_read, code),
+                                transition.move_read.clone(),
+                                (written, direction),
+                                next,
+                            )?;
+                        }
+                    }
+                }
+            }
+            SimState::Write {
+                state,
+                transition,
+                pending,
+            } => {
+                for char_read in self.reading_chars.clone() {
+                    // Back on the first cell, the step is over
+                    if pending == 0 {
+                        self.add_transition(
+                            from,
+                            (char_read, INIT_CHAR),
+                            TuringDirection::None,
+                            (INIT_CHAR, TuringDirection::None),
+                            SimState::Start(
+                                states[state].transitions[transition]
+                                    .index_to_state
+                                    .unwrap(),
+                            ),
+                        )?;
+                    }
+                    for symbol in self.all_symbols() {
+                        let (written, direction, next) =
+                            self.write_step(state, transition, pending, &symbol);
+                        let code = self.encode(&symbol);
+                        let written = self.encode(&written);
+                        self.add_transition(
+                            from,
+                            (char_read, code),
+                            TuringDirection::None,
+                            (written, direction),
+                            next,
+                        )?;
+                    }
+                }
+            }
+            SimState::MoveRight {
+                state,
+                transition,
+                pending,
+                right,
+            } => {
+                for char_read in self.reading_chars.clone() {
+                    for symbol in self.symbols.clone() {
+                        let code = self.encode(&symbol);
+                        let written = self.encode(&TrackSymbol {
+                            chars: symbol.chars,
+                            heads: symbol.heads | right,
+                        });
+                        self.add_transition(
+                            from,
+                            (char_read, code),
+                            TuringDirection::None,
+                            (written, TuringDirection::Left),
+                            SimState::Back {
+                                state,
+                                transition,
+                                pending,
+                            },
+                        )?;
+                    }
+                }
+            }
+            SimState::Back {
+                state,
+                transition,
+                pending,
+            } => {
+                for char_read in self.reading_chars.clone() {
+                    for symbol in self.all_symbols() {
+                        let code = self.encode(&symbol);
+                        self.add_transition(
+                            from,
+                            (char_read, code),
+                            TuringDirection::None,
+                            (code, TuringDirection::Left),
+                            SimState::Write {
+                                state,
+                                transition,
+                                pending,
+                            },
+                        )?;
+                    }
+                }
+            }
+            SimState::Final(_) => {}
+        }
+        Ok(())
+    }
+
+    /// Applies the given transition on a cell of the tracks, while going left.
+    ///
+    /// ## Returns
+    /// The new content of the cell, the direction to take and the next state.
+    fn write_step(
+        &self,
+        state: usize,
+        transition: usize,
+        pending: u32,
+        symbol: &TrackSymbol,
+    ) -> (TrackSymbol, TuringDirection, SimState) {
+        let transition_ref = &self.graph.get_states()[state].transitions[transition];
+        // The heads moving left from the previous cell are placed here
+        let mut written = TrackSymbol {
+            chars: symbol.chars.clone(),
+            heads: pending,
+        };
+        let mut left = 0;
+        let mut right = 0;
+
+        for (track, (c, direction)) in transition_ref.chars_write.iter().enumerate() {
+            let head = 1 << track;
+            if symbol.heads & head == 0 {
+                continue;
+            }
+            written.chars[track] = *c;
+            match direction {
+                TuringDirection::Left => left |= head,
+                TuringDirection::Right => right |= head,
+                TuringDirection::None => written.heads |= head,
+            }
+        }
+
+        if right == 0 {
+            (
+                written,
+                TuringDirection::Left,
+                SimState::Write {
+                    state,
+                    transition,
+                    pending: left,
+                },
+            )
+        } else {
+            (
+                written,
+                TuringDirection::Right,
+                SimState::MoveRight {
+                    state,
+                    transition,
+                    pending: left,
+                    right,
+                },
+            )
+        }
+    }
+
+    fn add_transition(
+        &mut self,
+        from: usize,
+        (char_read, track_read): (char, char),
+        move_read: TuringDirection,
+        track_write: (char, TuringDirection),
+        to: SimState,
+    ) -> Result<(), TuringError> {
+        let to = self.get_index(to);
+        self.result.append_rule_state(
+            from,
+            TuringTransition::new(vec![char_read, track_read], move_read, vec![track_write]),
+            to,
+        )
+    }
+
+    /// Returns the index of the given state in the generated graph, creating it if needed.
+    fn get_index(&mut self, state: SimState) -> usize {
+        if let Some(index) = self.indexes.get(&state) {
+            return *index;
+        }
+        let graph = self.graph;
+        let states = graph.get_states();
+        let index = match &state {
+            SimState::Init => 0,
+            SimState::Final(index) => *index,
+            SimState::Start(0) => self.result.add_fresh_state("i"),
+            SimState::Start(index) => self.result.add_state(&states[*index].name),
+            SimState::InitTracks => self.result.add_fresh_state("init"),
+            SimState::Read { .. } => self.result.add_fresh_state("read"),
+            SimState::Write { .. } => self.result.add_fresh_state("write"),
+            SimState::MoveRight { .. } => self.result.add_fresh_state("right"),
+            SimState::Back { .. } => self.result.add_fresh_state("back"),
+        };
+        self.indexes.insert(state.clone(), index);
+        self.to_visit.push_back(state);
+        index
+    }
+
+    fn all_symbols(&self) -> Vec<TrackSymbol> {
+        self.start_symbols
+            .iter()
+            .chain(self.symbols.iter())
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the character representing the given symbol on the single writing tape.
+    fn encode(&mut self, symbol: &TrackSymbol) -> char {
+        if symbol.is_blank() {
+            return BLANK_CHAR;
+        }
+        let next = self.codes.len() as u32;
+        *self
+            .codes
+            .entry(symbol.clone())
+            // There is always enough codes, this was checked when listing the symbols
+            .or_insert_with(|| char::from_u32(FIRST_SYMBOL_CODE + next).unwrap())
+    }
+}
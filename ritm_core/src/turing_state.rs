@@ -9,6 +9,11 @@ use crate::{
 };
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
 /// Represents the different types of states that can be found inside a turing machine graph
 pub enum TuringStateType {
     /// A normal state, has no special effect.
@@ -34,6 +39,7 @@ impl Display for TuringStateType {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Represents a state of a turing machine
 pub struct TuringState {
     /// Represents if the state is a final state or not
@@ -195,10 +201,14 @@ impl PartialEq for TuringState {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Represents the direction of a movement that the pointer of a tape can take after reading/writing a character
 pub enum TuringDirection {
+    #[cfg_attr(feature = "serde", serde(rename = "L"))]
     Left,
+    #[cfg_attr(feature = "serde", serde(rename = "R"))]
     Right,
+    #[cfg_attr(feature = "serde", serde(rename = "N"))]
     None,
 }
 
@@ -230,6 +240,7 @@ impl Display for TuringDirection {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// A struct representing a transition for a turing machine that has strictly more than **1 tape** :
 /// * `a_0, a_1, ..., a_{n-1} -> D_0, b_1, D_1, b_2, D_2, ..., b_{n-1}, D_{n-1}`
 /// - With :
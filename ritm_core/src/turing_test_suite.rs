@@ -0,0 +1,367 @@
+use pest::{Parser, iterators::Pair};
+use pest_derive::Parser;
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    turing_equivalence::{RunOutcome, run_with_last_step},
+    turing_errors::TuringParserError,
+    turing_graph::TuringMachineGraph,
+    turing_tape::{BLANK_CHAR, INIT_CHAR, TuringTape},
+};
+
+#[derive(Parser)]
+#[grammar = "test_suite.pest"]
+pub struct TestSuiteGrammar;
+
+/// The number of steps allowed for each execution when a suite does not give one.
+pub const DEFAULT_STEP_BUDGET: usize = 10_000;
+
+/// What a machine is expected to do with a word.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expectation {
+    /// The word is accepted
+    Accept,
+    /// The word is rejected
+    Reject,
+    /// The execution is still running after the given number of steps
+    Loop { steps: usize },
+    /// The word is accepted, and the last writing tape contains the given output
+    Output(String),
+}
+
+impl Display for Expectation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expectation::Accept => write!(f, "accept"),
+            Expectation::Reject => write!(f, "reject"),
+            Expectation::Loop { steps } => write!(f, "loop within {steps} steps"),
+            Expectation::Output(output) => write!(f, "accept with the output \"{output}\""),
+        }
+    }
+}
+
+/// A word along with what the machine is expected to do with it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestCase {
+    pub word: String,
+    pub expectation: Expectation,
+    /// The line of the suite where this test is written
+    pub line: usize,
+}
+
+/// A list of tests, parsed from a `.tmtest` file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestSuite {
+    /// The path of the machine to test, relative to the suite, if one is given
+    pub machine: Option<String>,
+    /// The maximum number of steps of the executions, except for the tests expecting a loop
+    pub budget: usize,
+    pub cases: Vec<TestCase>,
+}
+
+impl TestSuite {
+    /// Returns the path of the machine tested by the suite stored at the given path.
+    ///
+    /// It is the path given by the suite, relative to the folder of the suite, or the path of the suite with the `.tm` extension if none is given.
+    pub fn machine_path(&self, suite_path: &Path) -> PathBuf {
+        match &self.machine {
+            Some(machine) => suite_path.parent().unwrap_or(Path::new("")).join(machine),
+            None => suite_path.with_extension("tm"),
+        }
+    }
+}
+
+/// How the execution of a test ended.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaseRun {
+    pub outcome: RunOutcome,
+    /// The number of steps taken, backtracking included
+    pub steps: usize,
+    /// The content of the last writing tape if the word was accepted, without [INIT_CHAR] and the surrounding [BLANK_CHAR]
+    pub output: Option<String>,
+}
+
+impl Display for CaseRun {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let outcome = match self.outcome {
+            RunOutcome::Accepted => "accepted",
+            RunOutcome::Rejected => "rejected",
+            RunOutcome::OutOfBudget => "still running",
+        };
+        write!(f, "{outcome} after {} steps", self.steps)?;
+        if let Some(output) = &self.output {
+            write!(f, " with the output \"{output}\"")?;
+        }
+        Ok(())
+    }
+}
+
+/// The result of a test.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaseResult {
+    pub case: TestCase,
+    /// How the execution ended, or why it could not be started
+    pub run: Result<CaseRun, String>,
+    pub passed: bool,
+}
+
+impl CaseResult {
+    /// Returns what was expected and what happened if the test failed, with the first differing character of the outputs pointed out.
+    pub fn diff(&self) -> Option<String> {
+        if self.passed {
+            return None;
+        }
+        let actual = match &self.run {
+            Ok(run) => run.to_string(),
+            Err(e) => format!("could not run : {e}"),
+        };
+        let mut res = format!(
+            "- expected : {}\n+ actual   : {actual}",
+            self.case.expectation
+        );
+
+        if let (
+            Expectation::Output(expected),
+            Ok(CaseRun {
+                output: Some(output),
+                ..
+            }),
+        ) = (&self.case.expectation, &self.run)
+        {
+            let position = expected
+                .chars()
+                .zip(output.chars())
+                .take_while(|(e, o)| e == o)
+                .count();
+            res.push_str(&format!(
+                "\n- \"{expected}\"\n+ \"{output}\"\n   {}^",
+                " ".repeat(position)
+            ));
+        }
+        Some(res)
+    }
+}
+
+impl Display for CaseResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} line {} : {} \"{}\"",
+            if self.passed { "PASS" } else { "FAIL" },
+            self.case.line,
+            self.case.expectation,
+            self.case.word
+        )?;
+        if let Some(diff) = self.diff() {
+            for line in diff.lines() {
+                write!(f, "\n    {line}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The results of all the tests of a suite.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuiteReport {
+    pub results: Vec<CaseResult>,
+}
+
+impl SuiteReport {
+    pub fn passed(&self) -> usize {
+        self.results.iter().filter(|result| result.passed).count()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.results.len() - self.passed()
+    }
+
+    /// Returns true if every test passed.
+    pub fn is_success(&self) -> bool {
+        self.failed() == 0
+    }
+
+    /// Returns the number of tests that passed and failed, like `3 passed, 1 failed`.
+    pub fn summary(&self) -> String {
+        format!("{} passed, {} failed", self.passed(), self.failed())
+    }
+}
+
+impl Display for SuiteReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for result in &self.results {
+            writeln!(f, "{result}")?;
+        }
+        write!(f, "{}", self.summary())
+    }
+}
+
+/// Parses a test suite from the content of a `.tmtest` file.
+///
+/// See [parse_test_suite_string] for more information.
+pub fn parse_test_suite_file_path(file_path: String) -> Result<TestSuite, TuringParserError> {
+    match fs::read_to_string(&file_path) {
+        Ok(content) => parse_test_suite_string(content),
+        Err(e) => Err(TuringParserError::FileError {
+            given_path: file_path,
+            error_reason: e.to_string(),
+        }),
+    }
+}
+
+/// Parses a test suite written in the `.tmtest` format :
+/// ```text
+/// // The machine to test, relative to the suite (optional)
+/// machine "binary_palindrome.tm";
+/// // The maximum number of steps of each execution (optional)
+/// budget 1000;
+///
+/// accept "0110";
+/// reject "01";
+/// loop "1" within 500;
+/// output "0110" -> "0110";
+/// ```
+/// A test expecting an output passes if the word is accepted and the last writing tape contains the output,
+/// without [INIT_CHAR] and the surrounding [BLANK_CHAR].
+pub fn parse_test_suite_string(content: String) -> Result<TestSuite, TuringParserError> {
+    let suite = match TestSuiteGrammar::parse(Rule::test_suite, &content) {
+        Ok(mut suite) => suite.next().unwrap(), // the `test_suite` rule always matches once
+        Err(e) => {
+            return Err(TuringParserError::ParsingError {
+                line_col_pos: match e.line_col {
+                    pest::error::LineColLocation::Pos(p) => Some(p),
+                    pest::error::LineColLocation::Span(_, _) => None,
+                },
+                value: e.line().to_string(),
+                missing_value: match &e.variant {
+                    pest::error::ErrorVariant::ParsingError { positives, .. }
+                        if positives.contains(&Rule::semicolon) =>
+                    {
+                        Some(";".to_string())
+                    }
+                    _ => None,
+                },
+            });
+        }
+    };
+
+    let mut res = TestSuite {
+        machine: None,
+        budget: DEFAULT_STEP_BUDGET,
+        cases: vec![],
+    };
+    for rule in suite.into_inner() {
+        let line = rule.line_col().0;
+        let kind = rule.as_rule();
+        let mut inner = rule.into_inner();
+        let expectation = match kind {
+            Rule::machine => {
+                res.machine = Some(parse_string(inner.next().unwrap()));
+                continue;
+            }
+            Rule::budget => {
+                res.budget = parse_number(inner.next().unwrap())?;
+                continue;
+            }
+            Rule::accept_case => Expectation::Accept,
+            Rule::reject_case => Expectation::Reject,
+            Rule::loop_case => Expectation::Loop {
+                steps: parse_number(inner.clone().nth(1).unwrap())?,
+            },
+            Rule::output_case => Expectation::Output(parse_string(inner.clone().nth(1).unwrap())),
+            Rule::EOI => break,
+            _ => unreachable!(),
+        };
+        res.cases.push(TestCase {
+            word: parse_string(inner.next().unwrap()),
+            expectation,
+            line,
+        });
+    }
+    Ok(res)
+}
+
+/// Runs every test of the suite on the given machine.
+pub fn run_test_suite(graph: &TuringMachineGraph, suite: &TestSuite) -> SuiteReport {
+    SuiteReport {
+        results: suite
+            .cases
+            .iter()
+            .map(|case| run_test_case(graph, case, suite.budget))
+            .collect(),
+    }
+}
+
+/// Runs the tests embedded in the machine, like `// @accept 0110`, each execution being stopped after [DEFAULT_STEP_BUDGET] steps.
+pub fn run_embedded_tests(graph: &TuringMachineGraph) -> SuiteReport {
+    SuiteReport {
+        results: graph
+            .get_embedded_tests()
+            .iter()
+            .map(|test| TestCase {
+                word: test.word.clone(),
+                expectation: if test.accept {
+                    Expectation::Accept
+                } else {
+                    Expectation::Reject
+                },
+                line: test.line,
+            })
+            .map(|case| run_test_case(graph, &case, DEFAULT_STEP_BUDGET))
+            .collect(),
+    }
+}
+
+fn run_test_case(graph: &TuringMachineGraph, case: &TestCase, budget: usize) -> CaseResult {
+    let budget = match case.expectation {
+        Expectation::Loop { steps } => steps,
+        _ => budget,
+    };
+    let run = run_with_last_step(graph, &case.word, budget)
+        .map(|(result, last_step)| CaseRun {
+            outcome: result.outcome,
+            steps: result.steps,
+            output: match (result.outcome, last_step) {
+                (RunOutcome::Accepted, Some(step)) => step.get_writing_tapes().last().map(|tape| {
+                    let content: String = tape.get_contents().iter().collect();
+                    content
+                        .trim_start_matches(INIT_CHAR)
+                        .trim_matches(BLANK_CHAR)
+                        .to_string()
+                }),
+                _ => None,
+            },
+        })
+        .map_err(|e| e.to_string());
+
+    let passed = match (&case.expectation, &run) {
+        (_, Err(_)) => false,
+        (Expectation::Accept, Ok(run)) => run.outcome == RunOutcome::Accepted,
+        (Expectation::Reject, Ok(run)) => run.outcome == RunOutcome::Rejected,
+        (Expectation::Loop { .. }, Ok(run)) => run.outcome == RunOutcome::OutOfBudget,
+        (Expectation::Output(expected), Ok(run)) => run.output.as_ref() == Some(expected),
+    };
+    CaseResult {
+        case: case.clone(),
+        run,
+        passed,
+    }
+}
+
+fn parse_string(rule: Pair<Rule>) -> String {
+    rule.into_inner().as_str().to_string()
+}
+
+fn parse_number(rule: Pair<Rule>) -> Result<usize, TuringParserError> {
+    rule.as_str()
+        .parse()
+        .map_err(|_| TuringParserError::ParsingError {
+            line_col_pos: Some(rule.line_col()),
+            value: rule.as_str().to_string(),
+            missing_value: None,
+        })
+}
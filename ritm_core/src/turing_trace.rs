@@ -0,0 +1,198 @@
+use crate::{turing_machine::TuringExecutionSteps, turing_tape::TuringTape};
+
+/// A step of an execution, flattened into plain values so it can be written in a machine-readable format.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceRecord {
+    /// The iteration reached by this step
+    pub iteration: usize,
+    /// The kind of step: `first`, `transition` or `backtrack`
+    pub kind: &'static str,
+    /// The name of the state the step left, if any
+    pub previous_state: Option<String>,
+    /// The name of the state reached by the step
+    pub state: String,
+    /// The index of the transition taken inside of the previous state, if one was taken
+    pub transition_index: Option<usize>,
+    /// The transition taken, written like in the text format
+    pub transition: Option<String>,
+    /// The iteration the execution went back to, if the step is a backtrack
+    pub backtracked_iteration: Option<usize>,
+    /// The content of the reading tape
+    pub reading_tape: String,
+    /// The position of the head of the reading tape
+    pub reading_head: usize,
+    /// The content of every writing tape
+    pub writing_tapes: Vec<String>,
+    /// The position of the head of every writing tape
+    pub writing_heads: Vec<usize>,
+}
+
+impl TraceRecord {
+    /// Flattens the given step into a record.
+    pub fn from_step(step: &TuringExecutionSteps) -> Self {
+        let (kind, transition_index, transition, backtracked_iteration) = match step {
+            TuringExecutionSteps::FirstIteration { .. } => ("first", None, None, None),
+            TuringExecutionSteps::TransitionTaken {
+                transition_index_taken,
+                transition_taken,
+                ..
+            } => (
+                "transition",
+                Some(*transition_index_taken),
+                Some(transition_taken.to_string()),
+                None,
+            ),
+            TuringExecutionSteps::Backtracked {
+                backtracked_iteration,
+                ..
+            } => ("backtrack", None, None, Some(*backtracked_iteration)),
+        };
+
+        Self {
+            iteration: step.get_nb_iterations(),
+            kind,
+            previous_state: step.get_previous_state().map(|state| state.name.clone()),
+            state: step.get_current_state().name.clone(),
+            transition_index,
+            transition,
+            backtracked_iteration,
+            reading_tape: step.get_reading_tape().get_contents().iter().collect(),
+            reading_head: step.get_reading_tape().get_pointer(),
+            writing_tapes: step
+                .get_writing_tapes()
+                .iter()
+                .map(|tape| tape.get_contents().iter().collect())
+                .collect(),
+            writing_heads: step
+                .get_writing_tapes()
+                .iter()
+                .map(|tape| tape.get_pointer())
+                .collect(),
+        }
+    }
+
+    /// Writes this record as a single line JSON object, without the line break.
+    pub fn to_json(&self) -> String {
+        let tapes: Vec<String> = self
+            .writing_tapes
+            .iter()
+            .map(|tape| json_string(tape))
+            .collect();
+        let heads: Vec<String> = self.writing_heads.iter().map(|h| h.to_string()).collect();
+        format!(
+            "{{\"iteration\":{},\"kind\":\"{}\",\"previous_state\":{},\"state\":{},\"transition_index\":{},\"transition\":{},\"backtracked_iteration\":{},\"reading_tape\":{},\"reading_head\":{},\"writing_tapes\":[{}],\"writing_heads\":[{}]}}",
+            self.iteration,
+            self.kind,
+            json_option(self.previous_state.as_deref().map(json_string)),
+            json_string(&self.state),
+            json_option(self.transition_index.map(|i| i.to_string())),
+            json_option(self.transition.as_deref().map(json_string)),
+            json_option(self.backtracked_iteration.map(|i| i.to_string())),
+            json_string(&self.reading_tape),
+            self.reading_head,
+            tapes.join(","),
+            heads.join(",")
+        )
+    }
+
+    /// Writes this record as a CSV row, without the line break. The columns are the ones given by [csv_header].
+    pub fn to_csv(&self) -> String {
+        let mut fields = vec![
+            self.iteration.to_string(),
+            self.kind.to_string(),
+            self.previous_state.clone().unwrap_or_default(),
+            self.state.clone(),
+            self.transition_index
+                .map(|i| i.to_string())
+                .unwrap_or_default(),
+            self.transition.clone().unwrap_or_default(),
+            self.backtracked_iteration
+                .map(|i| i.to_string())
+                .unwrap_or_default(),
+            self.reading_tape.clone(),
+            self.reading_head.to_string(),
+        ];
+        for (tape, head) in self.writing_tapes.iter().zip(&self.writing_heads) {
+            fields.push(tape.clone());
+            fields.push(head.to_string());
+        }
+        fields
+            .iter()
+            .map(|field| csv_field(field))
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+}
+
+/// Returns the header of a CSV trace of a machine with `k` writing tapes.
+pub fn csv_header(k: usize) -> String {
+    let mut columns = vec![
+        "iteration".to_string(),
+        "kind".to_string(),
+        "previous_state".to_string(),
+        "state".to_string(),
+        "transition_index".to_string(),
+        "transition".to_string(),
+        "backtracked_iteration".to_string(),
+        "reading_tape".to_string(),
+        "reading_head".to_string(),
+    ];
+    for i in 1..=k {
+        columns.push(format!("tape_{i}"));
+        columns.push(format!("head_{i}"));
+    }
+    columns.join(",")
+}
+
+/// Turns the given steps into JSON Lines, with one object per step.
+pub fn trace_to_json_lines(steps: &[TuringExecutionSteps]) -> String {
+    steps
+        .iter()
+        .map(|step| format!("{}\n", TraceRecord::from_step(step).to_json()))
+        .collect()
+}
+
+/// Turns the given steps into a CSV document, starting with a header and followed by one row per step.
+pub fn trace_to_csv(steps: &[TuringExecutionSteps]) -> String {
+    let k = steps
+        .first()
+        .map(|step| step.get_writing_tapes().len())
+        .unwrap_or(0);
+    let mut res = format!("{}\n", csv_header(k));
+    for step in steps {
+        res.push_str(&TraceRecord::from_step(step).to_csv());
+        res.push('\n');
+    }
+    res
+}
+
+fn json_option(value: Option<String>) -> String {
+    value.unwrap_or_else(|| "null".to_string())
+}
+
+/// Writes the given value as a quoted JSON string, escaping what needs to be.
+fn json_string(value: &str) -> String {
+    let mut res = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            '\r' => res.push_str("\\r"),
+            '\t' => res.push_str("\\t"),
+            c if c.is_control() => res.push_str(&format!("\\u{:04x}", c as u32)),
+            c => res.push(c),
+        }
+    }
+    res.push('"');
+    res
+}
+
+/// Quotes the given field if it contains a character with a special meaning in CSV.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,321 @@
+use crate::{
+    turing_errors::TuringError,
+    turing_graph::TuringMachineGraph,
+    turing_state::{TuringStateType, TuringTransition},
+};
+
+/// The options used by [graph_to_table] and [graph_to_diagram].
+#[derive(Debug, Clone)]
+pub struct ViewOptions {
+    /// The maximum number of characters of a line, usually the width of the terminal
+    pub width: usize,
+    /// The name of the state to focus on, meaning only this state and its neighbors are shown
+    pub focus: Option<String>,
+}
+
+impl Default for ViewOptions {
+    fn default() -> Self {
+        Self {
+            width: 80,
+            focus: None,
+        }
+    }
+}
+
+/// Turns the given [TuringMachineGraph] into its transition table (δ), drawn with box-drawing characters.
+///
+/// Each row is a state and each column the characters read by a transition, the cells containing how the heads move,
+/// what is written and the next state, like `R, 0, R → q_1`. The transitions reading the same characters are separated by `/`.
+/// The initial state is marked with `→`, the accepting states with `✓` and the rejecting states with `✗`.
+///
+/// When the table is wider than [ViewOptions::width], its columns are split into several tables, each one repeating the states.
+/// If a state to focus on is given, only its row and the rows of its neighbors are shown.
+pub fn graph_to_table(
+    graph: &TuringMachineGraph,
+    options: &ViewOptions,
+) -> Result<String, TuringError> {
+    let shown = get_shown_states(graph, &options.focus)?;
+    let states = graph.get_states();
+
+    // The columns are the characters read, in the order they are first found
+    let mut columns: Vec<&Vec<char>> = vec![];
+    for index in &shown {
+        for transition in &states[*index].transitions {
+            if !columns.contains(&&transition.chars_read) {
+                columns.push(&transition.chars_read);
+            }
+        }
+    }
+
+    let headers: Vec<String> = columns.iter().map(|read| join_chars(read)).collect();
+    let labels: Vec<String> = shown
+        .iter()
+        .map(|index| {
+            let marker = match (index, &states[*index].state_type) {
+                (0, _) => "→",
+                (_, TuringStateType::Accepting) => "✓",
+                (_, TuringStateType::Rejecting) => "✗",
+                (_, TuringStateType::Normal) => " ",
+            };
+            format!("{marker} q_{}", states[*index].name)
+        })
+        .collect();
+    let cells: Vec<Vec<String>> = shown
+        .iter()
+        .map(|index| {
+            columns
+                .iter()
+                .map(|read| {
+                    states[*index]
+                        .transitions
+                        .iter()
+                        .filter(|transition| transition.chars_read == **read)
+                        .map(|transition| format_cell(graph, transition))
+                        .collect::<Vec<String>>()
+                        .join(" / ")
+                })
+                .collect()
+        })
+        .collect();
+
+    let label_width = labels.iter().map(|label| text_width(label)).max().unwrap();
+    let column_widths: Vec<usize> = (0..columns.len())
+        .map(|column| {
+            cells
+                .iter()
+                .map(|row| text_width(&row[column]))
+                .chain([text_width(&headers[column])])
+                .max()
+                .unwrap()
+        })
+        .collect();
+
+    // A column takes its width, along with a space on each side and a border
+    let available = options.width.saturating_sub(label_width + 4);
+    let mut res = String::new();
+    let mut start = 0;
+    loop {
+        let mut end = start;
+        let mut used = 0;
+        while end < columns.len() && (end == start || used + column_widths[end] + 3 <= available) {
+            used += column_widths[end] + 3;
+            end += 1;
+        }
+        // A column too wide to fit on its own is cut
+        let widths: Vec<usize> = column_widths[start..end]
+            .iter()
+            .map(|width| (*width).min(available.saturating_sub(3).max(1)))
+            .collect();
+
+        if !res.is_empty() {
+            res.push('\n');
+        }
+        push_table(
+            &mut res,
+            label_width,
+            &widths,
+            &headers[start..end],
+            &labels,
+            &cells
+                .iter()
+                .map(|row| &row[start..end])
+                .collect::<Vec<&[String]>>(),
+        );
+
+        start = end;
+        if start >= columns.len() {
+            break;
+        }
+    }
+    Ok(res)
+}
+
+/// Writes a table made of the given columns.
+fn push_table(
+    res: &mut String,
+    label_width: usize,
+    widths: &[usize],
+    headers: &[String],
+    labels: &[String],
+    cells: &[&[String]],
+) {
+    let border = |left: &str, middle: &str, right: &str| {
+        let mut line = format!("{left}{}", "─".repeat(label_width + 2));
+        for width in widths {
+            line.push_str(middle);
+            line.push_str(&"─".repeat(width + 2));
+        }
+        line.push_str(right);
+        line.push('\n');
+        line
+    };
+    let row = |label: &str, row_cells: &[String]| {
+        let mut line = format!("│ {} ", pad(label, label_width));
+        for (cell, width) in row_cells.iter().zip(widths) {
+            line.push_str(&format!("│ {} ", pad(&truncate(cell, *width), *width)));
+        }
+        line.push_str("│\n");
+        line
+    };
+
+    res.push_str(&border("┌", "┬", "┐"));
+    res.push_str(&row("", headers));
+    res.push_str(&border("├", "┼", "┤"));
+    for (label, row_cells) in labels.iter().zip(cells) {
+        res.push_str(&row(label, row_cells));
+    }
+    res.push_str(&border("└", "┴", "┘"));
+}
+
+/// Turns the given [TuringMachineGraph] into a diagram drawn with box-drawing characters.
+///
+/// Every state is drawn as a box, followed by the transitions leaving it, like `─┬─ {0, _ -> R, 0, R} ──▶ q_1`.
+/// The accepting states are drawn with a double border and the rejecting states with a heavy one, while the initial state is marked with `→`.
+///
+/// The transitions are cut to fit in [ViewOptions::width].
+/// If a state to focus on is given, only this state and its neighbors are drawn, along with the transitions leaving or reaching the focused state.
+pub fn graph_to_diagram(
+    graph: &TuringMachineGraph,
+    options: &ViewOptions,
+) -> Result<String, TuringError> {
+    let shown = get_shown_states(graph, &options.focus)?;
+    let focus = options
+        .focus
+        .as_ref()
+        .map(|name| graph.get_name_index_hashmap()[name]);
+    let states = graph.get_states();
+
+    let mut res = String::new();
+    for index in shown {
+        let state = &states[index];
+        let name = format!("q_{}", state.name);
+        let edges: Vec<(String, String)> = state
+            .transitions
+            .iter()
+            .filter(|transition| {
+                focus.is_none_or(|focus| index == focus || transition.index_to_state == Some(focus))
+            })
+            .map(|transition| {
+                (
+                    format!("{{{transition}}}"),
+                    format!("q_{}", states[transition.index_to_state.unwrap()].name),
+                )
+            })
+            .collect();
+
+        let (horizontal, vertical, corners) = match state.state_type {
+            TuringStateType::Normal => ("─", "│", ["┌", "┐", "└", "┘"]),
+            TuringStateType::Accepting => ("═", "║", ["╔", "╗", "╚", "╝"]),
+            TuringStateType::Rejecting => ("━", "┃", ["┏", "┓", "┗", "┛"]),
+        };
+        let line = horizontal.repeat(text_width(&name) + 2);
+        let start = if index == 0 { "→ " } else { "  " };
+        let mut lines = vec![
+            format!("  {}{line}{}", corners[0], corners[1]),
+            format!("{start}{vertical} {name} {vertical}"),
+            format!("  {}{line}{}", corners[2], corners[3]),
+        ];
+
+        let box_width = text_width(&lines[1]);
+        for (edge, (label, target)) in edges.iter().enumerate() {
+            let connector = match (edge, edges.len()) {
+                (0, 1) => "───",
+                (0, _) => "─┬─",
+                (edge, len) if edge == len - 1 => " └─",
+                _ => " ├─",
+            };
+            // The edges after the second one are written below the box
+            if edge + 1 >= lines.len() {
+                lines.push(" ".repeat(box_width));
+            }
+            let line = &mut lines[edge + 1];
+            let available = options
+                .width
+                .saturating_sub(text_width(line) + text_width(connector) + text_width(target) + 6);
+            line.push_str(&format!(
+                "{connector} {} ──▶ {target}",
+                truncate(label, available.max(1))
+            ));
+        }
+
+        for line in lines {
+            res.push_str(truncate(&line, options.width).trim_end());
+            res.push('\n');
+        }
+    }
+    Ok(res)
+}
+
+/// Returns the indexes of the states to show, in the order of the graph : every state,
+/// or only the state to focus on and the states it goes to or comes from.
+fn get_shown_states(
+    graph: &TuringMachineGraph,
+    focus: &Option<String>,
+) -> Result<Vec<usize>, TuringError> {
+    let states = graph.get_states();
+    let Some(name) = focus else {
+        return Ok((0..states.len()).collect());
+    };
+    let Some(focus) = graph.get_name_index_hashmap().get(name) else {
+        return Err(TuringError::UnknownStateError {
+            state_name: name.to_string(),
+        });
+    };
+
+    Ok((0..states.len())
+        .filter(|index| {
+            index == focus
+                || states[*focus]
+                    .transitions
+                    .iter()
+                    .any(|transition| transition.index_to_state == Some(*index))
+                || states[*index]
+                    .transitions
+                    .iter()
+                    .any(|transition| transition.index_to_state == Some(*focus))
+        })
+        .collect())
+}
+
+/// Writes how the heads move, what is written and the next state, like `R, 0, R → q_1`.
+fn format_cell(graph: &TuringMachineGraph, transition: &TuringTransition) -> String {
+    let mut res = format!("{}", transition.move_read);
+    for (c, direction) in &transition.chars_write {
+        res.push_str(&format!(", {c}, {direction}"));
+    }
+    let target = &graph.get_states()[transition.index_to_state.unwrap()].name;
+    format!("{res} → q_{target}")
+}
+
+fn join_chars(chars: &[char]) -> String {
+    chars
+        .iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// The number of columns taken by the text, every character taking one.
+fn text_width(text: &str) -> usize {
+    text.chars().count()
+}
+
+/// Cuts the text to the given width, ending it with `…` if it was too long.
+fn truncate(text: &str, width: usize) -> String {
+    if text_width(text) <= width {
+        return text.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+    let mut res: String = text.chars().take(width - 1).collect();
+    res.push('…');
+    res
+}
+
+fn pad(text: &str, width: usize) -> String {
+    format!(
+        "{text}{}",
+        " ".repeat(width.saturating_sub(text_width(text)))
+    )
+}
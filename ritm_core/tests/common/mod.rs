@@ -0,0 +1,56 @@
+//! The machines and helpers shared by the tests comparing the languages of machines.
+#![allow(dead_code)]
+
+use ritm_core::{
+    turing_equivalence::{RunOutcome, check_equivalence, run},
+    turing_graph::TuringMachineGraph,
+};
+
+/// The number of steps, backtracking included, after which a run is stopped
+pub const STEP_BUDGET: usize = 2_000_000;
+
+/// Accepts the words of the form 0^n 1^n, the second tape moves right while the first one moves left
+pub const TM_0N1N: &str = "q_i {ç, ç, ç -> R, ç, R, ç, R} q_1;
+q_1 {0, _, _ -> R, 0, R, _, N} q_1;
+q_1 {$, _, _ -> N, _, N, _, N} q_a;
+q_1 {1, _, _ -> N, _, L, _, N} q_2;
+q_2 {1, 0, _ -> R, 0, L, 1, R} q_2;
+q_2 {$, ç, _ -> N, ç, N, _, N} q_a;";
+
+/// Accepts the words of the form xx by guessing where the second half starts
+pub const TM_XX: &str = "q_i {ç, ç, ç -> R, ç, R, ç, R} q_1;
+q_1 {0, _, _ -> R, 0, R, _, N
+    |1, _, _ -> R, 1, R, _, N} q_1;
+q_1 {0, _, _ -> N, _, N, _, N
+    |1, _, _ -> N, _, N, _, N
+    |$, _, _ -> N, _, N, _, N} q_2;
+q_2 {0, _, _ -> R, _, N, 0, R
+    |1, _, _ -> R, _, N, 1, R} q_2;
+q_2 {$, _, _ -> N, _, L, _, L} q_3;
+q_3 {$, 0, 0 -> N, 0, L, 0, L
+    |$, 1, 1 -> N, 1, L, 1, L} q_3;
+q_3 {$, ç, ç -> N, ç, N, ç, N} q_a;";
+
+/// Returns true if the machine reaches an accepting state within [STEP_BUDGET] steps.
+pub fn accepts(graph: &TuringMachineGraph, word: &str) -> bool {
+    run(graph, word, STEP_BUDGET).unwrap().outcome == RunOutcome::Accepted
+}
+
+/// Checks that both graphs give the same answer on every word made of the given characters, up to the given length.
+pub fn assert_same_language(
+    graph1: &TuringMachineGraph,
+    graph2: &TuringMachineGraph,
+    alphabet: &[char],
+    max_len: usize,
+) {
+    let report = check_equivalence(graph1, graph2, alphabet, max_len, STEP_BUDGET).unwrap();
+    assert!(
+        report.is_equivalent(),
+        "the graphs do not agree on the words {:?}",
+        report
+            .counterexamples
+            .iter()
+            .map(|comparison| &comparison.word)
+            .collect::<Vec<&String>>()
+    );
+}
@@ -1,8 +1,11 @@
 use ritm_core::{
     turing_errors::{TuringError, TuringParserError},
     turing_graph::TuringMachineGraph,
-    turing_parser::{parse_transition_string, parse_turing_graph_string},
-    turing_state::{TuringDirection, TuringTransition},
+    turing_parser::{
+        TuringTokenKind, find_turing_graph_errors, graph_to_string, parse_transition_string,
+        parse_turing_graph_string, tokenize_turing_string,
+    },
+    turing_state::{TuringDirection, TuringStateType, TuringTransition},
 };
 
 #[test]
@@ -301,3 +304,265 @@ fn test_parse_graph_bad_transition() {
         },
     }
 }
+
+#[test]
+fn test_parse_annotations() {
+    let machine = String::from(
+        "@pos(q_1, -12.5, 340)
+        q_i {ç, ç -> R, ç, R} q_1;
+        q1 {0, _ -> R, a, R} q_a;
+        @color(q1, #FF8800) @doc(q_1, \"Reads a \\\"0\\\"\\nthen accepts\")
+        @pos(q_a, 0, 0)",
+    );
+
+    let graph = parse_turing_graph_string(machine).unwrap();
+    let metadata = graph.get_state_metadata("1").unwrap();
+    assert_eq!(metadata.position, Some((-12.5, 340.)));
+    assert_eq!(metadata.color.as_deref(), Some("#ff8800"));
+    assert_eq!(metadata.doc.as_deref(), Some("Reads a \"0\"\nthen accepts"));
+    assert_eq!(
+        graph.get_state_metadata("a").unwrap().position,
+        Some((0., 0.))
+    );
+    assert!(graph.get_state_metadata("i").is_none());
+
+    // The annotations are written after the transitions and parsed back
+    let code = graph_to_string(&graph);
+    assert!(code.ends_with(
+        "\n\n@pos(q_a, 0, 0)\n@pos(q_1, -12.5, 340)\n@color(q_1, #ff8800)\n@doc(q_1, \"Reads a \\\"0\\\"\\nthen accepts\")\n"
+    ));
+    let parsed = parse_turing_graph_string(code).unwrap();
+    assert_eq!(parsed.get_state_metadata("1"), Some(metadata));
+    assert_eq!(parsed.get_states(), graph.get_states());
+}
+
+#[test]
+fn test_parse_type_annotations() {
+    let machine = String::from(
+        "q_i {ç, ç -> R, ç, R} q_1;
+        q_1 {0, _ -> R, 0, R} q_2;
+        @type(q_1, accepting) @type(q_2, rejecting)",
+    );
+
+    let graph = parse_turing_graph_string(machine).unwrap();
+    let state_type = |graph: &TuringMachineGraph, name: &str| {
+        graph
+            .get_state_from_name(&name.to_string())
+            .unwrap()
+            .state_type
+            .clone()
+    };
+    assert_eq!(state_type(&graph, "1"), TuringStateType::Accepting);
+    assert_eq!(state_type(&graph, "2"), TuringStateType::Rejecting);
+
+    // Only the types of the states that are not normal are written
+    let code = graph_to_string(&graph);
+    assert!(code.ends_with("\n\n@type(q_1, accepting)\n@type(q_2, rejecting)\n"));
+    let parsed = parse_turing_graph_string(code).unwrap();
+    assert_eq!(parsed.get_states(), graph.get_states());
+
+    // The type of a state without transitions is kept too
+    let mut graph = graph;
+    let end = String::from("end");
+    graph.add_state(&end);
+    graph
+        .set_state_type(&end, TuringStateType::Accepting)
+        .unwrap();
+    let code = graph_to_string(&graph);
+    assert!(code.ends_with("@type(q_end, accepting)\n"));
+    let parsed = parse_turing_graph_string(code).unwrap();
+    assert_eq!(parsed.get_states(), graph.get_states());
+    assert_eq!(state_type(&parsed, "end"), TuringStateType::Accepting);
+
+    // The types of the default states cannot be changed
+    match parse_turing_graph_string("q_i {ç, ç -> R, ç, R} q_a;\n@type(q_a, normal)".to_string())
+    {
+        Err(TuringParserError::EncounteredTuringError {
+            turing_error: TuringError::IllegalActionError { .. },
+            ..
+        }) => (),
+        other => panic!("An IllegalActionError was expected, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_parse_annotations_invalid() {
+    let transitions = "q_i {ç, ç -> R, ç, R} q_1;\n";
+
+    for annotation in [
+        "@pos(q_1, 12)",
+        "@pos(q_1, a, 12)",
+        "@color(q_1, red)",
+        "@color(q_1, #12345)",
+        "@doc(q_1, unquoted)",
+        "@size(q_1, 12)",
+        "@type(q_1, final)",
+    ] {
+        match parse_turing_graph_string(format!("{transitions}{annotation}")) {
+            Err(TuringParserError::ParsingError { .. }) => (),
+            other => panic!("A parsing error was expected for {annotation}, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn test_parse_annotations_isolated_states() {
+    // The annotations declare the states that have no transitions
+    let graph =
+        parse_turing_graph_string("q_i {ç, ç -> R, ç, R} q_1;\n@pos(q_2, 0, 0)".to_string())
+            .unwrap();
+    assert_eq!(
+        graph.get_state_metadata("2").unwrap().position,
+        Some((0., 0.))
+    );
+
+    let mut graph = parse_turing_graph_string("q_i {ç, ç -> R, ç, R} q_1;".to_string()).unwrap();
+    let isolated = String::from("isolated");
+    graph.add_state(&isolated);
+    let metadata = graph.get_state_metadata_mut(&isolated).unwrap();
+    metadata.position = Some((10., -5.));
+    metadata.color = Some("#00ff00".to_string());
+    metadata.doc = Some("Not reached yet".to_string());
+
+    let code = graph_to_string(&graph);
+    let parsed = parse_turing_graph_string(code).unwrap();
+    assert_eq!(parsed.get_states(), graph.get_states());
+    assert_eq!(
+        parsed.get_state_metadata("isolated"),
+        graph.get_state_metadata("isolated")
+    );
+}
+
+#[test]
+fn test_parse_embedded_tests() {
+    let machine = String::from(
+        "// A machine accepting the words starting with a 0
+// @accept 0110
+//@reject\t1
+// @reject
+// @accepted is a simple comment, like @accept 0 in the middle of a line
+q_i {ç, ç -> R, ç, R} q_1; // @accept 01
+q_1 {0, _ -> R, _, N} q_a;",
+    );
+
+    let graph = parse_turing_graph_string(machine).unwrap();
+    let tests: Vec<(&str, bool, usize)> = graph
+        .get_embedded_tests()
+        .iter()
+        .map(|test| (test.word.as_str(), test.accept, test.line))
+        .collect();
+    assert_eq!(
+        tests,
+        vec![
+            ("0110", true, 2),
+            ("1", false, 3),
+            ("", false, 4),
+            ("01", true, 6),
+        ]
+    );
+
+    // The tests are written before the transitions and parsed back
+    let code = graph_to_string(&graph);
+    assert!(code.starts_with("// @accept 0110\n// @reject 1\n// @reject\n// @accept 01\n\n"));
+    let parsed = parse_turing_graph_string(code).unwrap();
+    let words: Vec<&str> = parsed
+        .get_embedded_tests()
+        .iter()
+        .map(|test| test.word.as_str())
+        .collect();
+    assert_eq!(words, vec!["0110", "1", "", "01"]);
+
+    // A file can only contain tests
+    let graph = parse_turing_graph_string("// @accept 1".to_string()).unwrap();
+    assert_eq!(graph.get_embedded_tests().len(), 1);
+
+    // Tests cannot be written inside of a transition
+    match parse_turing_graph_string("q_i {ç, ç -> R, ç, R // @accept 0\n} q_1;".to_string()) {
+        Err(TuringParserError::ParsingError { .. }) => (),
+        other => panic!("A parsing error was expected, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_tokenize() {
+    let source = "q_1 {ç, 0 -> R, _, L | $, 1 -> N, 1, R} q_a;";
+    let tokens: Vec<(TuringTokenKind, &str)> = tokenize_turing_string(source)
+        .into_iter()
+        .map(|token| (token.kind, &source[token.span]))
+        .collect();
+    assert_eq!(
+        &tokens[..6],
+        &[
+            (TuringTokenKind::StateName, "q_1"),
+            (TuringTokenKind::Bracket, "{"),
+            (TuringTokenKind::Symbol, "ç"),
+            (TuringTokenKind::Separator, ","),
+            (TuringTokenKind::Symbol, "0"),
+            (TuringTokenKind::Arrow, "->"),
+        ]
+    );
+    assert_eq!(tokens[6], (TuringTokenKind::Direction, "R"));
+    assert_eq!(
+        tokens[tokens.len() - 2],
+        (TuringTokenKind::StateName, "q_a")
+    );
+    assert_eq!(tokens[tokens.len() - 1], (TuringTokenKind::Separator, ";"));
+
+    // Sources that cannot be parsed are still split into tokens
+    let source = "q_i {0, - ->\n// comment\n  L";
+    let tokens: Vec<(TuringTokenKind, &str)> = tokenize_turing_string(source)
+        .into_iter()
+        .map(|token| (token.kind, &source[token.span]))
+        .collect();
+    assert_eq!(
+        tokens,
+        vec![
+            (TuringTokenKind::StateName, "q_i"),
+            (TuringTokenKind::Bracket, "{"),
+            (TuringTokenKind::Symbol, "0"),
+            (TuringTokenKind::Separator, ","),
+            (TuringTokenKind::Unknown, "-"),
+            (TuringTokenKind::Arrow, "->"),
+            (TuringTokenKind::Direction, "L"),
+        ]
+    );
+    assert!(tokenize_turing_string("").is_empty());
+}
+
+#[test]
+fn test_find_all_errors() {
+    let machine = "q_i {ç, ç -> R, ç, R} q_1;
+q_1 {0, _ -> R, 0, R q_1;
+// A comment; with a semicolon
+@doc(q_1, \"reads; then accepts\")
+q_1 {1, _ -> R, 1} q_a;
+q_1 {$, _ -> N, _, N} q_a;";
+
+    let lines: Vec<Option<usize>> = find_turing_graph_errors(machine)
+        .iter()
+        .map(|error| match error {
+            TuringParserError::ParsingError {
+                line_col_pos,
+                value,
+                ..
+            } => {
+                assert!(value.starts_with("q_1 {"));
+                line_col_pos.map(|(line, _)| line)
+            }
+            other => panic!("A parsing error was expected, got {other:?}"),
+        })
+        .collect();
+    assert_eq!(lines, vec![Some(2), Some(5)]);
+
+    // Valid machines have no errors
+    assert!(find_turing_graph_errors("q_i {ç, ç -> R, ç, R} q_a; // end").is_empty());
+
+    // Errors involving several statements are still reported
+    let errors =
+        find_turing_graph_errors("q_i {ç, ç -> R, ç, R} q_1;\nq_1 {0, _, _ -> R, 0, R, 0, R} q_a;");
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0],
+        TuringParserError::EncounteredTuringError { .. }
+    ));
+}
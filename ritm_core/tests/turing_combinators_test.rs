@@ -0,0 +1,267 @@
+mod common;
+
+use common::{TM_0N1N, accepts};
+use ritm_core::{
+    turing_combinators::{complement, intersection, sequence, union},
+    turing_equivalence::{RunOutcome, run, words_up_to},
+    turing_errors::TuringError,
+    turing_parser::{parse_turing_graph_file_path, parse_turing_graph_string},
+    turing_state::TuringStateType,
+};
+
+/// Accepts the words ending with a 0
+const TM_ENDS_0: &str = "q_i {ç, ç -> R, ç, N} q_1;
+q_1 {0, ç -> R, ç, N
+    |1, ç -> R, ç, N} q_1;
+q_1 {$, ç -> L, ç, N} q_2;
+q_2 {0, ç -> N, ç, N} q_a;
+q_2 {1, ç -> N, ç, N
+    |ç, ç -> N, ç, N} q_r;";
+
+/// Accepts the words starting with a 1, reading a 0 is explicit so that it is part of the inferred input alphabet
+const TM_STARTS_1: &str = "q_i {ç, ç -> R, ç, N} q_1;
+q_1 {1, ç -> N, ç, N} q_a;
+q_1 {0, ç -> N, ç, N} q_r;";
+
+/// Writes the input with every bit flipped on its first writing tape
+const TM_FLIP: &str = "q_i {ç, ç -> R, ç, R} q_1;
+q_1 {0, _ -> R, 1, R
+    |1, _ -> R, 0, R} q_1;
+q_1 {$, _ -> N, _, N} q_a;";
+
+#[test]
+fn union_test() {
+    let m1 = parse_turing_graph_string(TM_ENDS_0.to_string()).unwrap();
+    let m2 = parse_turing_graph_string(TM_0N1N.to_string()).unwrap();
+    let combined = union(&m1, &m2).unwrap();
+
+    // The tape counts are lined up
+    assert_eq!(combined.get_k(), 2);
+    assert!(combined.get_name_index_hashmap().contains_key("A1"));
+    assert!(combined.get_name_index_hashmap().contains_key("B1"));
+
+    for word in words_up_to(&['0', '1'], 4) {
+        assert_eq!(
+            accepts(&combined, &word),
+            accepts(&m1, &word) || accepts(&m2, &word),
+            "wrong result for \"{word}\""
+        );
+    }
+}
+
+#[test]
+fn intersection_test() {
+    let m1 = parse_turing_graph_file_path("resources/binary_palindrome.tm".to_string()).unwrap();
+    let m2 = parse_turing_graph_string(TM_ENDS_0.to_string()).unwrap();
+    let combined = intersection(&m1, &m2).unwrap();
+
+    // Each machine has its own tapes, plus the copy of the input
+    assert_eq!(combined.get_k(), m1.get_k() + m2.get_k() + 1);
+
+    for word in words_up_to(&['0', '1'], 4) {
+        assert_eq!(
+            accepts(&combined, &word),
+            accepts(&m1, &word) && accepts(&m2, &word),
+            "wrong result for \"{word}\""
+        );
+    }
+}
+
+#[test]
+fn intersection_different_tapes() {
+    let m1 = parse_turing_graph_string(TM_0N1N.to_string()).unwrap();
+    let m2 = parse_turing_graph_string(TM_STARTS_1.to_string()).unwrap();
+    let combined = intersection(&m1, &m2).unwrap();
+
+    assert_eq!(combined.get_k(), 4);
+    // 0^n 1^n never starts with a 1
+    for word in words_up_to(&['0', '1'], 4) {
+        assert!(!accepts(&combined, &word), "wrong result for \"{word}\"");
+    }
+
+    let combined = intersection(&m1, &complement(&m2, None).unwrap()).unwrap();
+    for word in words_up_to(&['0', '1'], 4) {
+        assert_eq!(
+            accepts(&combined, &word),
+            accepts(&m1, &word),
+            "wrong result for \"{word}\""
+        );
+    }
+}
+
+#[test]
+fn sequence_test() {
+    let flip = parse_turing_graph_string(TM_FLIP.to_string()).unwrap();
+    let starts_1 = parse_turing_graph_string(TM_STARTS_1.to_string()).unwrap();
+    let m_0n1n = parse_turing_graph_string(TM_0N1N.to_string()).unwrap();
+
+    // Words starting with a 0
+    let combined = sequence(&flip, &starts_1).unwrap();
+    assert_eq!(combined.get_k(), 2);
+    for word in words_up_to(&['0', '1'], 4) {
+        assert_eq!(
+            accepts(&combined, &word),
+            word.starts_with('0'),
+            "wrong result for \"{word}\""
+        );
+    }
+
+    // Words of the form 1^n 0^n
+    let combined = sequence(&flip, &m_0n1n).unwrap();
+    assert_eq!(combined.get_k(), 3);
+    for word in words_up_to(&['0', '1'], 4) {
+        let flipped: String = word
+            .chars()
+            .map(|c| if c == '0' { '1' } else { '0' })
+            .collect();
+        assert_eq!(
+            accepts(&combined, &word),
+            accepts(&m_0n1n, &flipped),
+            "wrong result for \"{word}\""
+        );
+    }
+}
+
+#[test]
+fn sequence_unknown_characters() {
+    // Accepts the words starting with a 1 without reading the rest, then the empty output
+    let first = parse_turing_graph_string(
+        "q_i {ç, ç -> R, ç, R} q_1;
+        q_1 {1, _ -> N, _, N} q_a;"
+            .to_string(),
+    )
+    .unwrap();
+    let second = parse_turing_graph_string(
+        "q_i {ç, ç -> R, ç, R} q_1;
+        q_1 {$, _ -> N, _, N} q_a;"
+            .to_string(),
+    )
+    .unwrap();
+    let combined = sequence(&first, &second).unwrap();
+
+    // x is never read by the first machine, so it only matters when it is reached
+    assert!(accepts(&combined, "1x"));
+    assert!(!accepts(&combined, "x1"));
+
+    // The input is read beforehand by the intersection
+    let combined = intersection(&first, &first).unwrap();
+    assert!(accepts(&combined, "11"));
+    assert!(!accepts(&combined, "1x"));
+}
+
+#[test]
+fn complement_test() {
+    for m in [TM_ENDS_0, TM_STARTS_1, TM_0N1N] {
+        let m = parse_turing_graph_string(m.to_string()).unwrap();
+        let combined = complement(&m, None).unwrap();
+
+        assert!(combined.is_deterministic());
+        for word in words_up_to(&['0', '1'], 6) {
+            assert_ne!(
+                accepts(&combined, &word),
+                accepts(&m, &word),
+                "wrong result for \"{word}\""
+            );
+        }
+    }
+}
+
+#[test]
+fn complement_given_alphabet() {
+    let m = parse_turing_graph_string(TM_STARTS_1.to_string()).unwrap();
+
+    // x is not part of the inferred alphabet, so both machines get stuck on it
+    let combined = complement(&m, None).unwrap();
+    assert!(!accepts(&m, "x"));
+    assert!(!accepts(&combined, "x"));
+
+    let alphabet = ['0', '1', 'x'];
+    let combined = complement(&m, Some(&alphabet)).unwrap();
+    assert!(combined.is_deterministic());
+    for word in words_up_to(&alphabet, 4) {
+        assert_ne!(
+            accepts(&combined, &word),
+            accepts(&m, &word),
+            "wrong result for \"{word}\""
+        );
+    }
+
+    match complement(&m, Some(&['0', '_'])) {
+        Err(TuringError::IllegalActionError { cause: _ }) => (),
+        _ => panic!("Should have returned an illegal action error"),
+    }
+}
+
+#[test]
+fn complement_not_stopping() {
+    // Loops forever on the words starting with a 1
+    let m = parse_turing_graph_string(
+        "q_i {ç, ç -> R, ç, N} q_1;
+        q_1 {1, ç -> N, ç, N} q_1;
+        q_1 {0, ç -> N, ç, N} q_a;"
+            .to_string(),
+    )
+    .unwrap();
+    let combined = complement(&m, None).unwrap();
+
+    assert!(accepts(&combined, ""));
+    assert!(!accepts(&combined, "01"));
+    // The machine is not a total decider, so its complement does not accept the words it never stops on
+    for graph in [&m, &combined] {
+        assert_eq!(
+            run(graph, "10", 1000).unwrap().outcome,
+            RunOutcome::OutOfBudget
+        );
+    }
+}
+
+#[test]
+fn complement_typed_states() {
+    // Accepts the words starting with a 1 in q_yes, and rejects the other ones in q_no
+    let m = parse_turing_graph_string(
+        "q_i {ç, ç -> R, ç, N} q_1;
+        q_1 {1, ç -> N, ç, N} q_yes;
+        q_1 {0, ç -> N, ç, N
+            |$, ç -> N, ç, N} q_no;
+        @type(q_yes, accepting)
+        @type(q_no, rejecting)"
+            .to_string(),
+    )
+    .unwrap();
+    let combined = complement(&m, None).unwrap();
+
+    let yes = combined.get_name_index_hashmap()["yes"];
+    let no = combined.get_name_index_hashmap()["no"];
+    assert_eq!(
+        combined.get_state(yes).unwrap().state_type,
+        TuringStateType::Rejecting
+    );
+    assert_eq!(
+        combined.get_state(no).unwrap().state_type,
+        TuringStateType::Accepting
+    );
+    for word in words_up_to(&['0', '1'], 4) {
+        assert_ne!(
+            accepts(&combined, &word),
+            accepts(&m, &word),
+            "wrong result for \"{word}\""
+        );
+    }
+}
+
+#[test]
+fn complement_non_deterministic() {
+    let m = parse_turing_graph_string(
+        "q_i {ç, ç -> R, ç, N} q_1;
+        q_1 {1, ç -> N, ç, N} q_a;
+        q_1 {1, ç -> N, ç, N} q_r;"
+            .to_string(),
+    )
+    .unwrap();
+    assert!(!m.is_deterministic());
+
+    match complement(&m, None) {
+        Err(TuringError::IllegalActionError { cause: _ }) => (),
+        _ => panic!("Should have returned an illegal action error"),
+    }
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use common::{TM_XX, accepts, assert_same_language};
+use ritm_core::{
+    turing_determinizer::determinize,
+    turing_errors::TuringError,
+    turing_parser::{parse_turing_graph_file_path, parse_turing_graph_string},
+};
+
+/// Accepts the words containing 11, one of the branches never stops
+const TM_INFINITE_BRANCH: &str = "q_i {ç, ç -> R, ç, R} q_1;
+q_1 {0, _ -> R, _, N
+    |1, _ -> R, _, N} q_1;
+q_1 {1, _ -> R, _, N} q_2;
+q_1 {0, _ -> N, _, N} q_3;
+q_3 {0, _ -> N, _, N} q_3;
+q_2 {1, _ -> R, _, N} q_a;";
+
+#[test]
+fn determinize_resources() {
+    for name in [
+        "binary_palindrome",
+        "turing2",
+        "turing3",
+        "turing4",
+        "turing5",
+        "turing6",
+        "turing7",
+    ] {
+        let graph = parse_turing_graph_file_path(format!("resources/{name}.tm")).unwrap();
+        let deterministic = determinize(&graph, None).unwrap();
+
+        assert!(
+            deterministic.is_deterministic(),
+            "{name} is not deterministic"
+        );
+        assert_eq!(deterministic.get_k(), graph.get_k() + 1);
+        assert_same_language(&graph, &deterministic, &graph.get_input_alphabet(), 3);
+    }
+}
+
+#[test]
+fn determinize_guessing_machine() {
+    let graph = parse_turing_graph_string(TM_XX.to_string()).unwrap();
+    assert!(!graph.is_deterministic());
+    let deterministic = determinize(&graph, None).unwrap();
+
+    assert!(deterministic.is_deterministic());
+    assert!(accepts(&deterministic, "00"));
+    assert!(!accepts(&deterministic, "10"));
+    assert_same_language(&graph, &deterministic, &['0', '1'], 2);
+}
+
+#[test]
+fn determinize_explores_breadth_first() {
+    let graph = parse_turing_graph_string(TM_INFINITE_BRANCH.to_string()).unwrap();
+    let deterministic = determinize(&graph, None).unwrap();
+
+    // A depth first search could get stuck in q_3 before finding 11
+    assert!(accepts(&deterministic, "0110"));
+    assert!(accepts(&deterministic, "11"));
+    // Every branch is finite, so the machine stops and rejects
+    assert!(!accepts(&deterministic, "1"));
+    assert!(!accepts(&deterministic, ""));
+}
+
+#[test]
+fn determinize_given_alphabet() {
+    // The first branch dies on the character after the 0, while the second one accepts
+    let graph = parse_turing_graph_string(
+        "q_i {ç, ç -> R, ç, R} q_1;
+        q_1 {0, _ -> R, _, N} q_2;
+        q_1 {0, _ -> N, _, N} q_a;"
+            .to_string(),
+    )
+    .unwrap();
+    assert!(accepts(&graph, "0x"));
+
+    // The inferred alphabet is only made of 0, so the first branch gets stuck on x
+    let deterministic = determinize(&graph, None).unwrap();
+    assert!(accepts(&deterministic, "00"));
+    assert!(!accepts(&deterministic, "0x"));
+
+    let deterministic = determinize(&graph, Some(&['0', 'x'])).unwrap();
+    assert!(deterministic.is_deterministic());
+    assert!(accepts(&deterministic, "0x"));
+    assert_same_language(&graph, &deterministic, &['0', 'x'], 3);
+
+    match determinize(&graph, Some(&['0', '$'])) {
+        Err(TuringError::IllegalActionError { cause: _ }) => (),
+        _ => panic!("Should have returned an illegal action error"),
+    }
+}
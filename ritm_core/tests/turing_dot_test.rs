@@ -0,0 +1,57 @@
+use ritm_core::{
+    turing_dot::{DotOptions, graph_to_dot},
+    turing_machine::{Mode, TuringMachines},
+    turing_parser::parse_turing_graph_file_path,
+};
+
+#[test]
+fn dot_groups_transitions() {
+    let graph = parse_turing_graph_file_path("resources/binary_palindrome.tm".to_string()).unwrap();
+    let dot = graph_to_dot(&graph, &DotOptions::default());
+
+    assert!(dot.starts_with("digraph turing_machine {\n"));
+    assert!(dot.ends_with("}\n"));
+    assert!(dot.contains("    start -> \"q_i\";\n"));
+
+    // One edge per pair of states, plus the one pointing to the initial state
+    assert_eq!(dot.matches(" -> \"q_").count(), 8);
+    assert!(
+        dot.contains(
+            "    \"q_copy\" -> \"q_copy\" [label=\"0, _ -> R, 0, R\\n1, _ -> R, 1, R\"];\n"
+        )
+    );
+    assert!(dot.contains("    \"q_check\" -> \"q_a\" [label=\"$, ç -> N, ç, N\"];\n"));
+
+    // Final states are styled
+    assert!(dot.contains("    \"q_a\" [shape=doublecircle, color=darkgreen];\n"));
+    assert!(dot.contains("    \"q_r\" [shape=doubleoctagon, color=red];\n"));
+    assert!(dot.contains("    \"q_copy\";\n"));
+    // Nothing is highlighted
+    assert!(!dot.contains("blue"));
+}
+
+#[test]
+fn dot_highlights_path() {
+    let graph = parse_turing_graph_file_path("resources/binary_palindrome.tm".to_string()).unwrap();
+    let mut tm = TuringMachines::new(graph.clone(), "0".to_string(), Mode::SaveAll).unwrap();
+    let path = tm.get_path_to_accept(|| true).unwrap();
+    let options = DotOptions::from_path(&path);
+
+    assert_eq!(options.current_state, Some(1));
+    assert_eq!(options.path.len(), path.len() - 1);
+
+    let dot = graph_to_dot(&graph, &options);
+    assert!(dot.contains("    \"q_a\" [shape=doublecircle, color=darkgreen, style=filled, fillcolor=lightblue, penwidth=2];\n"));
+    // The transition reading a 1 is never taken, but it shares its edge with the one reading a 0
+    assert!(dot.contains("    \"q_copy\" -> \"q_copy\" [label=\"0, _ -> R, 0, R\\n1, _ -> R, 1, R\", color=blue, fontcolor=blue, penwidth=2];\n"));
+    assert!(dot.contains("    \"q_r\" [shape=doubleoctagon, color=red];\n"));
+
+    // Only the current state
+    let options = DotOptions {
+        current_state: Some(0),
+        path: vec![],
+    };
+    let dot = graph_to_dot(&graph, &options);
+    assert!(dot.contains("    \"q_i\" [style=filled, fillcolor=lightblue];\n"));
+    assert_eq!(dot.matches("blue").count(), 1);
+}
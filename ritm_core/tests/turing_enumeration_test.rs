@@ -0,0 +1,74 @@
+use ritm_core::{
+    turing_enumeration::enumerate_language,
+    turing_errors::TuringError,
+    turing_parser::{parse_turing_graph_file_path, parse_turing_graph_string},
+};
+
+/// The machine of the README, accepting the words of the form xx with x a non empty binary word
+const TM_XX_NON_EMPTY: &str = "q_i { ç, ç -> R, ç, R } q_1;
+
+q_1 { 0, _ -> R, 0, R 
+    | 1, _ -> R, 1, R } q_1;
+
+q_1 { 0, _ -> N, _, L 
+    | 1, _ -> N, _, L } q_2;
+
+q_2 { 0, 0 -> N, 0, L 
+    | 0, 1 -> N, 1, L 
+    | 1, 0 -> N, 0, L 
+    | 1, 1 -> N, 1, L } q_2;
+
+q_2 { 0, ç -> N, ç, R 
+    | 1, ç -> N, ç, R } q_3;
+
+q_3 { $, _ -> N, _, N } q_a;
+
+q_3 { 0, 0 -> R, 0, R 
+    | 1, 1 -> R, 1, R } q_3;";
+
+#[test]
+fn enumerate_xx() {
+    let graph = parse_turing_graph_string(TM_XX_NON_EMPTY.to_string()).unwrap();
+    let enumeration = enumerate_language(&graph, None, 4, 10_000).unwrap();
+
+    assert_eq!(enumeration.alphabet, vec!['0', '1']);
+    assert_eq!(
+        enumeration.accepted,
+        vec!["00", "11", "0000", "0101", "1010", "1111"]
+    );
+    assert!(enumeration.undecided.is_empty());
+    assert_eq!(enumeration.rejected.len(), 31 - 6);
+    // Sorted by length, then in lexicographic order
+    assert_eq!(enumeration.rejected[..4], ["", "0", "1", "01"]);
+}
+
+#[test]
+fn enumerate_given_alphabet() {
+    let graph = parse_turing_graph_file_path("resources/binary_palindrome.tm".to_string()).unwrap();
+    let enumeration = enumerate_language(&graph, Some(&['1', '0', '1']), 2, 1000).unwrap();
+
+    assert_eq!(enumeration.alphabet, vec!['0', '1']);
+    assert_eq!(enumeration.accepted, vec!["", "0", "1", "00", "11"]);
+    assert_eq!(enumeration.rejected, vec!["01", "10"]);
+
+    match enumerate_language(&graph, Some(&['0', '_']), 2, 1000) {
+        Err(TuringError::IllegalActionError { .. }) => (),
+        other => panic!("An IllegalActionError was expected, got {other:?}"),
+    }
+}
+
+#[test]
+fn enumerate_undecided() {
+    let graph = parse_turing_graph_string(
+        "q_i {ç, ç -> R, ç, N} q_loop;
+        q_loop {a, ç -> N, ç, N} q_loop;
+        q_loop {$, ç -> N, ç, N} q_a;"
+            .to_string(),
+    )
+    .unwrap();
+    let enumeration = enumerate_language(&graph, Some(&['a', 'b']), 2, 100).unwrap();
+
+    assert_eq!(enumeration.accepted, vec![""]);
+    assert_eq!(enumeration.rejected, vec!["b", "ba", "bb"]);
+    assert_eq!(enumeration.undecided, vec!["a", "aa", "ab"]);
+}
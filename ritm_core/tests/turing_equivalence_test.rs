@@ -0,0 +1,95 @@
+use ritm_core::{
+    turing_equivalence::{RunOutcome, check_equivalence, run},
+    turing_errors::TuringError,
+    turing_parser::{parse_turing_graph_file_path, parse_turing_graph_string},
+};
+
+/// Accepts every word made of 0 and 1
+const TM_ALL_BINARY: &str = "q_i {ç, ç -> R, ç, N} q_read;
+q_read {0, ç -> R, ç, N | 1, ç -> R, ç, N} q_read;
+q_read {$, ç -> N, ç, N} q_a;";
+
+/// Accepts the words starting with 0 and loops forever on the others
+const TM_LOOPS_ON_1: &str = "q_i {ç, ç -> R, ç, N} q_first;
+q_first {0, ç -> N, ç, N} q_a;
+q_first {$, ç -> N, ç, N} q_a;
+q_first {1, ç -> N, ç, N} q_loop;
+q_loop {1, ç -> N, ç, N} q_loop;";
+
+#[test]
+fn equivalent_machines() {
+    let graph = parse_turing_graph_file_path("resources/binary_palindrome.tm".to_string()).unwrap();
+    let report = check_equivalence(&graph, &graph.clone(), &['0', '1'], 6, 1000).unwrap();
+
+    assert!(report.is_equivalent());
+    assert_eq!(report.words_checked, 127);
+}
+
+#[test]
+fn counterexamples() {
+    let palindrome =
+        parse_turing_graph_file_path("resources/binary_palindrome.tm".to_string()).unwrap();
+    let all = parse_turing_graph_string(TM_ALL_BINARY.to_string()).unwrap();
+    let report = check_equivalence(&palindrome, &all, &['0', '1'], 4, 1000).unwrap();
+
+    assert!(!report.is_equivalent());
+    assert_eq!(report.words_checked, 31);
+    let words: Vec<&str> = report
+        .counterexamples
+        .iter()
+        .map(|c| c.word.as_str())
+        .collect();
+    // The words that are not palindromes, shortest first
+    assert_eq!(
+        words,
+        vec![
+            "01", "10", "001", "011", "100", "110", "0001", "0010", "0011", "0100", "0101", "0111",
+            "1000", "1010", "1011", "1100", "1101", "1110"
+        ]
+    );
+
+    let first = &report.counterexamples[0];
+    assert_eq!(first.first.outcome, RunOutcome::Rejected);
+    assert_eq!(first.second.outcome, RunOutcome::Accepted);
+    // The second machine reads every character then accepts
+    assert_eq!(first.second.steps, 4);
+    assert!(first.first.steps > 0);
+}
+
+#[test]
+fn out_of_budget() {
+    let loops = parse_turing_graph_string(TM_LOOPS_ON_1.to_string()).unwrap();
+    let all = parse_turing_graph_string(TM_ALL_BINARY.to_string()).unwrap();
+
+    let result = run(&loops, "10", 50).unwrap();
+    assert_eq!(result.outcome, RunOutcome::OutOfBudget);
+    assert_eq!(result.steps, 50);
+
+    let report = check_equivalence(&loops, &all, &['0', '1'], 2, 50).unwrap();
+    let words: Vec<&str> = report
+        .counterexamples
+        .iter()
+        .map(|c| c.word.as_str())
+        .collect();
+    // The words starting with 0 are accepted by both machines
+    assert_eq!(words, vec!["1", "10", "11"]);
+    assert!(
+        report
+            .counterexamples
+            .iter()
+            .all(|c| c.first.outcome == RunOutcome::OutOfBudget)
+    );
+
+    // Both machines run out of budget on the same word
+    let report = check_equivalence(&loops, &loops.clone(), &['1'], 1, 50).unwrap();
+    assert_eq!(report.counterexamples.len(), 1);
+}
+
+#[test]
+fn invalid_alphabet() {
+    let all = parse_turing_graph_string(TM_ALL_BINARY.to_string()).unwrap();
+    match check_equivalence(&all, &all.clone(), &['0', '$'], 2, 50) {
+        Err(TuringError::IllegalActionError { .. }) => (),
+        other => panic!("An IllegalActionError was expected, got {other:?}"),
+    }
+}
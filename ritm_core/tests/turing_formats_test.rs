@@ -0,0 +1,89 @@
+use std::path::Path;
+
+use ritm_core::{
+    turing_errors::TuringParserError,
+    turing_formats::{
+        GraphFormat, graph_to_string_by_extension, load_graph_by_extension,
+        parse_graph_by_extension,
+    },
+    turing_parser::{graph_to_string, parse_turing_graph_file_path},
+};
+
+/// Accepts the words starting with an a
+const JFF_STARTS_A: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?><structure>
+	<type>turing</type>
+	<automaton>
+		<state id="0" name="q0">
+			<x>60.0</x>
+			<y>120.0</y>
+			<initial/>
+		</state>
+		<state id="1" name="q1">
+			<x>200.0</x>
+			<y>120.0</y>
+			<final/>
+		</state>
+		<transition>
+			<from>0</from>
+			<to>1</to>
+			<read>a</read>
+			<write>a</write>
+			<move>R</move>
+		</transition>
+	</automaton>
+</structure>"#;
+
+#[test]
+fn detect_format() {
+    assert_eq!(
+        GraphFormat::from_path(Path::new("m.jff")),
+        GraphFormat::Jflap
+    );
+    assert_eq!(
+        GraphFormat::from_path(Path::new("dir/m.tm")),
+        GraphFormat::Tm
+    );
+    assert_eq!(GraphFormat::from_path(Path::new("m")), GraphFormat::Tm);
+    #[cfg(feature = "serde")]
+    assert_eq!(
+        GraphFormat::from_path(Path::new("m.json")),
+        GraphFormat::Json
+    );
+}
+
+#[test]
+fn load_by_extension() {
+    let path = Path::new("resources/binary_palindrome.tm");
+    let graph = load_graph_by_extension(path).unwrap();
+    let expected = parse_turing_graph_file_path(path.to_string_lossy().to_string()).unwrap();
+    assert_eq!(graph_to_string(&graph), graph_to_string(&expected));
+
+    match load_graph_by_extension(Path::new("resources/missing.jff")) {
+        Err(TuringParserError::FileError { given_path, .. }) => {
+            assert_eq!(given_path, "resources/missing.jff")
+        }
+        _ => panic!("Should have returned a file error"),
+    }
+}
+
+#[test]
+fn jflap_positions_are_kept() {
+    let graph = parse_graph_by_extension(Path::new("m.jff"), JFF_STARTS_A.to_string()).unwrap();
+    let positions = graph.get_state_positions();
+    assert_eq!(positions.get("a"), Some(&(200., 120.)));
+
+    // The positions are written in the metadata of every format
+    let tm = graph_to_string_by_extension(&graph, Path::new("m.tm"));
+    assert!(tm.contains("@pos(q_a, 200, 120)"), "{tm}");
+    let jff = graph_to_string_by_extension(&graph, Path::new("m.jff"));
+    assert!(jff.contains("name=\"q_a\">\n\t\t\t<x>200.0</x>\n\t\t\t<y>120.0</y>"));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json_round_trip() {
+    let graph = load_graph_by_extension(Path::new("resources/turing3.tm")).unwrap();
+    let json = graph_to_string_by_extension(&graph, Path::new("m.json"));
+    let loaded = parse_graph_by_extension(Path::new("m.json"), json).unwrap();
+    assert_eq!(graph_to_string(&loaded), graph_to_string(&graph));
+}
@@ -0,0 +1,75 @@
+use ritm_core::{
+    turing_errors::TuringParserError, turing_formatter::format_turing_graph_string,
+    turing_parser::parse_turing_graph_string,
+};
+
+#[test]
+fn format_machine() {
+    let source = "// Accepts the words starting with a 0
+// @accept 0   
+
+
+q_i{ç,ç->R,ç,R}q_1;   // Skip ç
+q1 {0, _ -> R, a, R
+   // Reads the first character
+   |1,_->R,b,R} q_a;
+@pos( q_1 ,  -12.5,340)   @doc(q_1, \"Reads, then \\\"accepts\\\"\")
+q_1 {
+    $, _ -> N, _, N } q_r;";
+
+    let formatted = format_turing_graph_string(source.to_string()).unwrap();
+    assert_eq!(
+        formatted,
+        "// Accepts the words starting with a 0
+// @accept 0
+
+q_i { ç, ç -> R, ç, R } q_1; // Skip ç
+// Reads the first character
+q_1 { 0, _ -> R, a, R
+    | 1, _ -> R, b, R } q_a;
+@pos(q_1, -12.5, 340)
+@doc(q_1, \"Reads, then \\\"accepts\\\"\")
+q_1 { $, _ -> N, _, N } q_r;
+"
+    );
+
+    // Formatting twice changes nothing, and the machine stays the same
+    assert_eq!(
+        format_turing_graph_string(formatted.clone()).unwrap(),
+        formatted
+    );
+    let before = parse_turing_graph_string(source.to_string()).unwrap();
+    let after = parse_turing_graph_string(formatted).unwrap();
+    assert_eq!(before.get_states(), after.get_states());
+    assert_eq!(
+        before.get_state_metadata("1"),
+        after.get_state_metadata("1")
+    );
+    assert_eq!(before.get_embedded_tests(), after.get_embedded_tests());
+}
+
+#[test]
+fn format_resources() {
+    for name in ["binary_palindrome", "turing3", "turing6"] {
+        let source = std::fs::read_to_string(format!("resources/{name}.tm")).unwrap();
+        let formatted = format_turing_graph_string(source.clone()).unwrap();
+        assert_eq!(
+            format_turing_graph_string(formatted.clone()).unwrap(),
+            formatted,
+            "{name}"
+        );
+        assert_eq!(
+            parse_turing_graph_string(source).unwrap().get_states(),
+            parse_turing_graph_string(formatted).unwrap().get_states(),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn format_invalid() {
+    match format_turing_graph_string("q_i {ç, ç -> R, ç, R} q_1".to_string()) {
+        Err(TuringParserError::ParsingError { .. }) => (),
+        other => panic!("A ParsingError was expected, got {other:?}"),
+    }
+}
@@ -417,6 +417,134 @@ fn delete_node() {
     );
 }
 
+#[test]
+fn rename_state() {
+    let mut graph = TuringMachineGraph::new(1).unwrap();
+    let q1 = "1".to_string();
+    let q2 = "2".to_string();
+    let ind_1 = graph.add_state(&q1);
+    graph.add_state(&q2);
+    let t = TuringTransition::create(
+        vec!['ç', 'ç'],
+        vec!['ç'],
+        vec![TuringDirection::Right, TuringDirection::Right],
+    )
+    .unwrap();
+    graph
+        .append_rule_state_by_name(&"i".to_string(), t.clone(), &q1)
+        .unwrap();
+    graph
+        .append_rule_state_by_name(&q1, t.clone(), &q1)
+        .unwrap();
+    graph.get_state_metadata_mut(&q1).unwrap().color = Some("#ff0000".to_string());
+
+    let renamed = "even".to_string();
+    graph.rename_state(&q1, &renamed).unwrap();
+    // The index, the transitions and the metadata are kept
+    assert_eq!(*graph.get_name_index_hashmap().get("even").unwrap(), ind_1);
+    assert!(graph.get_name_index_hashmap().get("1").is_none());
+    assert_eq!(graph.get_state(ind_1).unwrap().name, "even");
+    assert_eq!(graph.get_transitions_by_index(0, ind_1).unwrap(), vec!(&t));
+    assert_eq!(
+        graph.get_transitions_by_index(ind_1, ind_1).unwrap(),
+        vec!(&t)
+    );
+    assert!(graph.get_state_metadata("1").is_none());
+    assert_eq!(
+        graph.get_state_metadata("even").unwrap().color.as_deref(),
+        Some("#ff0000")
+    );
+
+    expect_illegal_action_error(graph.rename_state(&renamed, &q2));
+    expect_illegal_action_error(graph.rename_state(&renamed, &"q-2".to_string()));
+    expect_illegal_action_error(graph.rename_state(&"a".to_string(), &q1));
+    expect_unk_name_error(graph.rename_state(&q1, &"3".to_string()));
+}
+
+#[test]
+fn set_state_type() {
+    let mut graph = TuringMachineGraph::new(1).unwrap();
+    let q1 = "1".to_string();
+    graph.add_state(&q1);
+
+    assert_eq!(
+        graph
+            .set_state_type(&q1, TuringStateType::Accepting)
+            .unwrap(),
+        TuringStateType::Normal
+    );
+    assert_eq!(
+        TuringStateType::Accepting,
+        graph.get_state_from_name(&q1).unwrap().state_type
+    );
+
+    expect_illegal_action_error(graph.set_state_type(&"i".to_string(), TuringStateType::Accepting));
+    expect_illegal_action_error(graph.set_state_type(&"r".to_string(), TuringStateType::Normal));
+    expect_unk_name_error(graph.set_state_type(&"2".to_string(), TuringStateType::Normal));
+}
+
+#[test]
+fn replace_transition() {
+    let mut graph = TuringMachineGraph::new(1).unwrap();
+    let q1 = "1".to_string();
+    let q2 = "2".to_string();
+    let ind_1 = graph.add_state(&q1);
+    let ind_2 = graph.add_state(&q2);
+    let t1 = TuringTransition::create(
+        vec!['0', '_'],
+        vec!['0'],
+        vec![TuringDirection::Right, TuringDirection::Right],
+    )
+    .unwrap();
+    let t2 = TuringTransition::create(
+        vec!['1', '_'],
+        vec!['1'],
+        vec![TuringDirection::Right, TuringDirection::Right],
+    )
+    .unwrap();
+    let t3 = TuringTransition::create(
+        vec!['1', '_'],
+        vec!['_'],
+        vec![TuringDirection::None, TuringDirection::None],
+    )
+    .unwrap();
+    graph
+        .append_rule_state_by_name(&q1, t1.clone(), &q1)
+        .unwrap();
+    graph
+        .append_rule_state_by_name(&q1, t2.clone(), &q1)
+        .unwrap();
+
+    // The transition keeps its place, but can go to another state
+    graph
+        .replace_transition(&q1, &t1, &q1, t3.clone(), &q2)
+        .unwrap();
+    let transitions = &graph.get_state(ind_1).unwrap().transitions;
+    assert_eq!(transitions, &vec![t3.clone(), t2.clone()]);
+    assert_eq!(transitions[0].index_to_state, Some(ind_2));
+    assert_eq!(transitions[1].index_to_state, Some(ind_1));
+
+    // The replaced transition must exist
+    expect_illegal_action_error(graph.replace_transition(&q1, &t1, &q1, t3.clone(), &q2));
+    expect_illegal_action_error(graph.replace_transition(&q1, &t2, &q2, t3.clone(), &q2));
+    expect_unk_name_error(graph.replace_transition(&q1, &t2, &q1, t3.clone(), &"3".to_string()));
+
+    let two_tapes = TuringTransition::create(
+        vec!['1', '_', '_'],
+        vec!['_', '_'],
+        vec![
+            TuringDirection::None,
+            TuringDirection::None,
+            TuringDirection::None,
+        ],
+    )
+    .unwrap();
+    match graph.replace_transition(&q1, &t2, &q1, two_tapes, &q1) {
+        Err(TuringError::IncompatibleTransitionError { .. }) => (),
+        other => panic!("An IncompatibleTransitionError was expected, got {other:?}"),
+    }
+}
+
 // TODO : add test for removing states with indexes
 
 fn expect_illegal_action_error<O>(res: Result<O, TuringError>) {
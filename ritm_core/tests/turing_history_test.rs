@@ -0,0 +1,395 @@
+use ritm_core::{
+    turing_errors::TuringError,
+    turing_graph::{StateMetadata, TuringMachineGraph},
+    turing_history::{GraphEdit, GraphHistory},
+    turing_minimizer::minimize_graph,
+    turing_parser::{graph_to_string, parse_transition_string, parse_turing_graph_string},
+    turing_state::{TuringStateType, TuringTransition},
+};
+
+fn get_graph() -> TuringMachineGraph {
+    parse_turing_graph_string(
+        "q_i {ç, ç -> R, ç, R} q_1;
+        q_1 {0, _ -> R, 0, R} q_1;
+        q_1 {1, _ -> R, 1, R} q_2;
+        q_2 {$, _ -> N, _, N} q_a;
+        q_2 {0, _ -> R, 0, R} q_1;"
+            .to_string(),
+    )
+    .unwrap()
+}
+
+/// Returns the lines describing the graph, sorted so graphs ordered differently can be compared.
+fn sorted_lines(graph: &TuringMachineGraph) -> Vec<String> {
+    let mut lines: Vec<String> = graph_to_string(graph)
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect();
+    lines.sort();
+    lines
+}
+
+/// Parses a transition of the form `q_x {transition} q_y`, keeping only the transition.
+fn parse_transition(transition: &str) -> TuringTransition {
+    parse_transition_string(transition.to_string())
+        .unwrap()
+        .1
+        .remove(0)
+}
+
+#[test]
+fn undo_redo_transitions() {
+    let mut graph = get_graph();
+    let original = graph_to_string(&graph);
+    let mut history = GraphHistory::new();
+    assert!(!history.can_undo());
+
+    let transition = parse_transition("q_2 {1, _ -> R, 1, R} q_r");
+    history
+        .apply(
+            &mut graph,
+            GraphEdit::AddTransition {
+                from: "2".to_string(),
+                transition: transition.clone(),
+                to: "r".to_string(),
+            },
+        )
+        .unwrap();
+    let added = graph_to_string(&graph);
+    assert_ne!(original, added);
+    assert!(history.can_undo());
+
+    history.undo(&mut graph).unwrap().unwrap();
+    assert_eq!(original, graph_to_string(&graph));
+    assert!(!history.can_undo());
+    assert!(history.can_redo());
+
+    history.redo(&mut graph).unwrap().unwrap();
+    assert_eq!(added, graph_to_string(&graph));
+    assert!(!history.can_redo());
+
+    // Removing a transition that does not exist is refused and not remembered
+    let missing = parse_transition("q_2 {1, _ -> L, 1, R} q_r");
+    expect_illegal_action_error(history.apply(
+        &mut graph,
+        GraphEdit::RemoveTransition {
+            from: "2".to_string(),
+            transition: missing,
+            to: "r".to_string(),
+        },
+    ));
+    history.undo(&mut graph).unwrap().unwrap();
+    assert_eq!(original, graph_to_string(&graph));
+    assert!(history.undo(&mut graph).unwrap().is_none());
+}
+
+#[test]
+fn undo_remove_duplicated_transition() {
+    let mut graph = get_graph();
+    let transition = parse_transition("q_1 {0, _ -> R, 0, R} q_1");
+    graph
+        .append_rule_state_by_name(&"1".to_string(), transition.clone(), &"1".to_string())
+        .unwrap();
+    let original = graph_to_string(&graph);
+
+    let mut history = GraphHistory::new();
+    history
+        .apply(
+            &mut graph,
+            GraphEdit::RemoveTransition {
+                from: "1".to_string(),
+                transition,
+                to: "1".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        graph
+            .get_state_from_name(&"1".to_string())
+            .unwrap()
+            .transitions
+            .len(),
+        1
+    );
+
+    // Both copies are added back
+    history.undo(&mut graph).unwrap();
+    assert_eq!(original, graph_to_string(&graph));
+}
+
+#[test]
+fn undo_add_duplicated_transition() {
+    let mut graph = get_graph();
+    let original = graph_to_string(&graph);
+    let transitions_count = |graph: &TuringMachineGraph| {
+        graph
+            .get_state_from_name(&"i".to_string())
+            .unwrap()
+            .transitions
+            .len()
+    };
+    assert_eq!(transitions_count(&graph), 1);
+
+    let mut history = GraphHistory::new();
+    history
+        .apply(
+            &mut graph,
+            GraphEdit::AddTransition {
+                from: "i".to_string(),
+                transition: parse_transition("q_i {ç, ç -> R, ç, R} q_1"),
+                to: "1".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(transitions_count(&graph), 2);
+
+    // Only the added copy is removed
+    history.undo(&mut graph).unwrap().unwrap();
+    assert_eq!(transitions_count(&graph), 1);
+    assert_eq!(original, graph_to_string(&graph));
+
+    history.redo(&mut graph).unwrap().unwrap();
+    assert_eq!(transitions_count(&graph), 2);
+}
+
+#[test]
+fn undo_remove_state() {
+    let mut graph = get_graph();
+    graph.get_state_metadata_mut(&"1".to_string()).unwrap().doc =
+        Some("Reads the zeros".to_string());
+    let original = sorted_lines(&graph);
+    let mut history = GraphHistory::new();
+
+    history
+        .apply(
+            &mut graph,
+            GraphEdit::RemoveState {
+                name: "1".to_string(),
+            },
+        )
+        .unwrap();
+    assert!(graph.get_name_index_hashmap().get("1").is_none());
+    let removed = sorted_lines(&graph);
+
+    history.undo(&mut graph).unwrap();
+    assert_eq!(original, sorted_lines(&graph));
+    assert_eq!(
+        graph.get_state_metadata("1").unwrap().doc,
+        Some("Reads the zeros".to_string())
+    );
+
+    history.redo(&mut graph).unwrap();
+    assert_eq!(removed, sorted_lines(&graph));
+
+    // The default states cannot be removed
+    expect_illegal_action_error(history.apply(
+        &mut graph,
+        GraphEdit::RemoveState {
+            name: "i".to_string(),
+        },
+    ));
+    assert!(matches!(
+        history.apply(
+            &mut graph,
+            GraphEdit::RemoveState {
+                name: "9".to_string()
+            }
+        ),
+        Err(TuringError::UnknownStateError { .. })
+    ));
+}
+
+#[test]
+fn undo_state_edits() {
+    let mut graph = get_graph();
+    let original = graph_to_string(&graph);
+    let mut history = GraphHistory::new();
+
+    for edit in [
+        GraphEdit::RenameState {
+            name: "2".to_string(),
+            new_name: "end".to_string(),
+        },
+        GraphEdit::SetStateType {
+            name: "end".to_string(),
+            state_type: TuringStateType::Accepting,
+        },
+        GraphEdit::ReplaceTransition {
+            from: "1".to_string(),
+            transition: parse_transition("q_1 {0, _ -> R, 0, R} q_1"),
+            to: "1".to_string(),
+            new_transition: parse_transition("q_1 {0, _ -> N, _, N} q_r"),
+            new_to: "r".to_string(),
+        },
+    ] {
+        history.apply(&mut graph, edit).unwrap();
+    }
+    let edited = graph_to_string(&graph);
+    assert!(edited.contains("q_1 { 0, _ -> N, _, N } q_r;"));
+    assert!(edited.contains("@type(q_end, accepting)"));
+
+    // The type of a removed state is brought back
+    history
+        .apply(
+            &mut graph,
+            GraphEdit::RemoveState {
+                name: "end".to_string(),
+            },
+        )
+        .unwrap();
+    history.undo(&mut graph).unwrap();
+    assert_eq!(
+        graph
+            .get_state_from_name(&"end".to_string())
+            .unwrap()
+            .state_type,
+        TuringStateType::Accepting
+    );
+
+    while history.can_undo() {
+        history.undo(&mut graph).unwrap();
+    }
+    assert_eq!(original, graph_to_string(&graph));
+    while history.can_redo() {
+        history.redo(&mut graph).unwrap();
+    }
+    assert!(graph.get_name_index_hashmap().get("2").is_none());
+}
+
+#[test]
+fn apply_clears_redo() {
+    let mut graph = get_graph();
+    let mut history = GraphHistory::new();
+
+    history
+        .apply(
+            &mut graph,
+            GraphEdit::AddState {
+                name: "3".to_string(),
+            },
+        )
+        .unwrap();
+    history.undo(&mut graph).unwrap();
+    assert!(graph.get_name_index_hashmap().get("3").is_none());
+    assert!(history.can_redo());
+
+    history
+        .apply(
+            &mut graph,
+            GraphEdit::SetStateMetadata {
+                name: "2".to_string(),
+                metadata: StateMetadata {
+                    color: Some("#ff0000".to_string()),
+                    ..Default::default()
+                },
+            },
+        )
+        .unwrap();
+    assert!(!history.can_redo());
+    history.undo(&mut graph).unwrap();
+    assert!(graph.get_state_metadata("2").unwrap().is_empty());
+
+    // Adding a state that already exists changes nothing, so there is nothing to undo
+    history.clear();
+    history
+        .apply(
+            &mut graph,
+            GraphEdit::AddState {
+                name: "2".to_string(),
+            },
+        )
+        .unwrap();
+    assert!(!history.can_undo());
+}
+
+#[test]
+fn undo_replace_graph() {
+    // q_1 and q_2 are merged by the minimization
+    let mut graph = parse_turing_graph_string(
+        "q_i {ç, ç -> R, ç, R} q_1;
+        q_i {ç, ç -> R, ç, R} q_2;
+        q_1 {$, _ -> N, _, N} q_a;
+        q_2 {$, _ -> N, _, N} q_a;
+        @pos(q_2, 10, 20)"
+            .to_string(),
+    )
+    .unwrap();
+    let original = graph.clone();
+    let (minimized, _) = minimize_graph(&graph);
+    let mut history = GraphHistory::new();
+
+    // Some edits were made before the minimization
+    history
+        .apply(
+            &mut graph,
+            GraphEdit::AddState {
+                name: "3".to_string(),
+            },
+        )
+        .unwrap();
+    let edited = graph.clone();
+
+    let edit = GraphEdit::ReplaceGraph {
+        graph: Box::new(minimized.clone()),
+        description: "minimize the graph".to_string(),
+    };
+    assert_eq!(edit.to_string(), "minimize the graph");
+    history.apply(&mut graph, edit.clone()).unwrap();
+    assert_eq!(graph, minimized);
+
+    assert_eq!(history.undo(&mut graph).unwrap(), Some(edit.clone()));
+    assert_eq!(graph, edited);
+    assert_eq!(
+        graph.get_state_metadata("2"),
+        edited.get_state_metadata("2")
+    );
+    history.redo(&mut graph).unwrap();
+    assert_eq!(graph, minimized);
+
+    // The edits made before the replacement can still be undone
+    history.undo(&mut graph).unwrap();
+    history.undo(&mut graph).unwrap();
+    assert_eq!(graph, original);
+    assert!(!history.can_undo());
+
+    // Replacing a graph by itself changes nothing
+    history
+        .apply(
+            &mut graph,
+            GraphEdit::ReplaceGraph {
+                graph: Box::new(original.clone()),
+                description: "reload the graph".to_string(),
+            },
+        )
+        .unwrap();
+    assert!(!history.can_undo());
+}
+
+#[test]
+fn graph_equality_compares_targets() {
+    let graph = get_graph();
+    let mut other = get_graph();
+    assert_eq!(graph, other);
+
+    // Same transitions, but going to another state
+    other
+        .replace_transition(
+            &"2".to_string(),
+            &parse_transition("q_2 {0, _ -> R, 0, R} q_1"),
+            &"1".to_string(),
+            parse_transition("q_2 {0, _ -> R, 0, R} q_2"),
+            &"2".to_string(),
+        )
+        .unwrap();
+    assert_eq!(graph.get_states().len(), other.get_states().len());
+    assert_ne!(graph, other);
+}
+
+fn expect_illegal_action_error<T>(res: Result<T, TuringError>) {
+    match res {
+        Err(TuringError::IllegalActionError { cause: _ }) => {}
+        Err(e) => panic!("Expected an illegal action error, got {e:?}"),
+        Ok(_) => panic!("Expected an illegal action error"),
+    }
+}
@@ -0,0 +1,425 @@
+mod common;
+
+use common::{TM_XX, accepts};
+use ritm_core::{
+    turing_equivalence::words_up_to,
+    turing_errors::TuringParserError,
+    turing_jflap::{graph_to_jflap, parse_jflap_string},
+    turing_parser::{parse_turing_graph_file_path, parse_turing_graph_string},
+};
+use std::collections::HashMap;
+
+/// Accepts the words of the form a^n b^n with a single tape, going back to the blank on the left of the input after every b
+const JFF_ANBN: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?><!--Created with JFLAP 7.1.--><structure>
+	<type>turing</type>
+	<automaton>
+		<!--The list of states.-->
+		<state id="0" name="q0">
+			<x>60.0</x>
+			<y>120.0</y>
+			<initial/>
+		</state>
+		<state id="1" name="q1">
+			<x>200.0</x>
+			<y>120.0</y>
+		</state>
+		<state id="2" name="q2">
+			<x>340.0</x>
+			<y>120.0</y>
+		</state>
+		<state id="3" name="q3">
+			<x>60.0</x>
+			<y>260.0</y>
+		</state>
+		<state id="4" name="q4">
+			<x>200.0</x>
+			<y>260.0</y>
+			<final/>
+		</state>
+		<!--The list of transitions.-->
+		<transition>
+			<from>0</from>
+			<to>0</to>
+			<read>X</read>
+			<write>X</write>
+			<move>R</move>
+		</transition>
+		<transition>
+			<from>0</from>
+			<to>1</to>
+			<read>a</read>
+			<write>X</write>
+			<move>R</move>
+		</transition>
+		<transition>
+			<from>0</from>
+			<to>3</to>
+			<read>Y</read>
+			<write>Y</write>
+			<move>R</move>
+		</transition>
+		<transition>
+			<from>0</from>
+			<to>4</to>
+			<read/>
+			<write/>
+			<move>S</move>
+		</transition>
+		<transition>
+			<from>1</from>
+			<to>1</to>
+			<read>a</read>
+			<write>a</write>
+			<move>R</move>
+		</transition>
+		<transition>
+			<from>1</from>
+			<to>1</to>
+			<read>Y</read>
+			<write>Y</write>
+			<move>R</move>
+		</transition>
+		<transition>
+			<from>1</from>
+			<to>2</to>
+			<read>b</read>
+			<write>Y</write>
+			<move>L</move>
+		</transition>
+		<transition>
+			<from>2</from>
+			<to>2</to>
+			<read>a</read>
+			<write>a</write>
+			<move>L</move>
+		</transition>
+		<transition>
+			<from>2</from>
+			<to>2</to>
+			<read>X</read>
+			<write>X</write>
+			<move>L</move>
+		</transition>
+		<transition>
+			<from>2</from>
+			<to>2</to>
+			<read>Y</read>
+			<write>Y</write>
+			<move>L</move>
+		</transition>
+		<transition>
+			<from>2</from>
+			<to>0</to>
+			<read/>
+			<write/>
+			<move>R</move>
+		</transition>
+		<transition>
+			<from>3</from>
+			<to>3</to>
+			<read>Y</read>
+			<write>Y</write>
+			<move>R</move>
+		</transition>
+		<transition>
+			<from>3</from>
+			<to>4</to>
+			<read/>
+			<write/>
+			<move>S</move>
+		</transition>
+	</automaton>
+</structure>"#;
+
+/// Accepts the words of the form a^n b^n, counting the a on the second tape
+const JFF_ANBN_2_TAPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?><structure>
+	<type>turing</type>
+	<tapes>2</tapes>
+	<automaton>
+		<state id="0" name="q0"><x>0.0</x><y>0.0</y><initial/></state>
+		<state id="1" name="q1"><x>100.0</x><y>0.0</y></state>
+		<state id="2" name="q2"><x>200.0</x><y>0.0</y><final/></state>
+		<state id="3" name="q3"><x>0.0</x><y>100.0</y></state>
+		<transition>
+			<from>0</from><to>0</to>
+			<read tape="1">a</read><write tape="1">a</write><move tape="1">R</move>
+			<read tape="2"/><write tape="2">a</write><move tape="2">R</move>
+		</transition>
+		<transition>
+			<from>0</from><to>1</to>
+			<read tape="1">b</read><write tape="1">b</write><move tape="1">S</move>
+			<read tape="2"/><write tape="2"/><move tape="2">L</move>
+		</transition>
+		<transition>
+			<from>0</from><to>3</to>
+			<read tape="1"/><write tape="1"/><move tape="1">S</move>
+			<read tape="2"/><write tape="2"/><move tape="2">L</move>
+		</transition>
+		<transition>
+			<from>3</from><to>2</to>
+			<read tape="1"/><write tape="1"/><move tape="1">S</move>
+			<read tape="2"/><write tape="2"/><move tape="2">S</move>
+		</transition>
+		<transition>
+			<from>1</from><to>1</to>
+			<read tape="1">b</read><write tape="1">b</write><move tape="1">R</move>
+			<read tape="2">a</read><write tape="2">a</write><move tape="2">L</move>
+		</transition>
+		<transition>
+			<from>1</from><to>2</to>
+			<read tape="1"/><write tape="1"/><move tape="1">S</move>
+			<read tape="2"/><write tape="2"/><move tape="2">S</move>
+		</transition>
+	</automaton>
+</structure>"#;
+
+#[test]
+fn import_single_tape() {
+    let machine = parse_jflap_string(JFF_ANBN.to_string()).unwrap();
+    let graph = machine.graph;
+
+    assert_eq!(graph.get_k(), 1);
+    // The `q` prefix is removed like in the parser, the final state becomes q_a
+    for name in ["0", "1", "2", "3"] {
+        assert!(graph.get_name_index_hashmap().contains_key(name));
+    }
+    assert!(!graph.get_name_index_hashmap().contains_key("4"));
+    assert_eq!(machine.positions.get("1"), Some(&(200., 120.)));
+    assert_eq!(machine.positions.get("a"), Some(&(200., 260.)));
+
+    for word in words_up_to(&['a', 'b'], 6) {
+        assert_eq!(
+            accepts(&graph, &word),
+            is_anbn(&word),
+            "wrong result for \"{word}\""
+        );
+    }
+}
+
+#[test]
+fn import_multiple_tapes() {
+    let graph = parse_jflap_string(JFF_ANBN_2_TAPES.to_string())
+        .unwrap()
+        .graph;
+
+    assert_eq!(graph.get_k(), 2);
+    for word in words_up_to(&['a', 'b'], 6) {
+        assert_eq!(
+            accepts(&graph, &word),
+            is_anbn(&word),
+            "wrong result for \"{word}\""
+        );
+    }
+}
+
+#[test]
+fn import_unsupported() {
+    let unsupported = [
+        JFF_ANBN.replace("<type>turing</type>", "<type>fa</type>"),
+        JFF_ANBN.replace("<read>X</read>", "<read>~</read>"),
+        JFF_ANBN.replace("<read>X</read>", "<read>+</read>"),
+        JFF_ANBN.replace("<move>S</move>", "<move>U</move>"),
+        JFF_ANBN.replace(
+            "<!--The list of states.-->",
+            "<block id=\"5\" name=\"copy\"><tag>Machine5</tag></block>",
+        ),
+    ];
+    for content in unsupported {
+        match parse_jflap_string(content) {
+            Err(TuringParserError::UnsupportedConstructError { .. }) => (),
+            other => panic!("Should have returned an unsupported construct error, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn import_invalid() {
+    let invalid = [
+        // Not a XML document
+        JFF_ANBN.replace("</structure>", ""),
+        JFF_ANBN.replace("</automaton>", "</automata>"),
+        // Missing elements
+        JFF_ANBN.replace("<initial/>", ""),
+        JFF_ANBN.replace("<from>3</from>", ""),
+        JFF_ANBN_2_TAPES.replace("<read tape=\"2\">a</read>", ""),
+        JFF_ANBN.replace("<x>60.0</x>", "<x>left</x>"),
+    ];
+    for content in invalid {
+        match parse_jflap_string(content) {
+            Err(TuringParserError::ParsingError { .. }) => (),
+            other => panic!("Should have returned a parsing error, got {other:?}"),
+        }
+    }
+
+    match parse_jflap_string(JFF_ANBN.replace("<to>3</to>", "<to>8</to>")) {
+        Err(TuringParserError::EncounteredTuringError { .. }) => (),
+        other => panic!("Should have returned a turing error, got {other:?}"),
+    }
+}
+
+#[test]
+fn export_graph() {
+    let graph = parse_turing_graph_file_path("resources/binary_palindrome.tm".to_string()).unwrap();
+    let mut positions = HashMap::new();
+    positions.insert("a".to_string(), (12.5, 40.));
+    let content = graph_to_jflap(&graph, &positions);
+
+    assert!(content.contains(&format!("<tapes>{}</tapes>", graph.get_k() + 1)));
+    // The three states marking the tapes are added
+    assert_eq!(
+        content.matches("<state ").count(),
+        graph.get_states().len() + 3
+    );
+    assert_eq!(content.matches("<initial/>").count(), 1);
+    assert_eq!(content.matches("<final/>").count(), 1);
+    assert!(
+        content.contains("name=\"q_a\">\n\t\t\t<x>12.5</x>\n\t\t\t<y>40.0</y>\n\t\t\t<final/>")
+    );
+
+    let transitions: usize = graph
+        .get_states()
+        .iter()
+        .map(|state| state.transitions.len())
+        .sum();
+    let input_alphabet = graph.get_input_alphabet().len();
+    assert_eq!(
+        content.matches("<transition>").count(),
+        transitions + 3 * input_alphabet + 3
+    );
+}
+
+#[test]
+fn export_simulates_graph() {
+    let palindrome =
+        parse_turing_graph_file_path("resources/binary_palindrome.tm".to_string()).unwrap();
+    let xx = parse_turing_graph_string(TM_XX.to_string()).unwrap();
+
+    for graph in [palindrome, xx] {
+        let machine = JflapSimulator::new(&graph_to_jflap(&graph, &HashMap::new()));
+        assert_eq!(machine.tapes, graph.get_k() + 1);
+        for word in words_up_to(&['0', '1'], 4) {
+            assert_eq!(
+                machine.accepts(&word),
+                accepts(&graph, &word),
+                "wrong result for \"{word}\""
+            );
+        }
+    }
+}
+
+/// A JFLAP turing machine read from a `.jff` file, run like in JFLAP : its tapes are infinite on both sides and it accepts when a final state is reached.
+struct JflapSimulator {
+    tapes: usize,
+    initial: usize,
+    finals: Vec<usize>,
+    /// The state it goes from and to, along with what is done on each tape
+    transitions: Vec<(usize, usize, Vec<TapeAction>)>,
+}
+
+/// What a transition reads and writes on a tape (`None` being the blank symbol), and how the head moves.
+type TapeAction = (Option<char>, Option<char>, i64);
+
+impl JflapSimulator {
+    fn new(content: &str) -> Self {
+        let tapes = tag_value(content, "tapes").unwrap().parse().unwrap();
+        let mut initial = 0;
+        let mut finals = vec![];
+        for state in content.split("<state ").skip(1) {
+            let id = state.split('"').nth(1).unwrap().parse().unwrap();
+            let state = &state[..state.find("</state>").unwrap()];
+            if state.contains("<initial/>") {
+                initial = id;
+            }
+            if state.contains("<final/>") {
+                finals.push(id);
+            }
+        }
+
+        let mut transitions = vec![];
+        for transition in content.split("<transition>").skip(1) {
+            let transition = &transition[..transition.find("</transition>").unwrap()];
+            let tapes_actions = (1..=tapes)
+                .map(|tape| {
+                    let symbol = |tag: &str| {
+                        tag_value(transition, &format!("{tag} tape=\"{tape}\""))
+                            .map(|c| c.chars().next().unwrap())
+                    };
+                    let direction = match tag_value(transition, &format!("move tape=\"{tape}\"")) {
+                        Some("L") => -1,
+                        Some("R") => 1,
+                        _ => 0,
+                    };
+                    (symbol("read"), symbol("write"), direction)
+                })
+                .collect();
+            transitions.push((
+                tag_value(transition, "from").unwrap().parse().unwrap(),
+                tag_value(transition, "to").unwrap().parse().unwrap(),
+                tapes_actions,
+            ));
+        }
+        Self {
+            tapes,
+            initial,
+            finals,
+            transitions,
+        }
+    }
+
+    /// Explores every branch breadth-first, up to a given number of steps.
+    fn accepts(&self, word: &str) -> bool {
+        let mut first_tape = HashMap::new();
+        for (i, c) in word.chars().enumerate() {
+            first_tape.insert(i as i64, c);
+        }
+        let mut tapes = vec![HashMap::new(); self.tapes];
+        tapes[0] = first_tape;
+        let mut configurations = vec![(self.initial, tapes, vec![0; self.tapes])];
+
+        for _ in 0..1000 {
+            let mut next = vec![];
+            for (state, tapes, heads) in configurations {
+                if self.finals.contains(&state) {
+                    return true;
+                }
+                for (from, to, actions) in &self.transitions {
+                    let can_take = *from == state
+                        && actions.iter().enumerate().all(|(tape, (read, _, _))| {
+                            tapes[tape].get(&heads[tape]) == read.as_ref()
+                        });
+                    if !can_take {
+                        continue;
+                    }
+                    let mut tapes = tapes.clone();
+                    let mut heads = heads.clone();
+                    for (tape, (_, write, direction)) in actions.iter().enumerate() {
+                        match write {
+                            Some(c) => tapes[tape].insert(heads[tape], *c),
+                            None => tapes[tape].remove(&heads[tape]),
+                        };
+                        heads[tape] += direction;
+                    }
+                    next.push((*to, tapes, heads));
+                }
+            }
+            if next.is_empty() {
+                return false;
+            }
+            configurations = next;
+        }
+        panic!("The JFLAP machine did not stop on the word \"{word}\"");
+    }
+}
+
+/// Returns the text inside of the first `<tag ...>text</tag>` element, or `None` if the element is missing or empty.
+fn tag_value<'a>(content: &'a str, tag: &str) -> Option<&'a str> {
+    let start = content.find(&format!("<{tag}>"))? + tag.len() + 2;
+    let name = tag.split(' ').next().unwrap();
+    let end = content[start..].find(&format!("</{name}>"))?;
+    Some(&content[start..start + end])
+}
+
+fn is_anbn(word: &str) -> bool {
+    let n = word.len() / 2;
+    word == format!("{}{}", "a".repeat(n), "b".repeat(n))
+}
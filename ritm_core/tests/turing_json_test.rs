@@ -0,0 +1,274 @@
+#![cfg(feature = "serde")]
+
+use ritm_core::{
+    turing_errors::{TuringError, TuringParserError},
+    turing_graph::TuringMachineGraph,
+    turing_json::{GraphMetadata, JSON_FORMAT_VERSION, graph_to_json, parse_json_graph_string},
+    turing_parser::{graph_to_string, parse_turing_graph_file_path, parse_turing_graph_string},
+    turing_state::TuringStateType,
+    turing_test_suite::run_embedded_tests,
+};
+
+const JSON_ANBN: &str = r##"{
+  "version": 1,
+  "description": "Accepts the words of the form a^n b^n",
+  "k": 1,
+  "input_alphabet": ["a", "b"],
+  "tape_alphabets": [["a"]],
+  "states": [
+    {
+      "name": "i",
+      "type": "normal",
+      "position": [0.0, 0.0],
+      "color": "#ff0000",
+      "transitions": [
+        { "read": ["ç", "ç"], "move_read": "R", "write": [["ç", "R"]], "to": "count" }
+      ]
+    },
+    {
+      "name": "count",
+      "type": "normal",
+      "transitions": [
+        { "read": ["a", "_"], "move_read": "R", "write": [["a", "R"]], "to": "count" },
+        { "read": ["b", "_"], "move_read": "N", "write": [["_", "L"]], "to": "check" },
+        { "read": ["$", "_"], "move_read": "N", "write": [["_", "L"]], "to": "check" }
+      ]
+    },
+    {
+      "name": "check",
+      "type": "normal",
+      "transitions": [
+        { "read": ["b", "a"], "move_read": "R", "write": [["a", "L"]], "to": "check" },
+        { "read": ["$", "ç"], "move_read": "N", "write": [["ç", "N"]], "to": "a" }
+      ]
+    },
+    { "name": "a", "type": "accepting", "position": [100.0, 50.5] },
+    { "name": "r", "type": "rejecting" }
+  ]
+}"##;
+
+#[test]
+fn parse_json() {
+    let machine = parse_json_graph_string(JSON_ANBN.to_string()).unwrap();
+    let graph = machine.graph;
+
+    assert_eq!(graph.get_k(), 1);
+    assert_eq!(graph.get_states().len(), 5);
+    assert_eq!(
+        graph
+            .get_state_from_name(&"count".to_string())
+            .unwrap()
+            .transitions
+            .len(),
+        3
+    );
+    assert_eq!(graph.get_input_alphabet(), vec!['a', 'b']);
+
+    let metadata = machine.metadata;
+    assert_eq!(
+        metadata.description.as_deref(),
+        Some("Accepts the words of the form a^n b^n")
+    );
+    assert_eq!(metadata.positions.get("a"), Some(&(100., 50.5)));
+    assert_eq!(metadata.positions.len(), 2);
+    assert_eq!(
+        metadata.colors.get("i").map(String::as_str),
+        Some("#ff0000")
+    );
+    assert_eq!(metadata.input_alphabet, Some(vec!['a', 'b']));
+    assert_eq!(metadata.tape_alphabets, Some(vec![vec!['a']]));
+
+    // The positions and colors are also kept in the graph
+    let state_metadata = graph.get_state_metadata("i").unwrap();
+    assert_eq!(state_metadata.position, Some((0., 0.)));
+    assert_eq!(state_metadata.color.as_deref(), Some("#ff0000"));
+
+    // The states that are not default ones can have any type
+    let machine = parse_json_graph_string(JSON_ANBN.replace(
+        "\"name\": \"check\",\n      \"type\": \"normal\"",
+        "\"name\": \"check\",\n      \"type\": \"accepting\"",
+    ))
+    .unwrap();
+    assert_eq!(
+        machine
+            .graph
+            .get_state_from_name(&"check".to_string())
+            .unwrap()
+            .state_type,
+        TuringStateType::Accepting
+    );
+}
+
+#[test]
+fn round_trip() {
+    let graph = parse_turing_graph_file_path("resources/binary_palindrome.tm".to_string()).unwrap();
+    let mut metadata = GraphMetadata {
+        description: Some("Binary \"palindromes\"".to_string()),
+        input_alphabet: Some(vec!['0', '1']),
+        ..Default::default()
+    };
+    metadata.positions.insert("copy".to_string(), (1.5, -2.));
+    metadata
+        .colors
+        .insert("check".to_string(), "#00ff00".to_string());
+
+    let json = graph_to_json(&graph, &metadata);
+    assert!(json.contains(&format!("\"version\": {JSON_FORMAT_VERSION}")));
+    let machine = parse_json_graph_string(json).unwrap();
+    assert_eq!(machine.graph.get_states(), graph.get_states());
+    assert_eq!(machine.metadata, metadata);
+
+    // Through serde, without metadata
+    let json = serde_json::to_string(&graph).unwrap();
+    let parsed: TuringMachineGraph = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.get_states(), graph.get_states());
+    assert_eq!(
+        parsed.get_name_index_hashmap(),
+        graph.get_name_index_hashmap()
+    );
+}
+
+#[test]
+fn round_trip_embedded_tests() {
+    let graph = parse_turing_graph_file_path("resources/binary_palindrome.tm".to_string()).unwrap();
+
+    let json = graph_to_json(&graph, &GraphMetadata::default());
+    assert!(json.contains("\"tests\""));
+    let machine = parse_json_graph_string(json).unwrap();
+    let tests: Vec<(&str, bool)> = machine
+        .graph
+        .get_embedded_tests()
+        .iter()
+        .map(|test| (test.word.as_str(), test.accept))
+        .collect();
+    assert_eq!(
+        tests,
+        vec![
+            ("", true),
+            ("0110", true),
+            ("10101", true),
+            ("01", false),
+            ("0111", false)
+        ]
+    );
+    assert!(run_embedded_tests(&machine.graph).is_success());
+
+    // Their lines are the ones they get in the text format
+    let code = graph_to_string(&machine.graph);
+    let parsed = parse_turing_graph_string(code).unwrap();
+    assert_eq!(
+        parsed.get_embedded_tests(),
+        machine.graph.get_embedded_tests()
+    );
+
+    // A machine without tests does not write them
+    let json = graph_to_json(
+        &parse_turing_graph_string("q_i {ç, ç -> R, ç, R} q_a;".to_string()).unwrap(),
+        &GraphMetadata::default(),
+    );
+    assert!(!json.contains("\"tests\""));
+}
+
+#[test]
+fn parse_json_invalid() {
+    for content in [
+        JSON_ANBN.replace("\"states\"", "\"nodes\""),
+        JSON_ANBN.replace("\"R\"", "\"Right\""),
+        JSON_ANBN.replace("}\n  ]\n}", "}\n  ]"),
+    ] {
+        match parse_json_graph_string(content) {
+            Err(TuringParserError::ParsingError { .. }) => (),
+            other => panic!("Should have returned a parsing error, got {other:?}"),
+        }
+    }
+
+    match parse_json_graph_string(JSON_ANBN.replace("\"version\": 1", "\"version\": 99")) {
+        Err(TuringParserError::UnsupportedConstructError { .. }) => (),
+        other => panic!("Should have returned an unsupported construct error, got {other:?}"),
+    }
+}
+
+#[test]
+fn parse_json_turing_errors() {
+    let expect_error = |content: String| match parse_json_graph_string(content) {
+        Err(TuringParserError::EncounteredTuringError { turing_error, .. }) => turing_error,
+        other => panic!("Should have returned a turing error, got {other:?}"),
+    };
+
+    // Wrong number of tapes
+    match expect_error(
+        JSON_ANBN
+            .replace("\"k\": 1", "\"k\": 2")
+            .replace("\"tape_alphabets\": [[\"a\"]],", ""),
+    ) {
+        TuringError::IncompatibleTransitionError {
+            expected: 2,
+            received: 1,
+        } => (),
+        e => panic!("Expected an incompatible transition, got {e:?}"),
+    }
+    match expect_error(JSON_ANBN.replace("\"k\": 1", "\"k\": 0")) {
+        TuringError::IllegalActionError { .. } => (),
+        e => panic!("Expected an illegal action, got {e:?}"),
+    }
+    // Illegal replacement
+    match expect_error(JSON_ANBN.replace(
+        "\"read\": [\"ç\", \"ç\"], \"move_read\": \"R\"",
+        "\"read\": [\"ç\", \"ç\"], \"move_read\": \"L\"",
+    )) {
+        TuringError::IllegalActionError { .. } => (),
+        e => panic!("Expected an illegal action, got {e:?}"),
+    }
+    match expect_error(JSON_ANBN.replace(
+        "\"to\": \"check\" }\n      ]",
+        "\"to\": \"nowhere\" }\n      ]",
+    )) {
+        TuringError::UnknownStateError { state_name } => assert_eq!(state_name, "nowhere"),
+        e => panic!("Expected an unknown state, got {e:?}"),
+    }
+
+    for content in [
+        // The types of the default states cannot change
+        JSON_ANBN.replace(
+            "\"name\": \"i\",\n      \"type\": \"normal\"",
+            "\"name\": \"i\",\n      \"type\": \"accepting\"",
+        ),
+        JSON_ANBN.replace(
+            "\"name\": \"r\", \"type\": \"rejecting\"",
+            "\"name\": \"r\", \"type\": \"normal\"",
+        ),
+        // Duplicated state
+        JSON_ANBN.replace("\"name\": \"check\"", "\"name\": \"count\""),
+        // Invalid names and characters
+        JSON_ANBN
+            .replace("\"name\": \"check\"", "\"name\": \"check-2\"")
+            .replace("\"to\": \"check\"", "\"to\": \"check-2\""),
+        JSON_ANBN.replace("[\"a\", \"_\"]", "[\"*\", \"_\"]"),
+        // Characters outside of the declared alphabets
+        JSON_ANBN.replace(
+            "\"input_alphabet\": [\"a\", \"b\"]",
+            "\"input_alphabet\": [\"a\"]",
+        ),
+        JSON_ANBN.replace(
+            "\"tape_alphabets\": [[\"a\"]]",
+            "\"tape_alphabets\": [[\"b\"]]",
+        ),
+        JSON_ANBN.replace(
+            "\"input_alphabet\": [\"a\", \"b\"]",
+            "\"input_alphabet\": [\"a\", \"b\", \"$\"]",
+        ),
+    ] {
+        match expect_error(content) {
+            TuringError::IllegalActionError { .. } => (),
+            e => panic!("Expected an illegal action, got {e:?}"),
+        }
+    }
+
+    // Through serde
+    assert!(
+        serde_json::from_str::<TuringMachineGraph>(
+            &JSON_ANBN.replace("\"to\": \"a\"", "\"to\": \"z\"")
+        )
+        .is_err()
+    );
+}
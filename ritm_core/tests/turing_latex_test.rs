@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use ritm_core::{
+    turing_latex::{graph_to_tikz, path_to_latex_table},
+    turing_machine::{Mode, TuringMachines},
+    turing_parser::{parse_turing_graph_file_path, parse_turing_graph_string},
+};
+
+#[test]
+fn tikz_automatic_layout() {
+    let graph = parse_turing_graph_file_path("resources/binary_palindrome.tm".to_string()).unwrap();
+    let tikz = graph_to_tikz(&graph, &HashMap::new());
+
+    assert!(tikz.starts_with("\\begin{tikzpicture}["));
+    assert!(tikz.ends_with("\\end{tikzpicture}\n"));
+    assert_eq!(tikz.matches("\\node[").count(), graph.get_states().len());
+    assert!(tikz.contains("    \\node[state, initial] (s0) at (0.00, 0.00) {$q_{i}$};\n"));
+    // q_a is reached after 4 transitions, q_r is never reached
+    assert!(tikz.contains("    \\node[state, accepting] (s1) at (12.00, 0.00) {$q_{a}$};\n"));
+    assert!(tikz.contains("    \\node[state, rejecting] (s2) at (15.00, 0.00) {$q_{r}$};\n"));
+
+    // One edge per pair of states
+    assert_eq!(tikz.matches("\\path").count(), 7);
+    assert!(tikz.contains(
+        "edge[loop above] node[align=left] {\\texttt{0, \\_ $\\rightarrow$ R, 0, R} \\\\ \\texttt{1, \\_ $\\rightarrow$ R, 1, R}} (s3);\n"
+    ));
+    assert!(tikz.contains(
+        "    \\path (s5) edge node[align=left] {\\texttt{\\$, \\c{c} $\\rightarrow$ N, \\c{c}, N}} (s1);\n"
+    ));
+}
+
+#[test]
+fn tikz_positions() {
+    let graph = parse_turing_graph_string(
+        "q_i {ç, ç -> R, ç, R} q_1;
+        q_1 {0, _ -> R, 0, R} q_i;
+        q_1 {$, _ -> N, _, N} q_a;"
+            .to_string(),
+    )
+    .unwrap();
+    let mut positions = HashMap::new();
+    for (name, position) in [("i", (0., 0.)), ("a", (120., 60.)), ("r", (0., 120.))] {
+        positions.insert(name.to_string(), position);
+    }
+
+    // A missing position means the layout is computed
+    let tikz = graph_to_tikz(&graph, &positions);
+    assert!(tikz.contains("(s1) at (6.00, 0.00)"));
+
+    positions.insert("1".to_string(), (60., -60.));
+    let tikz = graph_to_tikz(&graph, &positions);
+    assert!(tikz.contains("(s1) at (2.00, -1.00)"));
+    assert!(tikz.contains("(s2) at (0.00, -2.00)"));
+    assert!(tikz.contains("(s3) at (1.00, 1.00)"));
+    // Edges in both directions are bent
+    assert!(tikz.contains("\\path (s0) edge[bend left]"));
+    assert!(tikz.contains("\\path (s3) edge[bend left]"));
+    assert!(tikz.contains("\\path (s3) edge node"));
+}
+
+#[test]
+fn latex_table() {
+    let graph = parse_turing_graph_file_path("resources/binary_palindrome.tm".to_string()).unwrap();
+    let mut tm = TuringMachines::new(graph, "01".to_string(), Mode::SaveAll).unwrap();
+    assert!(tm.get_path_to_accept(|| true).is_none());
+    tm.reset_word(&"0".to_string()).unwrap();
+    let path = tm.get_path_to_accept(|| true).unwrap();
+    let table = path_to_latex_table(&path);
+
+    assert!(table.starts_with(
+        "\\begin{tabular}{r|l|l|l}\nIteration & State & Reading tape & Tape 1 \\\\\n\\hline\n"
+    ));
+    assert!(table.ends_with("\\end{tabular}\n"));
+    assert_eq!(table.matches(" \\\\\n").count(), path.len() + 1);
+    assert!(table.contains(
+        "0 & $q_{i}$ & \\texttt{\\underline{\\c{c}}0\\$} & \\texttt{\\underline{\\c{c}}\\_} \\\\\n"
+    ));
+    assert!(table.contains(
+        "1 & $q_{copy}$ & \\texttt{\\c{c}\\underline{0}\\$} & \\texttt{\\c{c}\\underline{\\_}} \\\\\n"
+    ));
+}
@@ -0,0 +1,169 @@
+use ritm_core::{
+    turing_linter::{LintFinding, LintKind, LintLocation, LintSeverity, lint_graph},
+    turing_parser::{parse_turing_graph_file_path, parse_turing_graph_string},
+};
+
+#[test]
+fn lint_resources_without_mistakes() {
+    for name in [
+        "binary_palindrome",
+        "turing2",
+        "turing3",
+        "turing4",
+        "turing5",
+        "turing6",
+        "turing7",
+    ] {
+        let graph = parse_turing_graph_file_path(format!("resources/{name}.tm")).unwrap();
+        let findings = lint_graph(&graph);
+        assert!(
+            findings.is_empty(),
+            "{name} should not have any finding, got : {:?}",
+            findings
+        );
+    }
+}
+
+#[test]
+fn lint_unreachable_and_implicit_reject() {
+    let graph = parse_turing_graph_string(
+        "q_i {ç, ç -> R, ç, R} q_1;
+        q_1 {0, _ -> R, 0, R} q_a;
+        q_1 {1, _ -> R, 1, R} q_2;
+        q_3 {0, _ -> R, 0, R} q_a;"
+            .to_string(),
+    )
+    .unwrap();
+    let findings = lint_graph(&graph);
+
+    let q2 = *graph.get_name_index_hashmap().get("2").unwrap();
+    let q3 = *graph.get_name_index_hashmap().get("3").unwrap();
+
+    let unreachable = findings_of_kind(&findings, LintKind::UnreachableState);
+    assert_eq!(unreachable.len(), 1);
+    assert_eq!(
+        unreachable[0].location,
+        LintLocation::State { state_index: q3 }
+    );
+
+    let implicit_rejects = findings_of_kind(&findings, LintKind::ImplicitReject);
+    assert_eq!(implicit_rejects.len(), 1);
+    assert_eq!(
+        implicit_rejects[0].location,
+        LintLocation::State { state_index: q2 }
+    );
+
+    let cannot_accept = findings_of_kind(&findings, LintKind::CannotReachAccepting);
+    assert_eq!(cannot_accept.len(), 1);
+    assert_eq!(
+        cannot_accept[0].location,
+        LintLocation::State { state_index: q2 }
+    );
+    assert_eq!(cannot_accept[0].severity, LintSeverity::Warning);
+}
+
+#[test]
+fn lint_never_accepts() {
+    let graph = parse_turing_graph_string("q_i {ç, ç -> R, ç, R} q_1;".to_string()).unwrap();
+    let findings = lint_graph(&graph);
+
+    // The most severe finding comes first
+    assert_eq!(findings[0].severity, LintSeverity::Error);
+    assert_eq!(findings[0].kind, LintKind::CannotReachAccepting);
+    assert_eq!(findings[0].location, LintLocation::State { state_index: 0 });
+}
+
+#[test]
+fn lint_dead_transitions() {
+    let graph = parse_turing_graph_string(
+        "q_i {ç, ç -> R, ç, R} q_1;
+        q_i {0, ç -> R, ç, R} q_1;
+        q_1 {0, ç -> R, ç, N} q_1;
+        q_1 {1, x -> R, y, R} q_1;
+        q_1 {$, _ -> N, _, N} q_a;"
+            .to_string(),
+    )
+    .unwrap();
+    let findings = lint_graph(&graph);
+    let dead = findings_of_kind(&findings, LintKind::DeadTransition);
+
+    assert_eq!(dead.len(), 3);
+    // The reading tape is always on 'ç' in q_i
+    assert_eq!(
+        dead[0].location,
+        LintLocation::Transition {
+            state_index: 0,
+            transition_index: 1
+        }
+    );
+    // The writing head already left the first cell in q_1
+    assert_eq!(
+        dead[1].location,
+        LintLocation::Transition {
+            state_index: 3,
+            transition_index: 0
+        }
+    );
+    // 'x' is never written
+    assert_eq!(
+        dead[2].location,
+        LintLocation::Transition {
+            state_index: 3,
+            transition_index: 1
+        }
+    );
+}
+
+#[test]
+fn lint_head_can_come_back() {
+    // The writing head can come back to the first cell, reading 'ç' is fine
+    let graph = parse_turing_graph_string(
+        "q_i {ç, ç -> R, ç, R} q_1;
+        q_1 {0, _ -> N, 0, L} q_1;
+        q_1 {0, ç -> N, ç, N} q_a;"
+            .to_string(),
+    )
+    .unwrap();
+
+    assert!(findings_of_kind(&lint_graph(&graph), LintKind::DeadTransition).is_empty());
+}
+
+#[test]
+fn lint_duplicates_and_needless_writes() {
+    let graph = parse_turing_graph_string(
+        "q_i {ç, ç -> R, ç, R} q_1;
+        q_1 {0, _ -> R, 0, R} q_1;
+        q_1 {0, _ -> R, 0, R} q_1;
+        q_1 {0, _ -> R, 0, R} q_2;
+        q_2 {$, _ -> N, _, N} q_a;
+        q_1 {$, _ -> N, 1, N} q_a;"
+            .to_string(),
+    )
+    .unwrap();
+    let findings = lint_graph(&graph);
+
+    let duplicates = findings_of_kind(&findings, LintKind::DuplicateTransition);
+    assert_eq!(duplicates.len(), 1);
+    assert_eq!(
+        duplicates[0].location,
+        LintLocation::Transition {
+            state_index: 3,
+            transition_index: 1
+        }
+    );
+
+    let needless = findings_of_kind(&findings, LintKind::NeedlessWrite);
+    assert_eq!(needless.len(), 1);
+    assert_eq!(needless[0].severity, LintSeverity::Info);
+    assert_eq!(
+        needless[0].location,
+        LintLocation::Transition {
+            state_index: 3,
+            transition_index: 3
+        }
+    );
+}
+
+fn findings_of_kind(findings: &[LintFinding], kind: LintKind) -> Vec<&LintFinding> {
+    findings.iter().filter(|f| f.kind == kind).collect()
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use common::assert_same_language;
+use ritm_core::{
+    turing_minimizer::minimize_graph,
+    turing_parser::{graph_to_string, parse_turing_graph_file_path, parse_turing_graph_string},
+    turing_test_suite::run_embedded_tests,
+};
+
+#[test]
+fn minimize_merges_equivalent_states() {
+    // q_1/q_2 and q_3/q_4 do exactly the same thing
+    let graph = parse_turing_graph_string(
+        "q_i {ç, ç -> R, ç, R} q_1;
+        q_i {ç, ç -> R, ç, R} q_2;
+        q_1 {0, _ -> R, 0, R} q_1;
+        q_1 {1, _ -> R, 1, R} q_3;
+        q_2 {0, _ -> R, 0, R} q_2;
+        q_2 {1, _ -> R, 1, R} q_4;
+        q_3 {$, _ -> N, _, N} q_a;
+        q_4 {$, _ -> N, _, N} q_a;"
+            .to_string(),
+    )
+    .unwrap();
+
+    let (minimized, mapping) = minimize_graph(&graph);
+
+    assert_eq!(minimized.get_states().len(), 5);
+    assert_eq!(mapping.get("1").unwrap(), "1");
+    assert_eq!(mapping.get("2").unwrap(), "1");
+    assert_eq!(mapping.get("3").unwrap(), "3");
+    assert_eq!(mapping.get("4").unwrap(), "3");
+    assert_eq!(mapping.get("i").unwrap(), "i");
+    assert_eq!(mapping.get("a").unwrap(), "a");
+    assert_eq!(mapping.get("r").unwrap(), "r");
+
+    // The two transitions of q_i became the same one
+    assert_eq!(minimized.get_state(0).unwrap().transitions.len(), 1);
+    assert!(minimized.get_name_index_hashmap().get("2").is_none());
+
+    assert_same_language(&graph, &minimized, &['0', '1'], 6);
+}
+
+#[test]
+fn minimize_keeps_different_states() {
+    // q_1 and q_2 only differ by the state they go to
+    let graph = parse_turing_graph_string(
+        "q_i {ç, ç -> R, ç, R} q_1;
+        q_1 {0, _ -> R, 0, R} q_2;
+        q_2 {0, _ -> R, 0, R} q_1;
+        q_1 {$, _ -> N, _, N} q_a;
+        q_2 {$, _ -> N, _, N} q_r;"
+            .to_string(),
+    )
+    .unwrap();
+
+    let (minimized, mapping) = minimize_graph(&graph);
+
+    assert_eq!(minimized.get_states().len(), graph.get_states().len());
+    assert_eq!(mapping.get("1").unwrap(), "1");
+    assert_eq!(mapping.get("2").unwrap(), "2");
+}
+
+#[test]
+fn minimize_cycles() {
+    // Both cycles read any number of 0 before accepting
+    let graph = parse_turing_graph_string(
+        "q_i {ç, ç -> R, ç, R} q_1;
+        q_1 {0, _ -> R, _, N} q_2;
+        q_2 {0, _ -> R, _, N} q_1;
+        q_1 {$, _ -> N, _, N} q_a;
+        q_2 {$, _ -> N, _, N} q_a;"
+            .to_string(),
+    )
+    .unwrap();
+
+    let (minimized, mapping) = minimize_graph(&graph);
+
+    assert_eq!(minimized.get_states().len(), 4);
+    assert_eq!(mapping.get("2").unwrap(), "1");
+    assert_same_language(&graph, &minimized, &['0', '1'], 6);
+}
+
+#[test]
+fn minimize_resources() {
+    for name in [
+        "binary_palindrome",
+        "turing2",
+        "turing3",
+        "turing4",
+        "turing5",
+        "turing6",
+        "turing7",
+    ] {
+        let graph = parse_turing_graph_file_path(format!("resources/{name}.tm")).unwrap();
+        let (minimized, mapping) = minimize_graph(&graph);
+
+        assert!(minimized.get_states().len() <= graph.get_states().len());
+        assert_eq!(mapping.len(), graph.get_states().len());
+        assert_same_language(&graph, &minimized, &graph.get_input_alphabet(), 5);
+    }
+}
+
+#[test]
+fn minimize_keeps_embedded_tests() {
+    let graph = parse_turing_graph_file_path("resources/binary_palindrome.tm".to_string()).unwrap();
+    let (minimized, _) = minimize_graph(&graph);
+
+    assert_eq!(minimized.get_embedded_tests(), graph.get_embedded_tests());
+    assert!(run_embedded_tests(&minimized).is_success());
+
+    // The tests are saved along with the minimized graph
+    let saved = parse_turing_graph_string(graph_to_string(&minimized)).unwrap();
+    assert_eq!(
+        saved.get_embedded_tests().len(),
+        graph.get_embedded_tests().len()
+    );
+}
@@ -0,0 +1,131 @@
+use ritm_core::{
+    turing_errors::TuringError,
+    turing_parser::{parse_turing_graph_file_path, parse_turing_graph_string},
+    turing_profiler::{ComplexityModel, InputGenerator, ProfileOptions, profile_complexity},
+};
+
+/// Writes a `x` for every character read, going back to the start of the writing tape after each one
+const TM_QUADRATIC: &str = "q_i {ç, ç -> R, ç, R} q_write;
+q_write {0, _ -> R, x, L} q_back;
+q_back {0, x -> N, x, L | $, x -> N, x, L} q_back;
+q_back {0, ç -> N, ç, R | $, ç -> N, ç, R} q_forward;
+q_forward {0, x -> N, x, R | $, x -> N, x, R} q_forward;
+q_forward {0, _ -> N, _, N} q_write;
+q_forward {$, _ -> N, _, N} q_a;";
+
+#[test]
+fn profile_linear() {
+    let graph = parse_turing_graph_file_path("resources/binary_palindrome.tm".to_string()).unwrap();
+    let profile = profile_complexity(
+        &graph,
+        &ProfileOptions {
+            alphabet: None,
+            lengths: (0..=8).collect(),
+            generator: InputGenerator::All,
+            step_budget: 10_000,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(profile.lengths.len(), 9);
+    assert_eq!(profile.lengths[3].samples, 8);
+    assert!(profile.lengths.iter().all(|l| l.out_of_budget == 0));
+    // The input is copied on the writing tape
+    assert_eq!(profile.lengths[8].peak_tape_length, 8 + 2);
+
+    let time = profile.best_time_fit().unwrap();
+    assert_eq!(time.model, ComplexityModel::Linear);
+    assert!(time.r_squared > 0.99);
+    assert_eq!(
+        profile.best_space_fit().unwrap().model,
+        ComplexityModel::Linear
+    );
+
+    let table = profile.to_table();
+    assert!(table.starts_with("     n |  samples |"));
+    assert!(table.contains("* O(n) (R² = 1.0000"));
+    let svg = profile.to_svg();
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.ends_with("</svg>\n"));
+}
+
+#[test]
+fn profile_quadratic() {
+    let graph = parse_turing_graph_string(TM_QUADRATIC.to_string()).unwrap();
+    let profile = profile_complexity(
+        &graph,
+        &ProfileOptions {
+            alphabet: None,
+            lengths: vec![12, 2, 4, 6, 8, 10],
+            generator: InputGenerator::Random {
+                samples: 3,
+                seed: 42,
+            },
+            step_budget: 10_000,
+        },
+    )
+    .unwrap();
+
+    // The lengths are sorted
+    let ns: Vec<usize> = profile.lengths.iter().map(|l| l.n).collect();
+    assert_eq!(ns, vec![2, 4, 6, 8, 10, 12]);
+    assert_eq!(
+        profile.best_time_fit().unwrap().model,
+        ComplexityModel::Quadratic
+    );
+    assert_eq!(
+        profile.best_space_fit().unwrap().model,
+        ComplexityModel::Linear
+    );
+}
+
+#[test]
+fn profile_custom_words() {
+    let graph = parse_turing_graph_file_path("resources/binary_palindrome.tm".to_string()).unwrap();
+    let profile = profile_complexity(
+        &graph,
+        &ProfileOptions {
+            alphabet: None,
+            lengths: vec![1, 2, 3],
+            generator: InputGenerator::from_words(vec!["0".to_string(), "01".to_string()]),
+            step_budget: 3,
+        },
+    )
+    .unwrap();
+
+    // No word of length 3 was given
+    assert_eq!(profile.lengths.len(), 2);
+    assert_eq!(profile.lengths[1].samples, 1);
+    assert_eq!(profile.lengths[1].worst_steps, 3);
+    assert_eq!(profile.lengths[1].out_of_budget, 1);
+
+    let profile = profile_complexity(
+        &graph,
+        &ProfileOptions {
+            alphabet: None,
+            lengths: vec![1],
+            generator: InputGenerator::from_words(vec!["0".to_string()]),
+            step_budget: 3,
+        },
+    )
+    .unwrap();
+    assert!(profile.time_fits.is_empty());
+    assert!(profile.to_table().contains("not enough lengths"));
+}
+
+#[test]
+fn profile_invalid_alphabet() {
+    let graph = parse_turing_graph_file_path("resources/binary_palindrome.tm".to_string()).unwrap();
+    match profile_complexity(
+        &graph,
+        &ProfileOptions {
+            alphabet: Some(vec!['_']),
+            lengths: vec![1],
+            generator: InputGenerator::All,
+            step_budget: 100,
+        },
+    ) {
+        Err(TuringError::IllegalActionError { .. }) => (),
+        other => panic!("An IllegalActionError was expected, got {other:?}"),
+    }
+}
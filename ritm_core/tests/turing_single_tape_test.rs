@@ -0,0 +1,60 @@
+mod common;
+
+use common::{TM_0N1N, TM_XX, accepts, assert_same_language};
+use ritm_core::{
+    turing_parser::{parse_turing_graph_file_path, parse_turing_graph_string},
+    turing_single_tape::to_single_tape,
+};
+
+#[test]
+fn single_tape_resources() {
+    for name in [
+        "binary_palindrome",
+        "turing2",
+        "turing3",
+        "turing4",
+        "turing5",
+        "turing6",
+        "turing7",
+        "turing_test",
+    ] {
+        let graph = parse_turing_graph_file_path(format!("resources/{name}.tm")).unwrap();
+        let single = to_single_tape(&graph).unwrap();
+
+        // The resources already have a single writing tape, but it is still simulated as a track
+        assert!(single.get_states().len() > graph.get_states().len());
+        assert_same_language(&graph, &single, &graph.get_input_alphabet(), 4);
+    }
+}
+
+#[test]
+fn single_tape_multiple_tapes() {
+    let graph = parse_turing_graph_string(TM_0N1N.to_string()).unwrap();
+    assert_eq!(graph.get_k(), 2);
+    let single = to_single_tape(&graph).unwrap();
+
+    assert_eq!(single.get_k(), 1);
+    assert!(accepts(&single, "0011"));
+    assert!(!accepts(&single, "0010"));
+    assert_same_language(&graph, &single, &['0', '1'], 5);
+}
+
+#[test]
+fn single_tape_non_deterministic() {
+    let graph = parse_turing_graph_string(TM_XX.to_string()).unwrap();
+    let single = to_single_tape(&graph).unwrap();
+
+    assert!(accepts(&single, "0101"));
+    assert!(!accepts(&single, "0110"));
+    assert_same_language(&graph, &single, &['0', '1'], 4);
+}
+
+#[test]
+fn single_tape_keeps_state_names() {
+    let graph = parse_turing_graph_string(TM_0N1N.to_string()).unwrap();
+    let single = to_single_tape(&graph).unwrap();
+
+    for name in ["1", "2", "i", "a", "r"] {
+        assert!(single.get_name_index_hashmap().contains_key(name));
+    }
+}
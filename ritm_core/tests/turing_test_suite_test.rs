@@ -0,0 +1,212 @@
+use std::path::Path;
+
+use ritm_core::{
+    turing_equivalence::RunOutcome,
+    turing_errors::TuringParserError,
+    turing_parser::{parse_turing_graph_file_path, parse_turing_graph_string},
+    turing_test_suite::{
+        DEFAULT_STEP_BUDGET, Expectation, parse_test_suite_file_path, parse_test_suite_string,
+        run_embedded_tests, run_test_suite,
+    },
+};
+
+/// Loops forever on the words starting with an `a`, accepts the others
+const TM_LOOP: &str = "q_i {ç, ç -> R, ç, N} q_1;
+q_1 {a, ç -> N, ç, N} q_1;
+q_1 {b, ç -> N, ç, N | $, ç -> N, ç, N} q_a;";
+
+#[test]
+fn parse_suite() {
+    let suite = parse_test_suite_string(
+        "// A comment
+machine \"other.tm\";
+budget 50;
+
+accept \"ab\";
+reject \"\";
+loop \"a\" within 20;
+output \"01\" -> \"10\";"
+            .to_string(),
+    )
+    .unwrap();
+
+    assert_eq!(suite.machine, Some("other.tm".to_string()));
+    assert_eq!(suite.budget, 50);
+    let expectations: Vec<(&str, &Expectation, usize)> = suite
+        .cases
+        .iter()
+        .map(|case| (case.word.as_str(), &case.expectation, case.line))
+        .collect();
+    assert_eq!(
+        expectations,
+        vec![
+            ("ab", &Expectation::Accept, 5),
+            ("", &Expectation::Reject, 6),
+            ("a", &Expectation::Loop { steps: 20 }, 7),
+            ("01", &Expectation::Output("10".to_string()), 8),
+        ]
+    );
+
+    let suite = parse_test_suite_string("accept \"0\";".to_string()).unwrap();
+    assert_eq!(suite.machine, None);
+    assert_eq!(suite.budget, DEFAULT_STEP_BUDGET);
+}
+
+#[test]
+fn parse_suite_errors() {
+    match parse_test_suite_string("accept \"0\"\nreject \"1\";".to_string()) {
+        Err(TuringParserError::ParsingError {
+            line_col_pos: Some((2, 1)),
+            ..
+        }) => (),
+        other => panic!("A ParsingError was expected, got {other:?}"),
+    }
+    match parse_test_suite_string("loop \"0\";".to_string()) {
+        Err(TuringParserError::ParsingError { .. }) => (),
+        other => panic!("A ParsingError was expected, got {other:?}"),
+    }
+    match parse_test_suite_string("budget 99999999999999999999999;".to_string()) {
+        Err(TuringParserError::ParsingError { value, .. }) => {
+            assert_eq!(value, "99999999999999999999999")
+        }
+        other => panic!("A ParsingError was expected, got {other:?}"),
+    }
+    match parse_test_suite_file_path("resources/missing.tmtest".to_string()) {
+        Err(TuringParserError::FileError { .. }) => (),
+        other => panic!("A FileError was expected, got {other:?}"),
+    }
+}
+
+#[test]
+fn machine_path() {
+    let suite = parse_test_suite_string("accept \"0\";".to_string()).unwrap();
+    assert_eq!(
+        suite.machine_path(Path::new("resources/turing2.tmtest")),
+        Path::new("resources/turing2.tm")
+    );
+
+    let suite = parse_test_suite_string("machine \"../turing3.tm\";".to_string()).unwrap();
+    assert_eq!(
+        suite.machine_path(Path::new("resources/tests/turing2.tmtest")),
+        Path::new("resources/tests/../turing3.tm")
+    );
+}
+
+#[test]
+fn run_passing_suite() {
+    let graph = parse_turing_graph_string(TM_LOOP.to_string()).unwrap();
+    let suite = parse_test_suite_string(
+        "budget 100;
+accept \"\";
+accept \"b\";
+reject \"c\";
+loop \"a\" within 30;"
+            .to_string(),
+    )
+    .unwrap();
+
+    let report = run_test_suite(&graph, &suite);
+    assert!(report.is_success());
+    assert_eq!(report.passed(), 4);
+    // The looping word is only run for the steps given by its test
+    assert_eq!(report.results[3].run.as_ref().unwrap().steps, 30);
+    assert!(report.results.iter().all(|result| result.diff().is_none()));
+}
+
+#[test]
+fn run_failing_suite() {
+    let graph = parse_turing_graph_string(TM_LOOP.to_string()).unwrap();
+    let suite = parse_test_suite_string(
+        "budget 100;
+reject \"b\";
+accept \"a\";
+accept \"_\";"
+            .to_string(),
+    )
+    .unwrap();
+
+    let report = run_test_suite(&graph, &suite);
+    assert!(!report.is_success());
+    assert_eq!(report.failed(), 3);
+
+    let run = report.results[0].run.as_ref().unwrap();
+    assert_eq!(run.outcome, RunOutcome::Accepted);
+    assert_eq!(
+        report.results[1].run.as_ref().unwrap().outcome,
+        RunOutcome::OutOfBudget
+    );
+    // The word cannot be given to the machine
+    assert!(report.results[2].run.is_err());
+    assert!(
+        report.results[0]
+            .diff()
+            .unwrap()
+            .contains("- expected : reject")
+    );
+}
+
+#[test]
+fn run_output() {
+    let graph = parse_turing_graph_file_path("resources/binary_palindrome.tm".to_string()).unwrap();
+    let suite = parse_test_suite_string(
+        "output \"0110\" -> \"0110\";\noutput \"010\" -> \"011\";".to_string(),
+    )
+    .unwrap();
+
+    let report = run_test_suite(&graph, &suite);
+    assert!(report.results[0].passed);
+    assert!(!report.results[1].passed);
+    assert_eq!(
+        report.results[1].run.as_ref().unwrap().output,
+        Some("010".to_string())
+    );
+    // The caret points at the first character that differs
+    assert!(
+        report.results[1]
+            .diff()
+            .unwrap()
+            .ends_with("- \"011\"\n+ \"010\"\n     ^")
+    );
+}
+
+#[test]
+fn run_resource_suites() {
+    for name in [
+        "binary_palindrome",
+        "turing2",
+        "turing3",
+        "turing4",
+        "turing5",
+        "turing6",
+        "turing7",
+        "turing_test",
+    ] {
+        let suite_path = format!("resources/{name}.tmtest");
+        let suite = parse_test_suite_file_path(suite_path.clone()).unwrap();
+        let machine_path = suite.machine_path(Path::new(&suite_path));
+        let graph =
+            parse_turing_graph_file_path(machine_path.to_string_lossy().to_string()).unwrap();
+
+        let report = run_test_suite(&graph, &suite);
+        assert!(report.is_success(), "{name} :\n{report}");
+    }
+}
+
+#[test]
+fn run_embedded() {
+    let graph =
+        parse_turing_graph_string(format!("// @accept b\n// @reject\n// @reject c\n{TM_LOOP}"))
+            .unwrap();
+    let report = run_embedded_tests(&graph);
+    assert_eq!(report.passed(), 2);
+    assert_eq!(report.failed(), 1);
+    assert_eq!(report.results[1].case.line, 2);
+    assert_eq!(
+        report.results[1].run.as_ref().unwrap().outcome,
+        RunOutcome::Accepted
+    );
+
+    let graph = parse_turing_graph_file_path("resources/binary_palindrome.tm".to_string()).unwrap();
+    assert!(!graph.get_embedded_tests().is_empty());
+    assert!(run_embedded_tests(&graph).is_success());
+}
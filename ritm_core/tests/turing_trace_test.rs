@@ -0,0 +1,75 @@
+use ritm_core::{
+    turing_machine::{Mode, TuringExecutionSteps, TuringMachines},
+    turing_parser::{parse_turing_graph_file_path, parse_turing_graph_string},
+    turing_trace::{TraceRecord, csv_header, trace_to_csv, trace_to_json_lines},
+};
+
+fn run_palindrome(word: &str) -> Vec<TuringExecutionSteps> {
+    let graph = parse_turing_graph_file_path("resources/binary_palindrome.tm".to_string()).unwrap();
+    let mut tm = TuringMachines::new(graph, word.to_string(), Mode::SaveAll).unwrap();
+    (&mut tm).collect()
+}
+
+#[test]
+fn json_lines() {
+    let steps = run_palindrome("0");
+    let json = trace_to_json_lines(&steps);
+    let lines: Vec<&str> = json.lines().collect();
+
+    assert_eq!(lines.len(), steps.len());
+    assert_eq!(
+        lines[0],
+        "{\"iteration\":0,\"kind\":\"first\",\"previous_state\":null,\"state\":\"i\",\"transition_index\":null,\"transition\":null,\"backtracked_iteration\":null,\"reading_tape\":\"ç0$\",\"reading_head\":0,\"writing_tapes\":[\"ç_\"],\"writing_heads\":[0]}"
+    );
+    assert_eq!(
+        lines[2],
+        "{\"iteration\":2,\"kind\":\"transition\",\"previous_state\":\"copy\",\"state\":\"copy\",\"transition_index\":0,\"transition\":\"0, _ -> R, 0, R\",\"backtracked_iteration\":null,\"reading_tape\":\"ç0$\",\"reading_head\":2,\"writing_tapes\":[\"ç0_\"],\"writing_heads\":[2]}"
+    );
+    assert!(lines.last().unwrap().contains("\"state\":\"a\""));
+}
+
+#[test]
+fn csv() {
+    let steps = run_palindrome("0");
+    let csv = trace_to_csv(&steps);
+    let lines: Vec<&str> = csv.lines().collect();
+
+    assert_eq!(lines.len(), steps.len() + 1);
+    assert_eq!(lines[0], csv_header(1));
+    assert_eq!(
+        lines[0],
+        "iteration,kind,previous_state,state,transition_index,transition,backtracked_iteration,reading_tape,reading_head,tape_1,head_1"
+    );
+    assert_eq!(lines[1], "0,first,,i,,,,ç0$,0,ç_,0");
+    // The transitions contain commas, so they are quoted
+    assert_eq!(
+        lines[4],
+        "3,transition,copy,return,2,\"$, _ -> L, _, N\",,ç0$,1,ç0_,2"
+    );
+}
+
+#[test]
+fn backtracking() {
+    let graph = parse_turing_graph_string(
+        "q_i {ç, ç -> R, ç, R} q_1;
+        q_1 {a, _ -> N, _, N} q_r;
+        q_1 {a, _ -> R, _, N} q_2;
+        q_2 {$, _ -> N, _, N} q_a;"
+            .to_string(),
+    )
+    .unwrap();
+    let mut tm = TuringMachines::new(graph, "a".to_string(), Mode::SaveAll).unwrap();
+    let steps: Vec<TuringExecutionSteps> = (&mut tm).collect();
+
+    let records: Vec<TraceRecord> = steps.iter().map(TraceRecord::from_step).collect();
+    let backtrack = records
+        .iter()
+        .find(|record| record.kind == "backtrack")
+        .unwrap();
+    assert_eq!(backtrack.previous_state.as_deref(), Some("r"));
+    assert_eq!(backtrack.state, "1");
+    assert_eq!(backtrack.transition, None);
+    assert_eq!(backtrack.backtracked_iteration, Some(1));
+    assert!(backtrack.to_json().contains("\"kind\":\"backtrack\""));
+    assert_eq!(records.last().unwrap().state, "a");
+}
@@ -0,0 +1,139 @@
+use ritm_core::{
+    turing_errors::TuringError,
+    turing_graph::TuringMachineGraph,
+    turing_parser::{parse_turing_graph_file_path, parse_turing_graph_string},
+    turing_views::{ViewOptions, graph_to_diagram, graph_to_table},
+};
+
+fn get_graph() -> TuringMachineGraph {
+    parse_turing_graph_string(
+        "q_i {ç, ç -> R, ç, R} q_1;
+        q_1 {0, _ -> R, 0, R | 1, _ -> R, 1, R} q_1;
+        q_1 {$, _ -> N, _, N} q_a;"
+            .to_string(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn table() {
+    let table = graph_to_table(&get_graph(), &ViewOptions::default()).unwrap();
+    assert_eq!(
+        table,
+        "┌───────┬───────────────┬───────────────┬───────────────┬───────────────┐
+│       │ ç, ç          │ 0, _          │ 1, _          │ $, _          │
+├───────┼───────────────┼───────────────┼───────────────┼───────────────┤
+│ → q_i │ R, ç, R → q_1 │               │               │               │
+│ ✓ q_a │               │               │               │               │
+│ ✗ q_r │               │               │               │               │
+│   q_1 │               │ R, 0, R → q_1 │ R, 1, R → q_1 │ N, _, N → q_a │
+└───────┴───────────────┴───────────────┴───────────────┴───────────────┘
+"
+    );
+}
+
+#[test]
+fn table_respects_width() {
+    let graph = parse_turing_graph_file_path("resources/binary_palindrome.tm".to_string()).unwrap();
+    let full = graph_to_table(
+        &graph,
+        &ViewOptions {
+            width: 500,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    for width in [100, 60, 30, 10] {
+        let table = graph_to_table(
+            &graph,
+            &ViewOptions {
+                width,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        // The columns are split into several tables, unless a single one is already too wide
+        assert!(table.matches('┌').count() > full.matches('┌').count());
+        for line in table.lines() {
+            assert!(
+                line.chars().count() <= width.max(30),
+                "The line \"{line}\" is wider than {width}"
+            );
+        }
+        if width >= 60 {
+            assert!(table.contains("N, ç, N → q_a"));
+        }
+    }
+}
+
+#[test]
+fn diagram() {
+    let diagram = graph_to_diagram(&get_graph(), &ViewOptions::default()).unwrap();
+    assert_eq!(
+        diagram,
+        "  ┌─────┐
+→ │ q_i │─── {ç, ç -> R, ç, R} ──▶ q_1
+  └─────┘
+  ╔═════╗
+  ║ q_a ║
+  ╚═════╝
+  ┏━━━━━┓
+  ┃ q_r ┃
+  ┗━━━━━┛
+  ┌─────┐
+  │ q_1 │─┬─ {0, _ -> R, 0, R} ──▶ q_1
+  └─────┘ ├─ {1, _ -> R, 1, R} ──▶ q_1
+          └─ {$, _ -> N, _, N} ──▶ q_a
+"
+    );
+
+    // The transitions are cut, but still show the state they go to
+    let diagram = graph_to_diagram(
+        &get_graph(),
+        &ViewOptions {
+            width: 30,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert!(diagram.contains("  └─────┘ ├─ {1, _ ->… ──▶ q_1\n"));
+    for line in diagram.lines() {
+        assert!(line.chars().count() <= 30);
+    }
+}
+
+#[test]
+fn focus() {
+    let graph = parse_turing_graph_file_path("resources/binary_palindrome.tm".to_string()).unwrap();
+    let options = ViewOptions {
+        focus: Some("return".to_string()),
+        ..Default::default()
+    };
+
+    // Only the state and its neighbors are shown
+    let table = graph_to_table(&graph, &options).unwrap();
+    assert!(table.contains("q_copy") && table.contains("q_return") && table.contains("q_check"));
+    assert!(!table.contains("q_i") && !table.contains("✗ q_r"));
+
+    let diagram = graph_to_diagram(&graph, &options).unwrap();
+    assert!(diagram.contains("│ q_copy │─── {$, _ -> L, _, N} ──▶ q_return\n"));
+    assert!(diagram.contains("│ q_check │\n"));
+    assert!(!diagram.contains("q_i"));
+
+    let options = ViewOptions {
+        focus: Some("nowhere".to_string()),
+        ..Default::default()
+    };
+    for res in [
+        graph_to_table(&graph, &options),
+        graph_to_diagram(&graph, &options),
+    ] {
+        match res {
+            Err(TuringError::UnknownStateError { state_name }) => {
+                assert_eq!(state_name, "nowhere")
+            }
+            other => panic!("An unknown state error was expected, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use std::borrow::Cow;
+
+use colored::{ColoredString, Colorize};
+use ritm_core::turing_parser::{TuringTokenKind, parse_transition_string, tokenize_turing_string};
+use rustyline::{
+    Context, Helper,
+    completion::{Completer, FilenameCompleter, Pair},
+    highlight::{CmdKind, Highlighter},
+    hint::Hinter,
+    validate::{ValidationContext, ValidationResult, Validator},
+};
+
+use crate::modes::{choice_modes::ModeEvent, modify_mode::ModifyTuringMode};
+
+/// The characters separating the words that can be completed
+const WORD_SEPARATORS: [char; 7] = [' ', '\t', '{', '}', '|', ',', ';'];
+
+/// Helps typing the lines of the REPL :
+/// * The names of the commands, the names of the states of the loaded machine and the file paths are completed
+/// * The transitions are colored, and can be written over multiple lines until they end with a `;`. The ones that cannot be parsed are refused
+#[derive(Default)]
+pub struct ReplHelper {
+    files: FilenameCompleter,
+    /// The names of the commands that can be called
+    pub commands: Vec<String>,
+    /// The names of the states of the loaded machine, without their `q_` prefix
+    pub states: Vec<String>,
+    /// The names of the loaded machines
+    pub machines: Vec<String>,
+    /// Whether the line being read is a command, or the answer to a question asked by a command
+    pub reading_command: bool,
+    /// Whether the line being read is a transition
+    pub reading_transition: bool,
+}
+
+impl ReplHelper {
+    /// Returns the position where the transition written in the given line starts, if it contains one.
+    ///
+    /// When reading a command, the transition is the argument of the commands adding or removing transitions.
+    fn transition_start(&self, line: &str) -> Option<usize> {
+        if self.reading_transition {
+            return Some(0);
+        }
+        let command = line.split_whitespace().next()?;
+        let takes_transition = [
+            ModifyTuringMode::AddTransitions,
+            ModifyTuringMode::RemoveTransitions,
+        ]
+        .iter()
+        .any(|mode| mode.name() == command);
+        if self.reading_command && takes_transition {
+            return Some(line.find(command).unwrap() + command.len());
+        }
+        None
+    }
+}
+
+impl Completer for ReplHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let start = line[..pos]
+            .rfind(WORD_SEPARATORS)
+            .map(|index| index + 1)
+            .unwrap_or(0);
+        let word = &line[start..pos];
+
+        // The first word of a command is its name
+        if self.reading_command && line[..start].trim().is_empty() {
+            return Ok((start, complete_with(word, self.commands.iter().cloned())));
+        }
+        // The argument of the command switching machines is the name of a machine
+        if self.reading_command && line[..start].split_whitespace().eq(["switch"]) {
+            return Ok((start, complete_with(word, self.machines.iter().cloned())));
+        }
+        // Then the words starting like a state are states
+        if word.starts_with('q') {
+            let states = complete_with(word, self.states.iter().map(|name| format!("q_{name}")));
+            if !states.is_empty() {
+                return Ok((start, states));
+            }
+        }
+        self.files.complete(line, pos, ctx)
+    }
+}
+
+/// Keeps the candidates starting with the given word.
+fn complete_with(word: &str, candidates: impl Iterator<Item = String>) -> Vec<Pair> {
+    let mut pairs: Vec<Pair> = candidates
+        .filter(|candidate| candidate.starts_with(word))
+        .map(|candidate| Pair {
+            display: candidate.clone(),
+            replacement: candidate,
+        })
+        .collect();
+    pairs.sort_by(|a, b| a.display.cmp(&b.display));
+    pairs.dedup_by(|a, b| a.display == b.display);
+    pairs
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {
+    fn highlight<'l>(&self, line: &'l str, _pos: usize) -> Cow<'l, str> {
+        let Some(start) = self.transition_start(line) else {
+            return Cow::Borrowed(line);
+        };
+        let transition = &line[start..];
+        let mut res = line[..start].to_string();
+        let mut last_end = 0;
+        for token in tokenize_turing_string(transition) {
+            // The spaces and the comments are kept as they are
+            res.push_str(&transition[last_end..token.span.start]);
+            res.push_str(&color_token(token.kind, &transition[token.span.clone()]).to_string());
+            last_end = token.span.end;
+        }
+        res.push_str(&transition[last_end..]);
+        Cow::Owned(res)
+    }
+
+    fn highlight_char(&self, line: &str, _pos: usize, _kind: CmdKind) -> bool {
+        self.transition_start(line).is_some()
+    }
+}
+
+fn color_token(kind: TuringTokenKind, token: &str) -> ColoredString {
+    match kind {
+        TuringTokenKind::StateName => token.yellow(),
+        TuringTokenKind::Symbol => token.cyan(),
+        TuringTokenKind::Direction => token.blue().bold(),
+        TuringTokenKind::Arrow => token.purple(),
+        TuringTokenKind::Bracket => token.bold(),
+        TuringTokenKind::Separator => token.normal(),
+        TuringTokenKind::Unknown => token.red(),
+    }
+}
+
+impl Validator for ReplHelper {
+    fn validate(&self, ctx: &mut ValidationContext) -> rustyline::Result<ValidationResult> {
+        let input = ctx.input();
+        Ok(match self.transition_start(input) {
+            Some(start) => validate_transition(&input[start..]),
+            None => ValidationResult::Valid(None),
+        })
+    }
+}
+
+/// Waits for the brackets of a transition to be closed and for its `;`, before checking that it can be parsed.
+///
+/// The `;` can be left out when the transition is complete on its own.
+fn validate_transition(transition: &str) -> ValidationResult {
+    let transition = transition.trim();
+    if transition.is_empty() {
+        return ValidationResult::Valid(None);
+    }
+    let opened = transition.matches('{').count();
+    let closed = transition.matches('}').count();
+    if closed > opened {
+        return ValidationResult::Invalid(Some(
+            "\nMore brackets are closed than opened".red().to_string(),
+        ));
+    }
+    if closed < opened {
+        return ValidationResult::Incomplete;
+    }
+
+    let (statement, ended) = match transition.strip_suffix(';') {
+        Some(statement) => (statement, true),
+        None => (transition, false),
+    };
+    match parse_transition_string(statement.to_string()) {
+        Ok(_) => ValidationResult::Valid(None),
+        Err(_) if !ended => ValidationResult::Incomplete,
+        Err(e) => ValidationResult::Invalid(Some(format!("\n{}", e.to_string().red()))),
+    }
+}
+
+impl Helper for ReplHelper {}
@@ -0,0 +1,286 @@
+use std::{
+    collections::VecDeque,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use colored::Colorize;
+use rustyline::{Editor, ExternalPrinter, error::ReadlineError, history::FileHistory};
+
+use crate::{
+    completion::ReplHelper,
+    ripl_error::{RiplError, errors_reported},
+};
+
+/// A line read from a script, along with where it comes from.
+#[derive(Debug, Clone)]
+pub struct ScriptLine {
+    /// The path of the script containing this line
+    pub path: String,
+    /// The number of this line in the script, starting at 1
+    pub number: usize,
+    pub content: String,
+}
+
+/// Reads the lines given to the REPL, taking them from the arguments given along with the command,
+/// then from the scripts being run, before asking the user.
+///
+/// While a script is running, every line read is written after its prompt, and nothing is added to the history,
+/// so running the same script always gives the same output.
+pub struct ReplInput {
+    editor: Editor<ReplHelper, FileHistory>,
+    /// The arguments written after the name of the command being run and not read yet, answering its next questions
+    argument: Option<String>,
+    /// Whether the last line read was the argument of the command
+    reading_argument: bool,
+    /// The lines left to read from the scripts being run
+    lines: VecDeque<ScriptLine>,
+    /// The last line read from a script, if the last line read came from one
+    last_line: Option<ScriptLine>,
+    /// If false, the REPL stops once the scripts are over instead of asking the user
+    interactive: bool,
+    /// Whether a script was stopped because of an error
+    failed: bool,
+}
+
+impl ReplInput {
+    /// Creates the input of a REPL that asks the user when no script is running if it is *interactive*,
+    /// or that stops when the scripts are over otherwise.
+    pub fn new(interactive: bool) -> rustyline::Result<Self> {
+        let mut editor = Editor::new()?;
+        editor.set_helper(Some(ReplHelper::default()));
+        Ok(Self {
+            editor,
+            argument: None,
+            reading_argument: false,
+            lines: VecDeque::new(),
+            last_line: None,
+            interactive,
+            failed: false,
+        })
+    }
+
+    /// Reads the next line, using the given prompt if the user is asked.
+    ///
+    /// If no script is running and the REPL is not interactive, a [ReadlineError::Eof] is returned.
+    pub fn readline(&mut self, prompt: &str) -> rustyline::Result<String> {
+        match self.next_line(prompt, false) {
+            Some(line) => Ok(line),
+            None if self.interactive => self.editor.readline(prompt),
+            None => Err(ReadlineError::Eof),
+        }
+    }
+
+    /// Reads the next line, suggesting the given text (with the cursor placed between the two parts) if the user is asked.
+    pub fn readline_with_initial(
+        &mut self,
+        prompt: &str,
+        initial: (&str, &str),
+    ) -> rustyline::Result<String> {
+        self.readline_with_initial_from(prompt, initial, false)
+    }
+
+    /// Reads a command, the names of the commands being completed along with the states and the paths.
+    pub fn read_command(&mut self, prompt: &str) -> rustyline::Result<String> {
+        self.set_reading_command(true);
+        let res = self.readline(prompt);
+        self.set_reading_command(false);
+        res
+    }
+
+    /// Reads a transition, that can be written over multiple lines, suggesting the given text if the user is asked.
+    ///
+    /// Since a transition contains spaces, all the arguments left are read as the transition.
+    pub fn read_transition(&mut self, prompt: &str, initial: &str) -> rustyline::Result<String> {
+        if let Some(helper) = self.editor.helper_mut() {
+            helper.reading_transition = true;
+        }
+        let res = self.readline_with_initial_from(prompt, (initial, ""), true);
+        if let Some(helper) = self.editor.helper_mut() {
+            helper.reading_transition = false;
+        }
+        res
+    }
+
+    /// Returns a function printing the messages given by other threads above the line being typed, instead of in the middle of it.
+    pub fn external_printer(&mut self) -> Box<dyn FnMut(String) + Send> {
+        match self.editor.create_external_printer() {
+            Ok(mut printer) => Box::new(move |message| {
+                if printer.print(format!("{message}\n")).is_err() {
+                    println!("{message}");
+                }
+            }),
+            Err(_) => Box::new(|message| println!("{message}")),
+        }
+    }
+
+    /// Sets the names of the commands, of the states and of the machines suggested when a line is completed.
+    pub fn set_completions(
+        &mut self,
+        commands: Vec<String>,
+        states: Vec<String>,
+        machines: Vec<String>,
+    ) {
+        if let Some(helper) = self.editor.helper_mut() {
+            helper.commands = commands;
+            helper.states = states;
+            helper.machines = machines;
+        }
+    }
+
+    /// Gives the arguments written after the name of a command, each word being read as the answer to one of the questions it asks.
+    pub fn set_argument(&mut self, argument: String) {
+        self.argument = Some(argument);
+    }
+
+    /// Prints a question, unless it is answered by an argument given along with the command.
+    pub fn ask(&self, question: &str) {
+        if self.argument.is_none() {
+            println!("{question}");
+        }
+    }
+
+    /// Removes the arguments of the last command, returning the ones the command did not read.
+    pub fn take_argument(&mut self) -> Option<String> {
+        self.argument.take()
+    }
+
+    /// Adds a line typed by the user to the history. The lines of the scripts and the arguments are ignored.
+    pub fn add_history_entry(&mut self, line: String) -> rustyline::Result<bool> {
+        if self.is_running_script() || self.reading_argument {
+            return Ok(false);
+        }
+        self.editor.add_history_entry(line)
+    }
+
+    /// Clears the terminal, unless a script is running.
+    pub fn clear_screen(&mut self) -> rustyline::Result<()> {
+        if self.is_running_script() || !self.interactive {
+            return Ok(());
+        }
+        self.editor.clear_screen()
+    }
+
+    /// Runs the script stored at the given path, before the lines left of the scripts already running.
+    ///
+    /// Empty lines and lines starting with `//` are skipped. Colors are disabled until the scripts are over.
+    pub fn source(&mut self, current_path: &Option<PathBuf>, path: &str) -> Result<(), RiplError> {
+        let path = match current_path {
+            Some(curr_path) if Path::new(path).is_relative() => curr_path.join(path),
+            _ => PathBuf::from(path),
+        };
+        let content = fs::read_to_string(&path).map_err(|_| RiplError::FileNotExistError {
+            file_path: path.to_string_lossy().to_string(),
+        })?;
+
+        let script_path = path.to_string_lossy().to_string();
+        let script: Vec<ScriptLine> = content
+            .lines()
+            .enumerate()
+            .map(|(index, content)| ScriptLine {
+                path: script_path.clone(),
+                number: index + 1,
+                content: content.trim().to_string(),
+            })
+            .filter(|line| !line.content.is_empty() && !line.content.starts_with("//"))
+            .collect();
+        for line in script.into_iter().rev() {
+            self.lines.push_front(line);
+        }
+        colored::control::set_override(false);
+        Ok(())
+    }
+
+    /// Returns true if the last line read came from a script.
+    pub fn is_running_script(&self) -> bool {
+        self.last_line.is_some()
+    }
+
+    /// Returns true if the user is asked once the scripts are over.
+    pub fn is_interactive(&self) -> bool {
+        self.interactive
+    }
+
+    /// Returns true if a script was stopped because of an error.
+    pub fn has_failed(&self) -> bool {
+        self.failed
+    }
+
+    /// Must be called once a command is over, with the number of errors reported before it started.
+    ///
+    /// If new errors were reported while running a script, the scripts are stopped and the line that was last read is reported.
+    pub fn finish_command(&mut self, errors_before: usize) {
+        if let Some(line) = &self.last_line
+            && errors_reported() > errors_before
+        {
+            println!(
+                "{}",
+                format!(
+                    "Stopped the script \"{}\" because of an error at line {} : {}",
+                    line.path, line.number, line.content
+                )
+                .red()
+            );
+            self.lines.clear();
+            self.failed = true;
+        }
+        if self.lines.is_empty() {
+            self.last_line = None;
+            if self.interactive {
+                colored::control::unset_override();
+            }
+        }
+    }
+
+    fn set_reading_command(&mut self, reading_command: bool) {
+        if let Some(helper) = self.editor.helper_mut() {
+            helper.reading_command = reading_command;
+        }
+    }
+
+    fn readline_with_initial_from(
+        &mut self,
+        prompt: &str,
+        initial: (&str, &str),
+        whole_argument: bool,
+    ) -> rustyline::Result<String> {
+        match self.next_line(prompt, whole_argument) {
+            Some(line) => Ok(line),
+            None if self.interactive => self.editor.readline_with_initial(prompt, initial),
+            None => Err(ReadlineError::Eof),
+        }
+    }
+
+    /// Returns the next line, which is the next argument (or all of them if `whole_argument` is true) when there are some left.
+    fn next_line(&mut self, prompt: &str, whole_argument: bool) -> Option<String> {
+        self.reading_argument = self.argument.is_some();
+        if let Some(argument) = self.argument.take() {
+            if whole_argument {
+                return Some(argument);
+            }
+            return match argument.split_once(char::is_whitespace) {
+                Some((first, rest)) => {
+                    self.argument = Some(rest.trim_start().to_string());
+                    Some(first.to_string())
+                }
+                None => Some(argument),
+            };
+        }
+        match self.lines.pop_front() {
+            Some(line) => {
+                // Writes the line as if it was typed, so the output can be read like a session
+                println!("{prompt}{}", line.content);
+                let content = line.content.clone();
+                self.last_line = Some(line);
+                Some(content)
+            }
+            None => {
+                // Without a user, the last line is kept to report the scripts ending in the middle of a command
+                if self.interactive {
+                    self.last_line = None;
+                }
+                None
+            }
+        }
+    }
+}
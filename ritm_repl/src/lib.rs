@@ -1,31 +1,153 @@
 use std::{
     fmt::Debug,
-    path::PathBuf,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
     str::FromStr,
     sync::{Arc, atomic::AtomicBool},
+    time::SystemTime,
 };
 
+use colored::Colorize;
 use ritm_core::turing_machine::Mode;
-use ritm_core::{turing_graph::TuringMachineGraph, turing_machine::TuringMachines};
-use rustyline::{Editor, history::FileHistory};
+use ritm_core::{
+    turing_graph::TuringMachineGraph, turing_history::GraphHistory, turing_machine::TuringMachines,
+};
+
+use crate::{input::ReplInput, modes::choice_modes::Modes, ripl_error::RiplError};
 
-use crate::ripl_error::RiplError;
+pub mod completion;
+
+pub mod input;
 
 pub mod modes;
 
 pub mod ripl_error;
 
-pub struct DataStorage {
-    pub graph: Option<TuringMachineGraph>,
+pub mod watch;
+
+/// A machine loaded in the REPL, with its own execution.
+pub struct MachineSlot {
+    /// The name used to switch to this machine
+    pub name: String,
+    pub graph: TuringMachineGraph,
+    /// The execution of this machine, if a word was fed to it
     pub iterator: Option<TuringMachines>,
+    /// The path of the file this machine was loaded from or last saved to
+    pub save_path: Option<PathBuf>,
+    /// The modifications made to this machine, that can be undone
+    pub history: GraphHistory,
+    /// If the file of this machine is watched, the last time it was modified when it was loaded
+    pub watch: Option<SystemTime>,
+}
+
+pub struct DataStorage {
+    /// The machines loaded, in the order they were loaded
+    pub slots: Vec<MachineSlot>,
+    /// The index of the slot of the machine being used
+    pub active_slot: usize,
     pub is_running: Arc<AtomicBool>,
     pub curr_path: Option<PathBuf>,
     pub clear_after_step: bool,
     pub exec_mode: Mode,
 }
 
-pub fn query_usize(rl: &mut Editor<(), FileHistory>, query: String) -> Result<usize, RiplError> {
-    println!("{}", query);
+impl DataStorage {
+    /// Returns the slot of the machine being used, if any machine is loaded.
+    pub fn active(&self) -> Option<&MachineSlot> {
+        self.slots.get(self.active_slot)
+    }
+
+    /// Returns the slot of the machine being used, if any machine is loaded.
+    pub fn active_mut(&mut self) -> Option<&mut MachineSlot> {
+        self.slots.get_mut(self.active_slot)
+    }
+
+    /// Returns the index of the slot with the given name.
+    pub fn find_slot(&self, name: &str) -> Option<usize> {
+        self.slots.iter().position(|slot| slot.name == name)
+    }
+
+    /// Adds a machine in a new slot and uses it. The name of the slot is the name of its file if it has one.
+    ///
+    /// If this name is already used, a number is added after it.
+    pub fn add_slot(&mut self, graph: TuringMachineGraph, save_path: Option<PathBuf>) {
+        let base = save_path
+            .as_ref()
+            .and_then(|path| path.file_stem())
+            .map(|stem| {
+                stem.to_string_lossy()
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join("_")
+            })
+            .filter(|stem| !stem.is_empty())
+            .unwrap_or("tm".to_string());
+        let mut name = base.clone();
+        let mut index = 2;
+        while self.find_slot(&name).is_some() {
+            name = format!("{base}_{index}");
+            index += 1;
+        }
+        self.slots.push(MachineSlot {
+            name,
+            graph,
+            iterator: None,
+            save_path,
+            history: GraphHistory::new(),
+            watch: None,
+        });
+        self.active_slot = self.slots.len() - 1;
+    }
+
+    /// Uses the machine with the given name, and returns the mode it is used with.
+    pub fn switch_to(&mut self, name: &str) -> Result<Modes, RiplError> {
+        self.active_slot = self.find_slot(name).ok_or(RiplError::UnknownMachineError {
+            name: name.to_string(),
+        })?;
+        Ok(self.active_mode())
+    }
+
+    /// Removes the machine being used, and uses the one loaded before it, if any.
+    pub fn remove_active_slot(&mut self) {
+        if self.active_slot < self.slots.len() {
+            self.slots.remove(self.active_slot);
+        }
+        self.active_slot = self.active_slot.saturating_sub(1);
+    }
+
+    /// Returns the mode used with the machine being used : executing it if a word was fed to it, modifying it otherwise,
+    /// or creating one if none are loaded.
+    pub fn active_mode(&self) -> Modes {
+        match self.active() {
+            Some(MachineSlot {
+                iterator: Some(_), ..
+            }) => Modes::Execute,
+            Some(_) => Modes::Modify,
+            None => Modes::Start,
+        }
+    }
+}
+
+/// Returns the number of columns of the terminal.
+///
+/// On Unix, the size of the terminal the standard output is written to is asked first.
+/// When it cannot be known (like when the output is redirected), the `COLUMNS` variable is used, or 80 columns if it is not set.
+pub fn terminal_width() -> usize {
+    #[cfg(unix)]
+    if let Ok(size) = rustix::termios::tcgetwinsize(std::io::stdout())
+        && size.ws_col > 0
+    {
+        return size.ws_col as usize;
+    }
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .unwrap_or(80)
+}
+
+pub fn query_usize(rl: &mut ReplInput, query: String) -> Result<usize, RiplError> {
+    rl.ask(&query);
     loop {
         let readline = rl.readline("==> ");
         match readline {
@@ -53,14 +175,11 @@ pub fn query_usize(rl: &mut Editor<(), FileHistory>, query: String) -> Result<us
     }
 }
 
-pub fn query_prim<E: FromStr>(
-    rl: &mut Editor<(), FileHistory>,
-    query: String,
-) -> Result<E, RiplError>
+pub fn query_prim<E: FromStr>(rl: &mut ReplInput, query: String) -> Result<E, RiplError>
 where
     <E as FromStr>::Err: Debug,
 {
-    println!("{}", query);
+    rl.ask(&query);
     loop {
         let readline = rl.readline("==> ");
         match readline {
@@ -88,8 +207,8 @@ where
     }
 }
 
-pub fn query_float(rl: &mut Editor<(), FileHistory>, query: String) -> Result<f32, RiplError> {
-    println!("{}", query);
+pub fn query_float(rl: &mut ReplInput, query: String) -> Result<f32, RiplError> {
+    rl.ask(&query);
     loop {
         let readline = rl.readline("==> ");
         match readline {
@@ -117,8 +236,8 @@ pub fn query_float(rl: &mut Editor<(), FileHistory>, query: String) -> Result<f3
     }
 }
 
-pub fn query_string(rl: &mut Editor<(), FileHistory>, query: String) -> Result<String, RiplError> {
-    println!("{}", query);
+pub fn query_string(rl: &mut ReplInput, query: String) -> Result<String, RiplError> {
+    rl.ask(&query);
     loop {
         let readline = rl.readline("==> ");
         match readline {
@@ -138,3 +257,103 @@ pub fn query_string(rl: &mut Editor<(), FileHistory>, query: String) -> Result<S
         }
     }
 }
+
+/// Asks for the path of a file to write, suggesting the given name and extension in the current directory,
+/// then writes in it the content computed from the chosen path, which is returned.
+///
+/// If the file already exists, a confirmation is asked before overwritting it.
+pub fn query_write_file<F>(
+    rl: &mut ReplInput,
+    current_path: &Option<PathBuf>,
+    default_file: (&str, &str),
+    content: F,
+) -> Result<PathBuf, RiplError>
+where
+    F: Fn(&Path) -> String,
+{
+    loop {
+        rl.ask(&format!(
+            "Enter the {} of the {} to create: ",
+            "path".bold().blue(),
+            "file".bold()
+        ));
+        let readline = match current_path {
+            Some(p) => rl.readline_with_initial(
+                "==> ",
+                (
+                    p.as_path()
+                        .join(default_file.0)
+                        .to_str()
+                        .unwrap()
+                        .to_string()
+                        .as_str(),
+                    default_file.1,
+                ),
+            ),
+            None => rl.readline("==> "),
+        };
+        match readline {
+            Ok(l) => {
+                let l = l.trim().to_string();
+                if l.is_empty() {
+                    continue;
+                }
+                rl.add_history_entry(l.to_string()).unwrap();
+
+                let path = Path::new(&l);
+                // Check that no file with this name exists
+
+                if path.exists() {
+                    // If it does, ask user for confirmation before overwritting it
+                    let choice = query_string(
+                        rl,
+                        format!(
+                            "A file with this name already exists, rewrite it ? {}: ",
+                            "Y(es) or N(o)".italic().blue()
+                        ),
+                    );
+                    match choice {
+                        Ok(choice) => {
+                            let choice = choice.to_lowercase();
+                            if !choice.eq("y") && !choice.eq("yes") {
+                                continue;
+                            }
+                        }
+                        Err(e) => {
+                            return Err(e);
+                        }
+                    }
+                }
+
+                let content = content(path);
+                let file = File::create(path);
+                match file {
+                    Ok(mut f) => {
+                        if let Err(e) = f.write_all(content.as_bytes()) {
+                            return Err(RiplError::FileError {
+                                file_path: Some(e.to_string()),
+                            });
+                        }
+                    }
+                    Err(e) => {
+                        return Err(RiplError::FileError {
+                            file_path: Some(e.to_string()),
+                        });
+                    }
+                }
+
+                println!(
+                    "{}{}",
+                    "Saved the file at the location : ".green(),
+                    path.to_str().unwrap()
+                );
+                return Ok(path.to_path_buf());
+            }
+            Err(e) => {
+                return Err(RiplError::CouldNotParseStringError {
+                    value: e.to_string(),
+                });
+            }
+        }
+    }
+}
@@ -1,24 +1,61 @@
 use std::env;
 use std::fmt::Display;
+use std::path::PathBuf;
+use std::process::ExitCode;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
 use colored::Colorize;
 use ritm_repl::DataStorage;
+use ritm_repl::input::ReplInput;
 use ritm_repl::modes::choice_modes::{ModeEvent, Modes, collect_enum_values, print_help};
 use ritm_repl::modes::execute_mode::ExecuteTuringMode;
 use ritm_repl::modes::modify_mode::ModifyTuringMode;
 use ritm_repl::modes::starting_modes::{self, StartingMode};
-use ritm_repl::ripl_error::{RiplError, print_error_help};
+use ritm_repl::ripl_error::{RiplError, errors_reported, print_error_help};
+use ritm_repl::watch::{FileWatcher, reload_watched_machines, toggle_watch};
 use rustyline::error::ReadlineError;
-use rustyline::history::FileHistory;
-use rustyline::{DefaultEditor, Editor};
 use strum::IntoEnumIterator;
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mut rl = DefaultEditor::new()?;
+/// How often the watched files are checked while the REPL waits for a command
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
 
-    let args: Vec<String> = env::args().collect();
+fn main() -> Result<ExitCode, Box<dyn std::error::Error>> {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    // Read the path of the machine to load and the script to run, if any
+    let mut machine = None;
+    let mut script = None;
+    let mut watch = false;
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        if arg == "--watch" {
+            watch = true;
+        } else if arg == "--script" {
+            match args_iter.next() {
+                Some(path) => script = Some(path),
+                None => {
+                    print_error_help(RiplError::ArgsNumberError {
+                        received: 0,
+                        expected: 1,
+                    });
+                    return Ok(ExitCode::FAILURE);
+                }
+            }
+        } else if machine.is_none() {
+            machine = Some(arg);
+        } else {
+            print_error_help(RiplError::ArgsNumberError {
+                received: args.len(),
+                expected: 1,
+            });
+            return Ok(ExitCode::FAILURE);
+        }
+    }
+
+    // When a script is given, the REPL stops at the end of it instead of asking the user
+    let mut rl = ReplInput::new(script.is_none())?;
 
     // Adding a CTRL C handler
     let running = Arc::new(AtomicBool::new(true));
@@ -32,8 +69,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Creates the data storage
     let mut storage = DataStorage {
-        graph: None,
-        iterator: None,
+        slots: vec![],
+        active_slot: 0,
         is_running: running,
         curr_path: {
             match env::current_dir() {
@@ -49,36 +86,51 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     };
 
     // Choose the first mode
-    let mut curr_mode = {
-        if args.len() == 2 {
-            match starting_modes::load_tm(
-                &storage.curr_path,
-                args.get(1).expect("Gave more than one arg"),
-            ) {
-                Ok(tm) => storage.graph = Some(tm),
+    let mut curr_mode = match machine {
+        Some(path) => {
+            match starting_modes::load_tm(&storage.curr_path, path) {
+                Ok(tm) => storage.add_slot(tm, Some(PathBuf::from(path))),
                 Err(e) => {
                     print_error_help(e);
-                    return Ok(());
+                    return Ok(ExitCode::FAILURE);
                 }
             }
-
+            if watch {
+                watch_active_machine(&mut storage);
+            }
             Modes::Modify
-        } else if args.len() > 2 {
+        }
+        None if watch => {
             print_error_help(RiplError::ArgsNumberError {
-                received: args.len() - 1,
+                received: 0,
                 expected: 1,
             });
-            return Ok(());
-        } else {
-            Modes::Start
+            return Ok(ExitCode::FAILURE);
         }
+        None => Modes::Start,
     };
+
+    if let Some(path) = script
+        && let Err(e) = rl.source(&storage.curr_path, path)
+    {
+        print_error_help(e);
+        return Ok(ExitCode::FAILURE);
+    }
     // Clear screen
     rl.clear_screen().unwrap();
 
     let mut prev_mode = Modes::Execute;
 
+    // The changes of the watched files are reported while the user types, the scripts reloading them before every command
+    let watcher = rl
+        .is_interactive()
+        .then(|| FileWatcher::new(WATCH_INTERVAL, rl.external_printer()));
+
     loop {
+        if let Some(watcher) = &watcher {
+            watcher.update(&storage);
+        }
+        let errors = errors_reported();
         let status = match curr_mode {
             Modes::Start => {
                 eval_loop::<StartingMode>(&mut rl, &mut curr_mode, &mut prev_mode, &mut storage)
@@ -96,16 +148,77 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             )
             .unwrap(),
         };
+        // Stops the scripts if the command ran into an error
+        rl.finish_command(errors);
 
         if !status {
             break;
         }
     }
-    Ok(())
+    if rl.has_failed() {
+        return Ok(ExitCode::FAILURE);
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// The commands that can be used in every mode
+const DEFAULT_COMMANDS: [&str; 7] = [
+    "help", "clear", "source", "machines", "switch", "watch", "quit",
+];
+
+/// Lists the loaded machines, marking the one being used.
+fn print_machines(storage: &DataStorage) {
+    if storage.slots.is_empty() {
+        println!("{}", "No machine is loaded".blue());
+    }
+    for (index, slot) in storage.slots.iter().enumerate() {
+        let marker = if index == storage.active_slot {
+            "*"
+        } else {
+            " "
+        };
+        let state = match &slot.iterator {
+            Some(tm) => format!("executing \"{}\"", tm.get_word()),
+            None => "not executing".to_string(),
+        };
+        let path = match (&slot.save_path, slot.watch) {
+            (Some(path), Some(_)) => format!("{} (watched)", path.to_string_lossy()),
+            (Some(path), None) => path.to_string_lossy().to_string(),
+            (None, _) => "never saved".italic().to_string(),
+        };
+        println!("{marker} {} ({state}) : {path}", slot.name.bold().purple());
+    }
+}
+
+/// Starts or stops watching the file of the machine being used.
+fn watch_active_machine(storage: &mut DataStorage) {
+    let Some(slot) = storage.active_mut() else {
+        println!("{}", "No machine is loaded".blue());
+        return;
+    };
+    match toggle_watch(slot) {
+        Ok(true) => println!(
+            "{}",
+            format!(
+                "Watching the file of the machine \"{}\", it will be reloaded when it changes.",
+                slot.name.purple()
+            )
+            .green()
+        ),
+        Ok(false) => println!(
+            "{}",
+            format!(
+                "Stopped watching the file of the machine \"{}\".",
+                slot.name.purple()
+            )
+            .green()
+        ),
+        Err(e) => print_error_help(e),
+    }
 }
 
 fn eval_loop<E>(
-    rl: &mut Editor<(), FileHistory>,
+    rl: &mut ReplInput,
     current_mode: &mut Modes,
     previous_mode: &mut Modes,
     storage: &mut DataStorage,
@@ -114,6 +227,7 @@ where
     E: ModeEvent + IntoEnumIterator + Display,
 {
     let argument;
+    let mut command_argument = "";
     let mut need_help = false;
 
     // get possible commands
@@ -124,7 +238,28 @@ where
         *previous_mode = current_mode.clone();
     }
 
-    let readline = rl.readline(">> ");
+    // Suggests the names of the commands and of the states of the machine when completing the line
+    let mut names: Vec<String> = commands.iter().map(|c| c.name().to_string()).collect();
+    names.extend(DEFAULT_COMMANDS.iter().map(|name| name.to_string()));
+    let states = storage
+        .active()
+        .map(|slot| {
+            slot.graph
+                .get_states()
+                .iter()
+                .map(|s| s.name.clone())
+                .collect()
+        })
+        .unwrap_or_default();
+    let machines = storage.slots.iter().map(|slot| slot.name.clone()).collect();
+    rl.set_completions(names, states, machines);
+
+    // The name of the machine being used is shown once there are several
+    let prompt = match storage.active() {
+        Some(slot) if storage.slots.len() > 1 => format!("{} >> ", slot.name),
+        _ => ">> ".to_string(),
+    };
+    let readline = rl.read_command(&prompt);
 
     // rl.clear_screen().unwrap();
 
@@ -132,18 +267,34 @@ where
         Ok(line) => {
             let line = line.trim();
 
+            // The command must use the last version of the watched machines, an empty line only reloading them
+            reload_watched_machines(storage);
+            let stopped = *current_mode == Modes::Execute
+                && storage.active().is_some_and(|slot| slot.iterator.is_none());
             if line.is_empty() {
+                if stopped {
+                    *current_mode = storage.active_mode();
+                }
                 return Ok(true);
             }
 
             // Adds the given string to the history for convenience
             rl.add_history_entry(line.to_string())?;
 
+            if stopped {
+                println!(
+                    "{}",
+                    "The execution was stopped, so the command was not run.".yellow()
+                );
+                *current_mode = storage.active_mode();
+                return Ok(true);
+            }
+
             // Split line
-            let line_vec: Vec<&str> = line.split(" ").collect();
+            let line_vec: Vec<&str> = line.split_whitespace().collect();
 
             // if the line starts with : "h " or "help " then the user is requesting help first
-            if line.starts_with("h") || line.starts_with("help") {
+            if line_vec[0] == "h" || line_vec[0] == "help" {
                 if line_vec.len() == 1 {
                     print_help::<E>();
                     return Ok(true);
@@ -161,24 +312,54 @@ where
             } else if line.eq("cl") || line.eq("clear") {
                 rl.clear_screen().unwrap();
                 return Ok(true);
-            } else {
-                if line_vec.len() > 1 {
-                    print_error_help(RiplError::UnknownCommandError {
-                        command: line_vec.first().unwrap().to_string(),
+            } else if line.eq("machines") {
+                print_machines(storage);
+                return Ok(true);
+            } else if line.eq("watch") {
+                watch_active_machine(storage);
+                return Ok(true);
+            } else if line_vec[0] == "switch" {
+                if line_vec.len() != 2 {
+                    print_error_help(RiplError::ArgsNumberError {
+                        received: line_vec.len() - 1,
+                        expected: 1,
                     });
-                    return Ok(true);
+                } else {
+                    match storage.switch_to(line_vec[1]) {
+                        Ok(mode) => *current_mode = mode,
+                        Err(e) => print_error_help(e),
+                    }
+                }
+                return Ok(true);
+            } else if line_vec[0] == "source" {
+                // Runs the lines of the given script as if they were typed
+                let path = line["source".len()..].trim();
+                if path.is_empty() {
+                    print_error_help(RiplError::ArgsNumberError {
+                        received: 0,
+                        expected: 1,
+                    });
+                } else if let Err(e) = rl.source(&storage.curr_path, path) {
+                    print_error_help(e);
                 }
-                argument = line_vec.first().unwrap().to_string();
-            }
-
-            // Read requested nb
-            let index_res = argument.parse();
-            if index_res.is_err() {
-                print_error_help(RiplError::CouldNotParseStringIntError { value: argument });
                 return Ok(true);
+            } else {
+                // The words written after the command answer its questions
+                argument = line_vec.first().unwrap().to_string();
+                command_argument = line[argument.len()..].trim();
             }
 
-            let index = index_res.unwrap();
+            // Read the name of the command, or its index
+            let index = match commands.iter().position(|c| c.name() == argument) {
+                Some(index) => index,
+                None => match argument.parse() {
+                    Ok(index) => index,
+                    Err(_) => {
+                        print_error_help(RiplError::UnknownCommandError { command: argument });
+                        return Ok(true);
+                    }
+                },
+            };
 
             if index >= commands.len() {
                 print_error_help(RiplError::OutOfRangeIndexError { index });
@@ -189,8 +370,14 @@ where
                 // rl.clear_screen().unwrap();
                 commands.get(index).unwrap().print_help();
             } else {
+                if !command_argument.is_empty() {
+                    rl.set_argument(command_argument.to_string());
+                }
                 *current_mode = commands.get(index).unwrap().choose_option(rl, storage);
                 // println!("{:?}", current_mode);
+                if let Some(argument) = rl.take_argument() {
+                    print_error_help(RiplError::UnusedArgumentError { argument });
+                }
             }
             Ok(true)
         }
@@ -199,7 +386,10 @@ where
             Ok(false)
         }
         Err(ReadlineError::Eof) => {
-            println!("CTRL-D");
+            // Without a user, the end of the input only means the scripts are over
+            if rl.is_interactive() {
+                println!("CTRL-D");
+            }
             Ok(false)
         }
         Err(err) => {
@@ -1,13 +1,14 @@
 use colored::{ColoredString, Colorize};
-use rustyline::{Editor, history::FileHistory};
 use std::fmt::Display;
 use strum::IntoEnumIterator;
 
-use crate::DataStorage;
+use crate::{DataStorage, input::ReplInput};
 
 pub trait ModeEvent {
+    /// The name used to call this command, in place of its index
+    fn name(&self) -> &'static str;
     fn print_help(&self);
-    fn choose_option(&self, rl: &mut Editor<(), FileHistory>, storage: &mut DataStorage) -> Modes;
+    fn choose_option(&self, rl: &mut ReplInput, storage: &mut DataStorage) -> Modes;
 
     fn get_help_color(str: ColoredString) -> ColoredString;
 }
@@ -27,8 +28,9 @@ where
 {
     for (i, mode) in E::iter().enumerate() {
         println!(
-            "{}: {}",
+            "{} {}: {}",
             E::get_help_color(i.to_string().bold()),
+            E::get_help_color(format!("({})", mode.name()).normal()),
             mode.to_string().italic()
         );
     }
@@ -40,8 +42,9 @@ where
 {
     for (i, mode) in vec.iter().enumerate() {
         println!(
-            "{}: {}",
+            "{} {}: {}",
             E::get_help_color(i.to_string().bold()),
+            E::get_help_color(format!("({})", mode.name()).normal()),
             mode.to_string().italic()
         );
     }
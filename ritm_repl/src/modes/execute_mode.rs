@@ -3,15 +3,19 @@ use std::fmt::Display;
 
 use colored::{Color, ColoredString, Colorize};
 use ritm_core::{
+    turing_dot::{DotOptions, graph_to_dot},
+    turing_latex::path_to_latex_table,
     turing_machine::{Mode, TuringExecutionSteps, TuringMachines},
     turing_state::{TuringState, TuringStateType},
     turing_tape::{TuringReadingTape, TuringWritingTape},
+    turing_trace::{trace_to_csv, trace_to_json_lines},
 };
 use strum_macros::EnumIter;
 
 use crate::{
+    input::ReplInput,
     modes::choice_modes::{ModeEvent, Modes},
-    query_prim, query_string,
+    query_prim, query_string, query_write_file,
     ripl_error::{RiplError, print_error_help},
 };
 
@@ -28,6 +32,9 @@ pub enum ExecuteTuringMode {
     SetExecutionMode,
     SummaryGraph,
     SummaryExecution,
+    ExportDot,
+    ExportLatexPath,
+    ExportTrace,
     Stop,
 }
 
@@ -47,6 +54,11 @@ impl Display for ExecuteTuringMode {
                 ExecuteTuringMode::SetExecutionMode => "Sets the execution mode",
                 ExecuteTuringMode::SummaryGraph => "Print a summary of the graph",
                 ExecuteTuringMode::SummaryExecution => "Print a summary of the execution",
+                ExecuteTuringMode::ExportDot => "Export the graph as a Graphviz DOT file",
+                ExecuteTuringMode::ExportLatexPath =>
+                    "Export the path to an accepting state as a LaTeX table (can loop forever)",
+                ExecuteTuringMode::ExportTrace =>
+                    "Export every step of the execution as JSON Lines or CSV (can loop forever)",
                 ExecuteTuringMode::FakeGuessing =>
                     "Iterate over the correct path, if any (can loop forever)",
                 ExecuteTuringMode::Stop => "Stop the execution",
@@ -56,6 +68,26 @@ impl Display for ExecuteTuringMode {
 }
 
 impl ModeEvent for ExecuteTuringMode {
+    fn name(&self) -> &'static str {
+        match self {
+            ExecuteTuringMode::NextStep => "next",
+            ExecuteTuringMode::SkipSteps => "skip",
+            ExecuteTuringMode::AutoPlay => "autoplay",
+            ExecuteTuringMode::Finish => "finish",
+            ExecuteTuringMode::FakeGuessing => "guess",
+            ExecuteTuringMode::Reset => "reset",
+            ExecuteTuringMode::FeedWord => "feed",
+            ExecuteTuringMode::ToggleClearAfterStep => "toggle-clear",
+            ExecuteTuringMode::SetExecutionMode => "set-mode",
+            ExecuteTuringMode::SummaryGraph => "summary",
+            ExecuteTuringMode::SummaryExecution => "summary-execution",
+            ExecuteTuringMode::ExportDot => "export-dot",
+            ExecuteTuringMode::ExportLatexPath => "export-latex",
+            ExecuteTuringMode::ExportTrace => "export-trace",
+            ExecuteTuringMode::Stop => "stop",
+        }
+    }
+
     fn print_help(&self) {
         print!("-> ");
         println!("{}", match self {
@@ -74,16 +106,20 @@ impl ModeEvent for ExecuteTuringMode {
                                                 "Due to the nature of this call, if an infinite execution arises, stop the execution by pressing", "CTRL+C".red().bold()),
             ExecuteTuringMode::SummaryGraph => "Prints a detailed overview of the current Turing Machine".to_string(),
             ExecuteTuringMode::SummaryExecution => "Summarizes the current execution by showing some important informations, like the last iteration, the state of the memory.".to_string(),
+            ExecuteTuringMode::ExportDot => format!("Exports the current Turing Machine as a {} file, highlighting the current state and the last transition taken.", "DOT".purple()),
+            ExecuteTuringMode::ExportLatexPath => format!("{}\n{} {}", "Looks for a path leading to an accepting state, like the fake guessing does, and exports its configurations as a LaTeX table.",
+                                                "Due to the nature of this call, if an infinite execution arises, stop the execution by pressing", "CTRL+C".red().bold()),
+            ExecuteTuringMode::ExportTrace => format!("Replays the execution from the first iteration until it ends and exports one record per step, as {} or as {} if the name of the file ends with {}.\n{} {}", "JSON Lines".purple(), "CSV".purple(), ".csv".purple(),
+                                                "Due to the nature of this call, if an infinite execution arises, stop the execution by pressing", "CTRL+C".red().bold()),
             ExecuteTuringMode::Stop => "Stops the execution of this machine and goes back to the graph modification mode".to_string(),
         }.green())
     }
 
-    fn choose_option(
-        &self,
-        rl: &mut rustyline::Editor<(), rustyline::history::FileHistory>,
-        storage: &mut crate::DataStorage,
-    ) -> Modes {
-        let mut tm = storage.iterator.as_mut().unwrap();
+    fn choose_option(&self, rl: &mut ReplInput, storage: &mut crate::DataStorage) -> Modes {
+        let mut tm = storage.slots[storage.active_slot]
+            .iterator
+            .as_mut()
+            .unwrap();
         let res = match self {
             ExecuteTuringMode::NextStep => {
                 next_step(rl, tm, storage.clear_after_step);
@@ -179,7 +215,7 @@ impl ModeEvent for ExecuteTuringMode {
                 None
             }
             ExecuteTuringMode::Stop => {
-                storage.iterator = None;
+                storage.slots[storage.active_slot].iterator = None;
 
                 return Modes::Modify;
             }
@@ -215,6 +251,27 @@ impl ModeEvent for ExecuteTuringMode {
                 summarise_execution(rl, tm);
                 None
             }
+            ExecuteTuringMode::ExportDot => {
+                let graph = tm.graph_ref();
+                let mut options = DotOptions {
+                    current_state: Some(tm.get_state_pointer()),
+                    path: vec![],
+                };
+                if let Some(TuringExecutionSteps::TransitionTaken {
+                    previous_state,
+                    transition_index_taken,
+                    ..
+                }) = tm.get_last_step()
+                    && let Some(from) = graph.get_name_index_hashmap().get(&previous_state.name)
+                {
+                    options.path.push((*from, *transition_index_taken));
+                }
+                let dot = graph_to_dot(graph, &options);
+                query_write_file(rl, &storage.curr_path, ("turing_machine", ".dot"), |_| {
+                    dot.clone()
+                })
+                .err()
+            }
             ExecuteTuringMode::ToggleClearAfterStep => {
                 storage.clear_after_step = !storage.clear_after_step;
                 println!(
@@ -228,6 +285,58 @@ impl ModeEvent for ExecuteTuringMode {
                 );
                 None
             }
+            ExecuteTuringMode::ExportLatexPath => {
+                storage
+                    .is_running
+                    .store(true, std::sync::atomic::Ordering::SeqCst);
+                let res = tm.get_path_to_accept(|| {
+                    storage.is_running.load(std::sync::atomic::Ordering::SeqCst)
+                });
+                if let Some(path) = res {
+                    let table = path_to_latex_table(&path);
+                    query_write_file(rl, &storage.curr_path, ("execution", ".tex"), |_| {
+                        table.clone()
+                    })
+                    .err()
+                } else {
+                    println!(
+                        "{}",
+                        "No path found to an accepting state".to_string().cyan()
+                    );
+                    None
+                }
+            }
+            ExecuteTuringMode::ExportTrace => {
+                // The steps already taken are not kept, so the execution is replayed on a copy
+                match TuringMachines::new(
+                    tm.graph_ref().clone(),
+                    tm.get_word().clone(),
+                    tm.get_mode().clone(),
+                ) {
+                    Ok(mut replay) => {
+                        storage
+                            .is_running
+                            .store(true, std::sync::atomic::Ordering::SeqCst);
+                        let mut steps = vec![];
+                        for step in &mut replay {
+                            // Allow the user to stop the execution if it is taking too long (or infinite)
+                            if !storage.is_running.load(std::sync::atomic::Ordering::SeqCst) {
+                                break;
+                            }
+                            steps.push(step);
+                        }
+                        query_write_file(rl, &storage.curr_path, ("execution", ".jsonl"), |path| {
+                            if path.extension().is_some_and(|ext| ext == "csv") {
+                                trace_to_csv(&steps)
+                            } else {
+                                trace_to_json_lines(&steps)
+                            }
+                        })
+                        .err()
+                    }
+                    Err(e) => Some(RiplError::EncounteredTuringError { error: e }),
+                }
+            }
             ExecuteTuringMode::SetExecutionMode => match query_mode(rl) {
                 Ok(mode) => {
                     tm.set_mode(&mode);
@@ -266,13 +375,7 @@ impl ModeEvent for ExecuteTuringMode {
         str.yellow()
     }
 }
-// printf '%s\n' "0" "1" "6" "tmp" | cargo run
-
-pub fn next_step(
-    rl: &mut rustyline::Editor<(), rustyline::history::FileHistory>,
-    mut tm: &mut TuringMachines,
-    clear_after: bool,
-) -> bool {
+pub fn next_step(rl: &mut ReplInput, mut tm: &mut TuringMachines, clear_after: bool) -> bool {
     match tm.next() {
         Some(step) => {
             print_step(rl, &step, clear_after);
@@ -285,11 +388,7 @@ pub fn next_step(
     }
 }
 
-fn print_step(
-    rl: &mut rustyline::Editor<(), rustyline::history::FileHistory>,
-    st: &TuringExecutionSteps,
-    clear_after: bool,
-) {
+fn print_step(rl: &mut ReplInput, st: &TuringExecutionSteps, clear_after: bool) {
     if clear_after {
         rl.clear_screen().unwrap();
     }
@@ -419,9 +518,7 @@ fn format_tape(
     format!("{}{}", first, second).color(color)
 }
 
-fn query_mode(
-    rl: &mut rustyline::Editor<(), rustyline::history::FileHistory>,
-) -> Result<Mode, RiplError> {
+fn query_mode(rl: &mut ReplInput) -> Result<Mode, RiplError> {
     let save_all = "SaveAll";
     let stop_after = "StopAfter";
     let stop_first_reject = "StopFirstReject";
@@ -456,10 +553,7 @@ fn query_mode(
     }
 }
 
-fn summarise_execution(
-    rl: &mut rustyline::Editor<(), rustyline::history::FileHistory>,
-    tm: &TuringMachines,
-) {
+fn summarise_execution(rl: &mut ReplInput, tm: &TuringMachines) {
     // Show the last iteration
     if let Some(it) = tm.get_last_step().as_ref() {
         println!("{}", "Last iteration :".italic());
@@ -1,40 +1,68 @@
-use std::{
-    fmt::Display,
-    fs::File,
-    io::Write,
-    path::{Path, PathBuf},
-};
+use std::{collections::HashMap, fmt::Display, fs, path::PathBuf};
 
 use ritm_core::{
+    turing_dot::{DotOptions, graph_to_dot},
+    turing_enumeration::enumerate_language,
+    turing_equivalence::{RunResult, check_equivalence, run},
+    turing_errors::TuringError,
+    turing_formats::graph_to_string_by_extension,
     turing_graph::TuringMachineGraph,
-    turing_machine::TuringMachines,
+    turing_history::{GraphEdit, GraphHistory},
+    turing_latex::graph_to_tikz,
+    turing_linter::{LintSeverity, lint_graph},
+    turing_machine::{Mode, TuringExecutionSteps, TuringMachines},
+    turing_minimizer::minimize_graph,
     turing_parser::{self, parse_transition_string},
-    turing_state::TuringTransition,
+    turing_profiler::{InputGenerator, ProfileOptions, profile_complexity},
+    turing_state::{TuringStateType, TuringTransition},
+    turing_tape::TuringTape,
+    turing_test_suite::{parse_test_suite_file_path, run_test_suite},
+    turing_views::{ViewOptions, graph_to_diagram, graph_to_table},
 };
-use rustyline::{Editor, history::FileHistory};
 use strum_macros::EnumIter;
 
 use colored::{ColoredString, Colorize};
 
 use crate::{
     DataStorage,
+    input::ReplInput,
     modes::{
         choice_modes::{ModeEvent, Modes},
         execute_mode,
+        starting_modes::load_tm,
     },
-    query_string,
+    query_string, query_usize, query_write_file,
     ripl_error::{RiplError, print_error_help},
+    terminal_width,
+    watch::modification_time,
 };
 
 #[derive(EnumIter)]
 pub enum ModifyTuringMode {
     PrintSummary,
+    PrintTable,
+    PrintDiagram,
+    LintTM,
+    MinimizeTM,
+    CompareTM,
+    EnumerateLanguage,
+    RunTests,
+    ProfileTM,
     AddState,
     AddTransitions,
     RemoveTransitions,
     RemoveState,
+    RenameState,
+    SetStateType,
+    EditTransition,
+    Undo,
+    Redo,
     SaveTM,
+    ExportDot,
+    ExportTikz,
     FeedWord,
+    RunSideBySide,
+    NewTM,
     UnloadTM,
 }
 
@@ -45,12 +73,33 @@ impl Display for ModifyTuringMode {
             "{}",
             match self {
                 ModifyTuringMode::PrintSummary => "Print a summary of the Turing Machine",
+                ModifyTuringMode::PrintTable => "Print the transition table of the Turing Machine",
+                ModifyTuringMode::PrintDiagram => "Print a diagram of the Turing Machine",
+                ModifyTuringMode::LintTM => "Check the Turing Machine for likely mistakes",
+                ModifyTuringMode::MinimizeTM => "Merge the equivalent states of the Turing Machine",
+                ModifyTuringMode::CompareTM => "Compare the Turing Machine with another one",
+                ModifyTuringMode::EnumerateLanguage =>
+                    "List the words accepted by the Turing Machine",
+                ModifyTuringMode::RunTests => "Run a test suite on the Turing Machine",
+                ModifyTuringMode::ProfileTM =>
+                    "Measure the time and space used by the Turing Machine",
                 ModifyTuringMode::AddState => "Add a state",
                 ModifyTuringMode::AddTransitions => "Add one or multiple transition",
                 ModifyTuringMode::RemoveTransitions => "Remove one or multiple transition",
                 ModifyTuringMode::RemoveState => "Remove a state",
+                ModifyTuringMode::RenameState => "Rename a state",
+                ModifyTuringMode::SetStateType => "Change the type of a state",
+                ModifyTuringMode::EditTransition => "Edit a transition",
+                ModifyTuringMode::Undo => "Undo the last modification",
+                ModifyTuringMode::Redo => "Redo the last undone modification",
                 ModifyTuringMode::SaveTM => "Save this TM as a file",
+                ModifyTuringMode::ExportDot => "Export this TM as a Graphviz DOT file",
+                ModifyTuringMode::ExportTikz => "Export this TM as a LaTeX TikZ picture",
                 ModifyTuringMode::FeedWord => "Feed a word and start executing this Turing Machine",
+                ModifyTuringMode::RunSideBySide =>
+                    "Run a word on this Turing Machine and another one side by side",
+                ModifyTuringMode::NewTM =>
+                    "Create or load another Turing Machine, keeping this one",
                 ModifyTuringMode::UnloadTM => "Unload the current Turing Machine",
             }
         )
@@ -58,47 +107,157 @@ impl Display for ModifyTuringMode {
 }
 
 impl ModeEvent for ModifyTuringMode {
+    fn name(&self) -> &'static str {
+        match self {
+            ModifyTuringMode::PrintSummary => "summary",
+            ModifyTuringMode::PrintTable => "table",
+            ModifyTuringMode::PrintDiagram => "diagram",
+            ModifyTuringMode::LintTM => "lint",
+            ModifyTuringMode::MinimizeTM => "minimize",
+            ModifyTuringMode::CompareTM => "compare",
+            ModifyTuringMode::EnumerateLanguage => "enumerate",
+            ModifyTuringMode::RunTests => "test",
+            ModifyTuringMode::ProfileTM => "profile",
+            ModifyTuringMode::AddState => "add-state",
+            ModifyTuringMode::AddTransitions => "add-transition",
+            ModifyTuringMode::RemoveTransitions => "remove-transition",
+            ModifyTuringMode::RemoveState => "remove-state",
+            ModifyTuringMode::RenameState => "rename-state",
+            ModifyTuringMode::SetStateType => "set-state-type",
+            ModifyTuringMode::EditTransition => "edit-transition",
+            ModifyTuringMode::Undo => "undo",
+            ModifyTuringMode::Redo => "redo",
+            ModifyTuringMode::SaveTM => "save",
+            ModifyTuringMode::ExportDot => "export-dot",
+            ModifyTuringMode::ExportTikz => "export-tikz",
+            ModifyTuringMode::FeedWord => "feed",
+            ModifyTuringMode::RunSideBySide => "side-by-side",
+            ModifyTuringMode::NewTM => "new",
+            ModifyTuringMode::UnloadTM => "unload",
+        }
+    }
+
     fn print_help(&self) {
         let tm_it_bold = "Turing Machine".italic().bold();
         print!("-> ");
         println!("{}", match self {
                 ModifyTuringMode::PrintSummary => format!("Prints a detailed overview of the current {tm_it_bold}"),
+                ModifyTuringMode::PrintTable => format!("Prints the {} of the current {tm_it_bold}, with a row for each state and a column for each read tuple. Giving a state after the command, like {}, only shows this state and its {}", "transition table".purple(), "table q_1".purple(), "neighbors".purple()),
+                ModifyTuringMode::PrintDiagram => format!("Draws the {} of the current {tm_it_bold} and the {} leaving them. Giving a state after the command, like {}, only draws this state and its {}", "states".purple(), "transitions".purple(), "diagram q_1".purple(), "neighbors".purple()),
+                ModifyTuringMode::LintTM => format!("Looks for likely {} in the current {tm_it_bold} (unreachable states, transitions that can never be taken, ...)", "mistakes".purple()),
+                ModifyTuringMode::MinimizeTM => format!("Merges all the {} of the current {tm_it_bold} that behave the same way", "states".purple()),
+                ModifyTuringMode::CompareTM => format!("Runs the current {tm_it_bold} and the one stored in a {} on every word up to a given length, and shows the {} where they disagree", "file".purple(), "words".purple()),
+                ModifyTuringMode::EnumerateLanguage => format!("Runs the current {tm_it_bold} on every word up to a given length, and lists the {}, {} and {} words", "accepted".purple(), "rejected".purple(), "undecided".purple()),
+                ModifyTuringMode::RunTests => format!("Runs the tests of a {} file on the current {tm_it_bold}, and shows the {} ones along with what was expected", ".tmtest".purple(), "failing".purple()),
+                ModifyTuringMode::ProfileTM => format!("Runs the current {tm_it_bold} on words of increasing length, then compares the {} and the {} it used to the {}, {} and {} models", "steps".purple(), "tape length".purple(), "O(n)".purple(), "O(n log n)".purple(), "O(n²)".purple()),
                 ModifyTuringMode::AddState => format!("Adds a {} to the current {tm_it_bold}", "state".purple()),
                 ModifyTuringMode::AddTransitions => format!("Adds one or multiple {} to the current {tm_it_bold}", "transitions".purple()),
                 ModifyTuringMode::RemoveTransitions => format!("Removes one or multiple {} from the current {tm_it_bold}", "transitions".purple()),
                 ModifyTuringMode::RemoveState => format!("Removes a {} from the current {tm_it_bold}", "state".purple()),
-                ModifyTuringMode::SaveTM => format!("Saves the current {tm_it_bold} as a file"),
+                ModifyTuringMode::RenameState => format!("Renames a {} of the current {tm_it_bold}, keeping its transitions", "state".purple()),
+                ModifyTuringMode::SetStateType => format!("Makes a {} of the current {tm_it_bold} {}, {} or {}", "state".purple(), "normal".purple(), "accepting".purple(), "rejecting".purple()),
+                ModifyTuringMode::EditTransition => format!("Replaces a {} of the current {tm_it_bold} by an edited version of it, keeping its place among the transitions of its state", "transition".purple()),
+                ModifyTuringMode::Undo => format!("Reverts the last change made to the {} or the {} of the current {tm_it_bold}", "states".purple(), "transitions".purple()),
+                ModifyTuringMode::Redo => format!("Applies again the last {} that was undone, as long as no other one was made since", "modification".purple()),
+                ModifyTuringMode::SaveTM => format!("Saves the current {tm_it_bold} as a file, using the JSON format if its name ends with {} or the JFLAP format if it ends with {}", ".json".purple(), ".jff".purple()),
+                ModifyTuringMode::ExportDot => format!("Exports the current {tm_it_bold} as a {} file, that can be turned into a picture by Graphviz", "DOT".purple()),
+                ModifyTuringMode::ExportTikz => format!("Exports the current {tm_it_bold} as a {} picture, drawn with the {} library", "TikZ".purple(), "automata".purple()),
                 ModifyTuringMode::FeedWord => format!("Feeds a word to the {tm_it_bold} and starts executing it"),
-                ModifyTuringMode::UnloadTM => "Unloads the current Turing Machine and go back to the turing machine creation phase".to_string()
+                ModifyTuringMode::RunSideBySide => format!("Runs a {} on the current {tm_it_bold} and on another loaded one, and shows their {} next to each other", "word".purple(), "steps".purple()),
+                ModifyTuringMode::NewTM => format!("Goes back to the creation phase to create or load another {tm_it_bold}. The current one stays loaded, and can be used again with the {} command", "switch".purple()),
+                ModifyTuringMode::UnloadTM => "Unloads the current Turing Machine and uses the one loaded before it, or goes back to the turing machine creation phase if there are none".to_string()
             }.green())
     }
 
-    fn choose_option(&self, rl: &mut Editor<(), FileHistory>, storage: &mut DataStorage) -> Modes {
-        let tm = storage.graph.as_mut().unwrap();
+    fn choose_option(&self, rl: &mut ReplInput, storage: &mut DataStorage) -> Modes {
+        let slot = &mut storage.slots[storage.active_slot];
+        let tm = &mut slot.graph;
         match self {
             ModifyTuringMode::PrintSummary => {
                 println!("{}", tm.to_string().blue());
             }
+            ModifyTuringMode::PrintTable => {
+                if let Err(e) = print_view(rl, tm, graph_to_table) {
+                    print_error_help(e);
+                }
+            }
+            ModifyTuringMode::PrintDiagram => {
+                if let Err(e) = print_view(rl, tm, graph_to_diagram) {
+                    print_error_help(e);
+                }
+            }
+            ModifyTuringMode::LintTM => {
+                print_lint_findings(tm);
+            }
+            ModifyTuringMode::MinimizeTM => {
+                let (minimized, mapping) = minimize_graph(tm);
+                let mut merged: Vec<(&String, &String)> =
+                    mapping.iter().filter(|(old, new)| old != new).collect();
+                merged.sort();
+                if merged.is_empty() {
+                    println!("{}", "No equivalent states were found.".green());
+                } else {
+                    for (old, new) in merged {
+                        println!(
+                            "{}",
+                            format!("Merged \'q_{}\' into \'q_{}\'", old.yellow(), new.yellow())
+                                .green()
+                        );
+                    }
+                    let edit = GraphEdit::ReplaceGraph {
+                        graph: Box::new(minimized),
+                        description: "minimize the graph".to_string(),
+                    };
+                    if let Err(e) = slot.history.apply(tm, edit) {
+                        print_error_help(RiplError::EncounteredTuringError { error: e });
+                    }
+                }
+            }
+            ModifyTuringMode::CompareTM => {
+                if let Err(e) = compare_tm(rl, tm, &storage.curr_path) {
+                    print_error_help(e);
+                }
+            }
+            ModifyTuringMode::EnumerateLanguage => {
+                if let Err(e) = enumerate_words(rl, tm) {
+                    print_error_help(e);
+                }
+            }
+            ModifyTuringMode::RunTests => {
+                if let Err(e) = run_tests(rl, tm, &storage.curr_path) {
+                    print_error_help(e);
+                }
+            }
+            ModifyTuringMode::ProfileTM => {
+                if let Err(e) = profile_tm(rl, tm, &storage.curr_path) {
+                    print_error_help(e);
+                }
+            }
             ModifyTuringMode::AddState => {
                 let res = get_state_name(rl);
                 if let Err(e) = res {
                     print_error_help(e);
                 } else {
-                    let name = &res.unwrap();
-                    tm.add_state(name);
-                    println!(
-                        "{}",
-                        format!("Successfully added the state \'q_{}\'.", name.yellow()).green()
-                    )
+                    let name = res.unwrap();
+                    let edit = GraphEdit::AddState { name: name.clone() };
+                    if let Err(e) = slot.history.apply(tm, edit) {
+                        print_error_help(RiplError::EncounteredTuringError { error: e });
+                    } else {
+                        println!(
+                            "{}",
+                            format!("Successfully added the state \'q_{}\'.", name.yellow())
+                                .green()
+                        )
+                    }
                 }
             }
             ModifyTuringMode::AddTransitions => {
-                if let Err(e) = add_transition(rl, tm) {
+                if let Err(e) = add_transition(rl, tm, &mut slot.history) {
                     print_error_help(e);
                 }
             }
             ModifyTuringMode::RemoveTransitions => {
-                if let Err(e) = remove_transition(rl, tm) {
+                if let Err(e) = remove_transition(rl, tm, &mut slot.history) {
                     print_error_help(e);
                 }
             }
@@ -107,16 +266,80 @@ impl ModeEvent for ModifyTuringMode {
                 if let Err(e) = res {
                     print_error_help(e);
                 } else {
-                    let name = &res.unwrap();
-                    if let Err(e) = tm.remove_state_with_name(name) {
+                    let name = res.unwrap();
+                    let edit = GraphEdit::RemoveState { name: name.clone() };
+                    if let Err(e) = slot.history.apply(tm, edit) {
                         print_error_help(RiplError::EncounteredTuringError { error: e });
                     } else {
                         println!("{}", format!("Successfully removed the state \'q_{}\' and all related transitions.", name.yellow()).green())
                     }
                 }
             }
+            ModifyTuringMode::RenameState => {
+                if let Err(e) = rename_state(rl, tm, &mut slot.history) {
+                    print_error_help(e);
+                }
+            }
+            ModifyTuringMode::SetStateType => {
+                if let Err(e) = set_state_type(rl, tm, &mut slot.history) {
+                    print_error_help(e);
+                }
+            }
+            ModifyTuringMode::EditTransition => {
+                if let Err(e) = edit_transition(rl, tm, &mut slot.history) {
+                    print_error_help(e);
+                }
+            }
+            ModifyTuringMode::Undo => match slot.history.undo(tm) {
+                Ok(Some(edit)) => println!(
+                    "{}{}",
+                    "Undid the modification : ".green(),
+                    edit.to_string().yellow()
+                ),
+                Ok(None) => println!("{}", "There is nothing to undo.".yellow()),
+                Err(e) => print_error_help(RiplError::EncounteredTuringError { error: e }),
+            },
+            ModifyTuringMode::Redo => match slot.history.redo(tm) {
+                Ok(Some(edit)) => println!(
+                    "{}{}",
+                    "Redid the modification : ".green(),
+                    edit.to_string().yellow()
+                ),
+                Ok(None) => println!("{}", "There is nothing to redo.".yellow()),
+                Err(e) => print_error_help(RiplError::EncounteredTuringError { error: e }),
+            },
             ModifyTuringMode::SaveTM => {
-                if let Err(e) = save_tm(rl, tm, &storage.curr_path) {
+                match save_tm(rl, tm, &slot.save_path, &storage.curr_path) {
+                    Ok(Some(path)) => {
+                        // Saving the machine must not reload it
+                        if slot.watch.is_some()
+                            && let Some(time) = modification_time(&path)
+                        {
+                            slot.watch = Some(time);
+                        }
+                        slot.save_path = Some(path);
+                    }
+                    Ok(None) => {}
+                    Err(e) => print_error_help(e),
+                }
+            }
+            ModifyTuringMode::ExportDot => {
+                let dot = graph_to_dot(tm, &DotOptions::default());
+                if let Err(e) =
+                    query_write_file(rl, &storage.curr_path, ("turing_machine", ".dot"), |_| {
+                        dot.clone()
+                    })
+                {
+                    print_error_help(e);
+                }
+            }
+            ModifyTuringMode::ExportTikz => {
+                let tikz = graph_to_tikz(tm, &HashMap::new());
+                if let Err(e) =
+                    query_write_file(rl, &storage.curr_path, ("turing_machine", ".tex"), |_| {
+                        tikz.clone()
+                    })
+                {
                     print_error_help(e);
                 }
             }
@@ -133,16 +356,22 @@ impl ModeEvent for ModifyTuringMode {
                     if let Err(e) = res {
                         print_error_help(RiplError::EncounteredTuringError { error: e });
                     } else {
-                        storage.iterator = Some(res.unwrap());
+                        slot.iterator = Some(res.unwrap());
                         execute_mode::next_step(
                             rl,
-                            storage.iterator.as_mut().unwrap(),
+                            slot.iterator.as_mut().unwrap(),
                             storage.clear_after_step,
                         );
                         return Modes::Execute;
                     }
                 }
             }
+            ModifyTuringMode::RunSideBySide => {
+                if let Err(e) = run_side_by_side(rl, storage) {
+                    print_error_help(e);
+                }
+            }
+            ModifyTuringMode::NewTM => return Modes::Start,
             ModifyTuringMode::UnloadTM => {
                 // If it does, ask user for confirmation before deleting the current graph it
                 let choice = query_string(
@@ -156,8 +385,8 @@ impl ModeEvent for ModifyTuringMode {
                     Ok(choice) => {
                         let choice = choice.to_lowercase();
                         if choice.eq("y") || choice.eq("yes") {
-                            storage.graph = None;
-                            return Modes::Start;
+                            storage.remove_active_slot();
+                            return storage.active_mode();
                         }
                     }
                     Err(e) => {
@@ -174,26 +403,409 @@ impl ModeEvent for ModifyTuringMode {
     }
 }
 
-fn get_state_name(rl: &mut Editor<(), FileHistory>) -> Result<String, RiplError> {
-    let name_res = query_string(rl, format!("Enter the {} of the state: ", "name".blue()));
-
-    match name_res {
-        Ok(name) => {
-            if name.starts_with("q_") {
-                Ok(name.strip_prefix("q_").unwrap().to_string())
-            } else if name.starts_with("q") {
-                Ok(name.strip_prefix("q").unwrap().to_string())
-            } else {
-                Ok(name)
+/// Prints a view of the machine fitting in the terminal, focused on the state given after the command if there is one.
+fn print_view(
+    rl: &mut ReplInput,
+    tm: &TuringMachineGraph,
+    view: fn(&TuringMachineGraph, &ViewOptions) -> Result<String, TuringError>,
+) -> Result<(), RiplError> {
+    let options = ViewOptions {
+        width: terminal_width(),
+        focus: rl.take_argument().map(strip_state_prefix),
+    };
+    match view(tm, &options) {
+        Ok(res) => {
+            print!("{}", res.blue());
+            Ok(())
+        }
+        Err(e) => Err(RiplError::EncounteredTuringError { error: e }),
+    }
+}
+
+fn print_lint_findings(tm: &TuringMachineGraph) {
+    let findings = lint_graph(tm);
+    if findings.is_empty() {
+        println!("{}", "No likely mistake was found in this machine.".green());
+        return;
+    }
+    for finding in findings {
+        let line = finding.to_string();
+        println!(
+            "{}",
+            match finding.severity {
+                LintSeverity::Error => line.red(),
+                LintSeverity::Warning => line.yellow(),
+                LintSeverity::Info => line.blue(),
             }
+        );
+    }
+}
+
+/// The maximum number of words where the machines disagree that are printed
+const MAX_COUNTEREXAMPLES_SHOWN: usize = 10;
+
+fn compare_tm(
+    rl: &mut ReplInput,
+    tm: &TuringMachineGraph,
+    current_path: &Option<PathBuf>,
+) -> Result<(), RiplError> {
+    let path = query_string(
+        rl,
+        format!(
+            "Enter the {} of the Turing machine to compare with:",
+            "path".blue()
+        ),
+    )?;
+    let reference = load_tm(current_path, &path)?;
+
+    let alphabet = query_string(
+        rl,
+        format!(
+            "Enter the characters the words are made of ({}): ",
+            "leave empty to use the ones read by both machines".italic()
+        ),
+    )?;
+    let mut alphabet: Vec<char> = alphabet.chars().filter(|c| !c.is_whitespace()).collect();
+    if alphabet.is_empty() {
+        alphabet = tm.get_input_alphabet();
+        alphabet.extend(reference.get_input_alphabet());
+    }
+    alphabet.sort();
+    alphabet.dedup();
+
+    let max_len = query_usize(rl, "Enter the maximum length of the words: ".to_string())?;
+    let step_budget = query_usize(
+        rl,
+        "Enter the maximum number of steps of each execution: ".to_string(),
+    )?;
+
+    let report = check_equivalence(tm, &reference, &alphabet, max_len, step_budget)
+        .map_err(|e| RiplError::EncounteredTuringError { error: e })?;
+    if report.is_equivalent() {
+        println!(
+            "{}",
+            format!(
+                "Both machines gave the same answer for the {} words checked.",
+                report.words_checked
+            )
+            .green()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!(
+            "The machines disagree on {} of the {} words checked :",
+            report.counterexamples.len(),
+            report.words_checked
+        )
+        .red()
+    );
+    for comparison in report
+        .counterexamples
+        .iter()
+        .take(MAX_COUNTEREXAMPLES_SHOWN)
+    {
+        println!(
+            "  \"{}\" : {} ({} steps) for this machine, {} ({} steps) for the other one",
+            comparison.word.yellow(),
+            comparison.first.outcome,
+            comparison.first.steps,
+            comparison.second.outcome,
+            comparison.second.steps
+        );
+    }
+    if report.counterexamples.len() > MAX_COUNTEREXAMPLES_SHOWN {
+        println!(
+            "  ... and {} more",
+            report.counterexamples.len() - MAX_COUNTEREXAMPLES_SHOWN
+        );
+    }
+    Ok(())
+}
+
+fn run_tests(
+    rl: &mut ReplInput,
+    tm: &TuringMachineGraph,
+    current_path: &Option<PathBuf>,
+) -> Result<(), RiplError> {
+    let path = PathBuf::from(query_string(
+        rl,
+        format!("Enter the {} of the test suite:", "path".blue()),
+    )?);
+    let path = match current_path {
+        Some(curr_path) if path.is_relative() => curr_path.join(path),
+        _ => path,
+    };
+    let suite = parse_test_suite_file_path(path.to_string_lossy().to_string())
+        .map_err(|e| RiplError::EncounteredParsingError { error: e })?;
+
+    let report = run_test_suite(tm, &suite);
+    for result in &report.results {
+        let result_str = result.to_string();
+        if result.passed {
+            println!("{}", result_str.green());
+        } else {
+            println!("{}", result_str.red());
         }
-        Err(e) => Err(e),
+    }
+    if report.is_success() {
+        println!("{}", report.summary().green());
+    } else {
+        println!("{}", report.summary().red());
+    }
+    Ok(())
+}
+
+fn profile_tm(
+    rl: &mut ReplInput,
+    tm: &TuringMachineGraph,
+    current_path: &Option<PathBuf>,
+) -> Result<(), RiplError> {
+    let alphabet = query_string(
+        rl,
+        format!(
+            "Enter the characters the words are made of ({}): ",
+            "leave empty to use the ones read by the machine".italic()
+        ),
+    )?;
+    let alphabet: Vec<char> = alphabet.chars().filter(|c| !c.is_whitespace()).collect();
+    let min_len = query_usize(rl, "Enter the minimum length of the words: ".to_string())?;
+    let max_len = query_usize(rl, "Enter the maximum length of the words: ".to_string())?;
+
+    let generator = query_string(
+        rl,
+        format!(
+            "Enter how the words are generated, {} of a given length, {} words, or the {} of a file listing words: ",
+            "all".blue(),
+            "random".blue(),
+            "path".blue()
+        ),
+    )?;
+    let generator = match generator.as_str() {
+        "all" => InputGenerator::All,
+        "random" => InputGenerator::Random {
+            samples: query_usize(rl, "Enter the number of words of each length: ".to_string())?,
+            seed: query_usize(rl, "Enter the seed of the random words: ".to_string())? as u64,
+        },
+        path => match fs::read_to_string(path) {
+            Ok(content) => InputGenerator::from_words(
+                content
+                    .lines()
+                    .map(|word| word.trim().to_string())
+                    .filter(|word| !word.is_empty())
+                    .collect(),
+            ),
+            Err(_) => {
+                return Err(RiplError::FileNotExistError {
+                    file_path: path.to_string(),
+                });
+            }
+        },
+    };
+    let step_budget = query_usize(
+        rl,
+        "Enter the maximum number of steps of each execution: ".to_string(),
+    )?;
+
+    let options = ProfileOptions {
+        alphabet: (!alphabet.is_empty()).then_some(alphabet),
+        lengths: (min_len..=max_len).collect(),
+        generator,
+        step_budget,
+    };
+    let profile = profile_complexity(tm, &options)
+        .map_err(|e| RiplError::EncounteredTuringError { error: e })?;
+    println!("{}", profile.to_table().blue());
+
+    let save = query_string(
+        rl,
+        format!(
+            "Save the plot as an SVG picture ? {}: ",
+            "Y(es) or N(o)".italic().blue()
+        ),
+    )?
+    .to_lowercase();
+    if save == "y" || save == "yes" {
+        let svg = profile.to_svg();
+        query_write_file(rl, current_path, ("profile", ".svg"), |_| svg.clone())?;
+    }
+    Ok(())
+}
+
+fn enumerate_words(rl: &mut ReplInput, tm: &TuringMachineGraph) -> Result<(), RiplError> {
+    let alphabet = query_string(
+        rl,
+        format!(
+            "Enter the characters the words are made of ({}): ",
+            "leave empty to use the ones read by the machine".italic()
+        ),
+    )?;
+    let alphabet: Vec<char> = alphabet.chars().filter(|c| !c.is_whitespace()).collect();
+    let max_len = query_usize(rl, "Enter the maximum length of the words: ".to_string())?;
+    let step_budget = query_usize(
+        rl,
+        "Enter the maximum number of steps of each execution: ".to_string(),
+    )?;
+
+    let enumeration = enumerate_language(
+        tm,
+        (!alphabet.is_empty()).then_some(alphabet.as_slice()),
+        max_len,
+        step_budget,
+    )
+    .map_err(|e| RiplError::EncounteredTuringError { error: e })?;
+
+    let format_words = |words: &Vec<String>| {
+        if words.is_empty() {
+            "none".italic().to_string()
+        } else {
+            words
+                .iter()
+                .map(|word| format!("\"{word}\""))
+                .collect::<Vec<String>>()
+                .join(", ")
+        }
+    };
+    println!(
+        "{} {}",
+        format!("Accepted ({}) :", enumeration.accepted.len()).green(),
+        format_words(&enumeration.accepted)
+    );
+    println!(
+        "{} {}",
+        format!("Rejected ({}) :", enumeration.rejected.len()).red(),
+        format_words(&enumeration.rejected)
+    );
+    println!(
+        "{} {}",
+        format!("Undecided ({}) :", enumeration.undecided.len()).yellow(),
+        format_words(&enumeration.undecided)
+    );
+    Ok(())
+}
+
+/// Writes the state and the tapes of a step on one line, the character under each head being put between brackets.
+fn format_step_cell(step: &TuringExecutionSteps) -> String {
+    let format_tape = |contents: &Vec<char>, pointer: usize| -> String {
+        contents
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                if i == pointer {
+                    format!("[{c}]")
+                } else {
+                    c.to_string()
+                }
+            })
+            .collect()
+    };
+    let mut tapes = vec![format_tape(
+        step.get_reading_tape().get_contents(),
+        step.get_reading_tape().get_pointer(),
+    )];
+    tapes.extend(
+        step.get_writing_tapes()
+            .iter()
+            .map(|tape| format_tape(tape.get_contents(), tape.get_pointer())),
+    );
+    format!("q_{} {}", step.get_current_state().name, tapes.join(" "))
+}
+
+fn run_side_by_side(rl: &mut ReplInput, storage: &DataStorage) -> Result<(), RiplError> {
+    let name = query_string(
+        rl,
+        format!(
+            "Enter the {} of the other machine ({}): ",
+            "name".blue(),
+            storage
+                .slots
+                .iter()
+                .map(|slot| slot.name.as_str())
+                .collect::<Vec<&str>>()
+                .join(", ")
+                .italic()
+        ),
+    )?;
+    let other = storage
+        .find_slot(&name)
+        .ok_or(RiplError::UnknownMachineError { name })?;
+    let word = query_string(rl, "Enter the word to feed to both machines: ".to_string())?;
+    let step_budget = query_usize(
+        rl,
+        "Enter the maximum number of steps of each execution: ".to_string(),
+    )?;
+
+    let machines = [storage.active().unwrap(), &storage.slots[other]];
+    let mut columns = vec![];
+    let mut outcomes = vec![];
+    for slot in machines {
+        let mut tm = TuringMachines::new(slot.graph.clone(), word.clone(), Mode::SaveAll)
+            .map_err(|e| RiplError::EncounteredTuringError { error: e })?;
+        // The first iteration is not a step
+        let cells: Vec<String> = (&mut tm)
+            .take(step_budget + 1)
+            .map(|step| format_step_cell(&step))
+            .collect();
+        columns.push(cells);
+        outcomes.push(
+            run(&slot.graph, &word, step_budget)
+                .map_err(|e| RiplError::EncounteredTuringError { error: e })?,
+        );
+    }
+
+    let outcomes: Vec<String> = outcomes
+        .iter()
+        .map(|result: &RunResult| format!("{} after {} steps", result.outcome, result.steps))
+        .collect();
+    let width = columns[0]
+        .iter()
+        .chain([&machines[0].name, &outcomes[0]])
+        .map(|cell| cell.chars().count())
+        .max()
+        .unwrap_or(0);
+    let pad = |text: &str| format!("{text}{}", " ".repeat(width - text.chars().count()));
+    println!(
+        "{}",
+        format!("Step | {} | {}", pad(&machines[0].name), machines[1].name).bold()
+    );
+    for i in 0..columns[0].len().max(columns[1].len()) {
+        let first = columns[0].get(i).map(|cell| cell.as_str()).unwrap_or("");
+        let second = columns[1].get(i).map(|cell| cell.as_str()).unwrap_or("");
+        println!("{i:>4} | {} | {second}", pad(first));
+    }
+    println!(
+        "{}",
+        format!("     | {} | {}", pad(&outcomes[0]), outcomes[1]).blue()
+    );
+    Ok(())
+}
+
+fn get_state_name(rl: &mut ReplInput) -> Result<String, RiplError> {
+    query_state_name(rl, format!("Enter the {} of the state: ", "name".blue()))
+}
+
+/// Asks for the name of a state, with or without its `q_` prefix.
+fn query_state_name(rl: &mut ReplInput, query: String) -> Result<String, RiplError> {
+    query_string(rl, query).map(strip_state_prefix)
+}
+
+/// Removes the `q_` or `q` prefix of the name of a state.
+fn strip_state_prefix(name: String) -> String {
+    if name.starts_with("q_") {
+        name.strip_prefix("q_").unwrap().to_string()
+    } else if name.starts_with("q") {
+        name.strip_prefix("q").unwrap().to_string()
+    } else {
+        name
     }
 }
 
 fn add_transition(
-    rl: &mut Editor<(), FileHistory>,
+    rl: &mut ReplInput,
     turing_graph: &mut TuringMachineGraph,
+    history: &mut GraphHistory,
 ) -> Result<(), RiplError> {
     let (q1, vec_tm, q2) = query_transition(
         rl,
@@ -204,7 +816,12 @@ fn add_transition(
     )?;
 
     for transition in vec_tm {
-        if let Err(e) = turing_graph.append_rule_state_by_name(&q1, transition.clone(), &q2) {
+        let edit = GraphEdit::AddTransition {
+            from: q1.clone(),
+            transition: transition.clone(),
+            to: q2.clone(),
+        };
+        if let Err(e) = history.apply(turing_graph, edit) {
             print_error_help(RiplError::EncounteredTuringError { error: e });
         } else {
             println!(
@@ -219,8 +836,9 @@ fn add_transition(
 }
 
 fn remove_transition(
-    rl: &mut Editor<(), FileHistory>,
+    rl: &mut ReplInput,
     turing_graph: &mut TuringMachineGraph,
+    history: &mut GraphHistory,
 ) -> Result<(), RiplError> {
     let (q1, vec_tm, q2) = query_transition(
         rl,
@@ -232,7 +850,12 @@ fn remove_transition(
     )?;
 
     for transition in vec_tm {
-        if let Err(e) = turing_graph.remove_transition(&q1, &transition, &q2) {
+        let edit = GraphEdit::RemoveTransition {
+            from: q1.clone(),
+            transition: transition.clone(),
+            to: q2.clone(),
+        };
+        if let Err(e) = history.apply(turing_graph, edit) {
             print_error_help(RiplError::EncounteredTuringError { error: e });
         } else {
             println!(
@@ -246,17 +869,155 @@ fn remove_transition(
     Ok(())
 }
 
+fn rename_state(
+    rl: &mut ReplInput,
+    turing_graph: &mut TuringMachineGraph,
+    history: &mut GraphHistory,
+) -> Result<(), RiplError> {
+    let name = get_state_name(rl)?;
+    let new_name = query_state_name(
+        rl,
+        format!("Enter the {} of the state: ", "new name".blue()),
+    )?;
+
+    let edit = GraphEdit::RenameState {
+        name: name.clone(),
+        new_name: new_name.clone(),
+    };
+    if let Err(e) = history.apply(turing_graph, edit) {
+        return Err(RiplError::EncounteredTuringError { error: e });
+    }
+    println!(
+        "{}",
+        format!(
+            "Successfully renamed the state \'q_{}\' to \'q_{}\'.",
+            name.yellow(),
+            new_name.yellow()
+        )
+        .green()
+    );
+    Ok(())
+}
+
+fn set_state_type(
+    rl: &mut ReplInput,
+    turing_graph: &mut TuringMachineGraph,
+    history: &mut GraphHistory,
+) -> Result<(), RiplError> {
+    let name = get_state_name(rl)?;
+    let value = query_string(
+        rl,
+        format!(
+            "Enter the {} of the state ({}, {} or {}): ",
+            "type".blue(),
+            "normal".italic(),
+            "accepting".italic(),
+            "rejecting".italic()
+        ),
+    )?;
+    let state_type = match value.to_lowercase().as_str() {
+        "n" | "normal" => TuringStateType::Normal,
+        "a" | "accepting" => TuringStateType::Accepting,
+        "r" | "rejecting" => TuringStateType::Rejecting,
+        _ => return Err(RiplError::CouldNotParseStringError { value }),
+    };
+
+    let edit = GraphEdit::SetStateType {
+        name: name.clone(),
+        state_type: state_type.clone(),
+    };
+    if let Err(e) = history.apply(turing_graph, edit) {
+        return Err(RiplError::EncounteredTuringError { error: e });
+    }
+    println!(
+        "{}",
+        format!(
+            "The state \'q_{}\' is now {}.",
+            name.yellow(),
+            state_type.to_string().to_lowercase()
+        )
+        .green()
+    );
+    Ok(())
+}
+
+/// Asks for a transition, then lets the user edit it before replacing it in the graph.
+fn edit_transition(
+    rl: &mut ReplInput,
+    turing_graph: &mut TuringMachineGraph,
+    history: &mut GraphHistory,
+) -> Result<(), RiplError> {
+    let (from, transition, to) = query_single_transition(
+        rl,
+        format!("Enter the {} to edit: ", "transition".blue()),
+        "",
+    )?;
+    let (new_from, new_transition, new_to) = query_single_transition(
+        rl,
+        format!("Edit the {}: ", "transition".blue()),
+        &format!("q_{from} {{{transition}}} q_{to}"),
+    )?;
+    if new_from != from {
+        return Err(RiplError::EditedSourceStateError {
+            expected: from,
+            received: new_from,
+        });
+    }
+
+    let edit = GraphEdit::ReplaceTransition {
+        from: from.clone(),
+        transition,
+        to,
+        new_transition: new_transition.clone(),
+        new_to: new_to.clone(),
+    };
+    if let Err(e) = history.apply(turing_graph, edit) {
+        return Err(RiplError::EncounteredTuringError { error: e });
+    }
+    println!(
+        "{}{}",
+        "Successfully edited the transition : ".green(),
+        format_transition(&from, &new_transition, &new_to)
+    );
+    Ok(())
+}
+
+/// Asks for a transition that must contain a single one, suggesting the given text if the user is asked.
+fn query_single_transition(
+    rl: &mut ReplInput,
+    query: String,
+    initial: &str,
+) -> Result<(String, TuringTransition, String), RiplError> {
+    let (from, mut transitions, to) = query_transition_with_initial(rl, query, initial)?;
+    if transitions.len() != 1 {
+        return Err(RiplError::ArgsNumberError {
+            received: transitions.len(),
+            expected: 1,
+        });
+    }
+    Ok((from, transitions.remove(0), to))
+}
+
 fn format_transition(from: &String, transition: &TuringTransition, to: &String) -> ColoredString {
     format!("q_{} {}{}{} q_{}", from, "{", transition, "}", to).yellow()
 }
 
 pub fn query_transition(
-    rl: &mut Editor<(), FileHistory>,
+    rl: &mut ReplInput,
+    query: String,
+) -> Result<(String, Vec<TuringTransition>, String), RiplError> {
+    query_transition_with_initial(rl, query, "")
+}
+
+/// Asks for one or multiple transitions, suggesting the given text if the user is asked.
+pub fn query_transition_with_initial(
+    rl: &mut ReplInput,
     query: String,
+    initial: &str,
 ) -> Result<(String, Vec<TuringTransition>, String), RiplError> {
-    println!("{}", query);
+    rl.ask(&query);
     loop {
-        let readline = rl.readline("==> ");
+        let readline = rl.read_transition("==> ", initial);
         match readline {
             Ok(l) => {
                 let l = l.trim().to_string();
@@ -265,7 +1026,8 @@ pub fn query_transition(
                 }
                 rl.add_history_entry(l.to_string()).unwrap();
 
-                let res = parse_transition_string(l);
+                // The transition can end with a `;`, like in a file
+                let res = parse_transition_string(l.trim_end_matches(';').to_string());
                 if let Err(e) = res {
                     return Err(RiplError::EncounteredParsingError { error: e });
                 }
@@ -280,101 +1042,39 @@ pub fn query_transition(
     }
 }
 
+/// Saves the machine, suggesting the path it was loaded from or last saved to, and returns the chosen path.
 fn save_tm(
-    rl: &mut Editor<(), FileHistory>,
+    rl: &mut ReplInput,
     tm: &TuringMachineGraph,
+    save_path: &Option<PathBuf>,
     current_path: &Option<PathBuf>,
-) -> Result<(), RiplError> {
+) -> Result<Option<PathBuf>, RiplError> {
     let tm_string = turing_parser::graph_to_string(tm);
     if tm_string.is_empty() {
         println!(
             "{}",
             "Nothing to save because this machine has no transitions !".red()
         );
-        return Ok(());
-    }
-    loop {
-        println!(
-            "Enter the {} of the {} to create: ",
-            "path".bold().blue(),
-            "file".bold()
-        );
-        let readline = match current_path {
-            Some(p) => rl.readline_with_initial(
-                "==> ",
-                (
-                    p.as_path()
-                        .join("turing_machine")
-                        .to_str()
-                        .unwrap()
-                        .to_string()
-                        .as_str(),
-                    ".tm",
-                ),
-            ),
-            None => rl.readline("==> "),
-        };
-        match readline {
-            Ok(l) => {
-                let l = l.trim().to_string();
-                if l.is_empty() {
-                    continue;
-                }
-                rl.add_history_entry(l.to_string()).unwrap();
-
-                let path = Path::new(&l);
-                // Check that no file with this name exists
-
-                if path.exists() {
-                    // If it does, ask user for confirmation before overwritting it
-                    let choice = query_string(
-                        rl,
-                        format!(
-                            "A file with this name already exists, rewrite it ? {}: ",
-                            "Y(es) or N(o)".italic().blue()
-                        ),
-                    );
-                    match choice {
-                        Ok(choice) => {
-                            let choice = choice.to_lowercase();
-                            if !choice.eq("y") && !choice.eq("yes") {
-                                continue;
-                            }
-                        }
-                        Err(e) => {
-                            return Err(e);
-                        }
-                    }
-                }
-
-                let file = File::create(path);
-                match file {
-                    Ok(mut f) => {
-                        if let Err(e) = f.write_all(tm_string.as_bytes()) {
-                            return Err(RiplError::FileError {
-                                file_path: Some(e.to_string()),
-                            });
-                        }
-                    }
-                    Err(e) => {
-                        return Err(RiplError::FileError {
-                            file_path: Some(e.to_string()),
-                        });
-                    }
-                }
-
-                println!(
-                    "{}{}",
-                    "Saved the file at the location : ".green(),
-                    path.to_str().unwrap()
-                );
-                return Ok(());
-            }
-            Err(e) => {
-                return Err(RiplError::CouldNotParseStringError {
-                    value: e.to_string(),
-                });
-            }
-        }
+        return Ok(None);
     }
+    let (directory, name, extension) = match save_path {
+        Some(path) => (
+            path.parent().map(|parent| parent.to_path_buf()),
+            path.file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or("turing_machine".to_string()),
+            path.extension()
+                .map(|ext| format!(".{}", ext.to_string_lossy()))
+                .unwrap_or(".tm".to_string()),
+        ),
+        None => (
+            current_path.clone(),
+            "turing_machine".to_string(),
+            ".tm".to_string(),
+        ),
+    };
+    query_write_file(rl, &directory, (&name, &extension), |path| {
+        graph_to_string_by_extension(tm, path)
+    })
+    .map(Some)
 }
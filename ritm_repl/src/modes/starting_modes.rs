@@ -5,13 +5,13 @@ use std::{
 
 use crate::{
     DataStorage,
+    input::ReplInput,
     modes::choice_modes::{ModeEvent, Modes},
     query_string, query_usize,
     ripl_error::{RiplError, print_error_help},
 };
 use colored::Colorize;
-use ritm_core::{turing_graph::TuringMachineGraph, turing_parser::parse_turing_graph_file_path};
-use rustyline::{Editor, history::FileHistory};
+use ritm_core::{turing_formats::load_graph_by_extension, turing_graph::TuringMachineGraph};
 use strum_macros::EnumIter;
 
 #[derive(EnumIter)]
@@ -34,6 +34,13 @@ impl Display for StartingMode {
 }
 
 impl ModeEvent for StartingMode {
+    fn name(&self) -> &'static str {
+        match self {
+            StartingMode::CreateTM => "create",
+            StartingMode::LoadTM => "load",
+        }
+    }
+
     fn print_help(&self) {
         let tm_it = "Turing Machine".italic().bold();
         print!("-> ");
@@ -45,25 +52,30 @@ impl ModeEvent for StartingMode {
                     "number of writting tapes".purple()
                 ),
                 StartingMode::LoadTM => format!(
-                    "Loads a new {tm_it} by specifying a {} to it from",
-                    "file path".purple()
+                    "Loads a new {tm_it} by specifying a {} to it from (a {} file, a {} file or a JFLAP {} file)",
+                    "file path".purple(),
+                    ".tm".purple(),
+                    ".json".purple(),
+                    ".jff".purple()
                 ),
             }
             .green()
         );
     }
 
-    fn choose_option(&self, rl: &mut Editor<(), FileHistory>, storage: &mut DataStorage) -> Modes {
+    fn choose_option(&self, rl: &mut ReplInput, storage: &mut DataStorage) -> Modes {
         let res = match self {
-            StartingMode::CreateTM => create_tm(rl),
-            StartingMode::LoadTM => query_load_tm(rl, &storage.curr_path),
+            StartingMode::CreateTM => create_tm(rl).map(|tm| (tm, None)),
+            StartingMode::LoadTM => query_load_tm(rl, &storage.curr_path)
+                .map(|(tm, path)| (tm, Some(PathBuf::from(path)))),
         };
         if let Err(e) = res {
             print_error_help(e);
             return Modes::Start;
         }
-        // Store the created/loaded turing machine graph
-        storage.graph = Some(res.unwrap());
+        // Store the created/loaded turing machine graph in a new slot, and use it
+        let (tm, save_path) = res.unwrap();
+        storage.add_slot(tm, save_path);
         // Change the mode to allow modifying this tm graph
         Modes::Modify
     }
@@ -73,7 +85,7 @@ impl ModeEvent for StartingMode {
     }
 }
 
-fn create_tm(rl: &mut Editor<(), FileHistory>) -> Result<TuringMachineGraph, RiplError> {
+fn create_tm(rl: &mut ReplInput) -> Result<TuringMachineGraph, RiplError> {
     let res = query_usize(
         rl,
         format!(
@@ -91,16 +103,17 @@ fn create_tm(rl: &mut Editor<(), FileHistory>) -> Result<TuringMachineGraph, Rip
     Ok(tm.unwrap())
 }
 
+/// Loads the machine stored in the file given by the user, and returns it along with the given path.
 fn query_load_tm(
-    rl: &mut Editor<(), FileHistory>,
+    rl: &mut ReplInput,
     current_path: &Option<PathBuf>,
-) -> Result<TuringMachineGraph, RiplError> {
+) -> Result<(TuringMachineGraph, String), RiplError> {
     let path_str = query_string(
         rl,
         format!("Enter the {} the Turing machine to read:", "path".blue()),
     )?;
 
-    load_tm(current_path, &path_str)
+    load_tm(current_path, &path_str).map(|tm| (tm, path_str))
 }
 
 pub fn load_tm(
@@ -132,7 +145,7 @@ pub fn load_tm(
         });
     }
 
-    let tm = parse_turing_graph_file_path(abs_path.unwrap().to_string());
+    let tm = load_graph_by_extension(Path::new(abs_path.unwrap()));
     if let Err(e) = tm {
         return Err(RiplError::EncounteredParsingError { error: e });
     }
@@ -1,4 +1,7 @@
-use std::fmt::Display;
+use std::{
+    fmt::Display,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
 use colored::{ColoredString, Colorize};
 use ritm_core::turing_errors::{TuringError, TuringParserError};
@@ -8,6 +11,7 @@ pub enum RiplError {
     OutOfRangeIndexError { index: usize },
     NegativeValueError { value: f32 },
     UnknownCommandError { command: String },
+    UnusedArgumentError { argument: String },
     CouldNotParseStringIntError { value: String },
     CouldNotParseStringError { value: String },
     ArgsNumberError { received: usize, expected: usize },
@@ -15,9 +19,21 @@ pub enum RiplError {
     EncounteredParsingError { error: TuringParserError },
     FileError { file_path: Option<String> },
     FileNotExistError { file_path: String },
+    UnknownMachineError { name: String },
+    EditedSourceStateError { expected: String, received: String },
+    NoFileToWatchError { name: String },
+}
+
+/// The number of errors printed since the start of the REPL
+static ERRORS_REPORTED: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns the number of errors printed by [print_error_help] since the start of the REPL.
+pub fn errors_reported() -> usize {
+    ERRORS_REPORTED.load(Ordering::SeqCst)
 }
 
 pub fn print_error_help(error: RiplError) {
+    ERRORS_REPORTED.fetch_add(1, Ordering::SeqCst);
     println!(
         "{}",
         match error {
@@ -29,6 +45,10 @@ pub fn print_error_help(error: RiplError) {
                 "The given command \"{}\" is not a known command",
                 as_arg_error(command)
             ),
+            RiplError::UnusedArgumentError { argument } => format!(
+                "The command does not take that many arguments, so \"{}\" was ignored",
+                as_arg_error(argument)
+            ),
             RiplError::CouldNotParseStringIntError { value } => format!(
                 "Could not parse the value \"{}\" into a positive integer",
                 as_arg_error(value)
@@ -54,6 +74,17 @@ pub fn print_error_help(error: RiplError) {
                 "No file found at the given path : \"{}\"",
                 as_arg_error(file_path)
             ),
+            RiplError::UnknownMachineError { name } =>
+                format!("No machine named \"{}\" is loaded", as_arg_error(name)),
+            RiplError::NoFileToWatchError { name } => format!(
+                "The machine \"{}\" is not stored in a file, save it before watching it",
+                as_arg_error(name)
+            ),
+            RiplError::EditedSourceStateError { expected, received } => format!(
+                "The edited transition must still leave the state \"q_{}\", not \"q_{}\"",
+                expected.blue(),
+                as_arg_error(received)
+            ),
             RiplError::NegativeValueError { value } =>
                 format!("The given value (\"{value}\") should not be negative."),
         }
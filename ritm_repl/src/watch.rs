@@ -0,0 +1,208 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use colored::Colorize;
+use ritm_core::{turing_history::GraphEdit, turing_machine::TuringMachines};
+
+use crate::{
+    DataStorage, MachineSlot,
+    modes::starting_modes::load_tm,
+    ripl_error::{RiplError, print_error_help},
+};
+
+/// Returns the last time the file at the given path was modified, if it can be known.
+pub fn modification_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Starts watching the file of the machine if it was not watched, or stops watching it otherwise.
+///
+/// Returns true if the file is now watched.
+pub fn toggle_watch(slot: &mut MachineSlot) -> Result<bool, RiplError> {
+    if slot.watch.is_some() {
+        slot.watch = None;
+        return Ok(false);
+    }
+    let Some(path) = &slot.save_path else {
+        return Err(RiplError::NoFileToWatchError {
+            name: slot.name.clone(),
+        });
+    };
+    match modification_time(path) {
+        Some(time) => {
+            slot.watch = Some(time);
+            Ok(true)
+        }
+        None => Err(RiplError::FileNotExistError {
+            file_path: path.to_string_lossy().to_string(),
+        }),
+    }
+}
+
+/// A watched file, along with the version of it that was loaded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchedFile {
+    /// The name of the machine stored in the file
+    pub name: String,
+    pub path: PathBuf,
+    /// The last time the file was modified when the machine was loaded
+    pub loaded: SystemTime,
+}
+
+/// Returns the files of the watched machines.
+pub fn watched_files(storage: &DataStorage) -> Vec<WatchedFile> {
+    storage
+        .slots
+        .iter()
+        .filter_map(|slot| {
+            Some(WatchedFile {
+                name: slot.name.clone(),
+                path: slot.save_path.clone()?,
+                loaded: slot.watch?,
+            })
+        })
+        .collect()
+}
+
+/// Checks the watched files in the background, so that the user learns that one of them changed while the REPL waits for a command.
+///
+/// The machines are only reloaded by [reload_watched_machines], since they cannot be modified while a command is read.
+/// The thread checking the files stops once the watcher is dropped.
+pub struct FileWatcher {
+    files: Arc<Mutex<Vec<WatchedFile>>>,
+}
+
+impl FileWatcher {
+    /// Starts checking the files every `interval`, giving a message to `notify` once for each new version of a file.
+    pub fn new(interval: Duration, mut notify: impl FnMut(String) + Send + 'static) -> Self {
+        let files: Arc<Mutex<Vec<WatchedFile>>> = Arc::default();
+        let watched = Arc::downgrade(&files);
+        thread::spawn(move || {
+            // The last version of each file that was reported
+            let mut reported: HashMap<PathBuf, SystemTime> = HashMap::new();
+            loop {
+                thread::sleep(interval);
+                let Some(files) = watched.upgrade() else {
+                    return;
+                };
+                let files = files.lock().unwrap().clone();
+                for file in files {
+                    let Some(time) = modification_time(&file.path) else {
+                        continue;
+                    };
+                    if time == file.loaded || reported.get(&file.path) == Some(&time) {
+                        continue;
+                    }
+                    reported.insert(file.path.clone(), time);
+                    notify(
+                        format!(
+                            "The file \"{}\" of the machine \"{}\" changed, press Enter to reload it.",
+                            file.path.to_string_lossy(),
+                            file.name
+                        )
+                        .yellow()
+                        .to_string(),
+                    );
+                }
+            }
+        });
+        Self { files }
+    }
+
+    /// Checks the files of the machines watched in the storage from now on.
+    pub fn update(&self, storage: &DataStorage) {
+        *self.files.lock().unwrap() = watched_files(storage);
+    }
+}
+
+/// Reloads the watched machines whose file was modified since they were last loaded.
+///
+/// The reload is recorded in the history of the machine, so the modifications that were not saved can be recovered by undoing it.
+/// A machine being executed is executed again from the start, with the same word and mode.
+/// If the file cannot be parsed, the error is shown and the previous version of the machine is kept.
+pub fn reload_watched_machines(storage: &mut DataStorage) {
+    for slot in &mut storage.slots {
+        let (Some(last_time), Some(path)) = (slot.watch, slot.save_path.clone()) else {
+            continue;
+        };
+        // The file can be missing for a moment while an editor replaces it
+        let Some(time) = modification_time(&path) else {
+            continue;
+        };
+        if time == last_time {
+            continue;
+        }
+        slot.watch = Some(time);
+        reload_slot(slot, &storage.curr_path, &path);
+    }
+}
+
+fn reload_slot(slot: &mut MachineSlot, current_path: &Option<PathBuf>, path: &Path) {
+    let path_str = path.to_string_lossy().to_string();
+    let graph = match load_tm(current_path, &path_str) {
+        Ok(graph) => graph,
+        Err(e) => {
+            println!(
+                "{}",
+                format!(
+                    "The file \"{path_str}\" of the machine \"{}\" changed but could not be loaded, the previous version is kept :",
+                    slot.name
+                )
+                .yellow()
+            );
+            print_error_help(e);
+            return;
+        }
+    };
+
+    // Like when the file was only formatted again
+    if graph == slot.graph {
+        return;
+    }
+    let edit = GraphEdit::ReplaceGraph {
+        graph: Box::new(graph),
+        description: format!("reload the machine from \"{path_str}\""),
+    };
+    if let Err(e) = slot.history.apply(&mut slot.graph, edit) {
+        print_error_help(RiplError::EncounteredTuringError { error: e });
+        return;
+    }
+    println!(
+        "{}",
+        format!(
+            "Reloaded the machine \"{}\" from \"{path_str}\", use undo to get the previous version back.",
+            slot.name.purple()
+        )
+        .green()
+    );
+
+    let Some(iterator) = &slot.iterator else {
+        return;
+    };
+    let word = iterator.get_word().clone();
+    match TuringMachines::new(
+        slot.graph.clone(),
+        word.clone(),
+        iterator.get_mode().clone(),
+    ) {
+        Ok(iterator) => {
+            slot.iterator = Some(iterator);
+            println!(
+                "{}",
+                format!("Restarted the execution of the word \"{}\".", word.yellow()).green()
+            );
+        }
+        Err(e) => {
+            slot.iterator = None;
+            print_error_help(RiplError::EncounteredTuringError { error: e });
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use ritm_repl::input::ReplInput;
+
+#[test]
+fn each_argument_answers_one_question() {
+    let mut rl = ReplInput::new(false).unwrap();
+    rl.set_argument("q_x  q_y".to_string());
+    assert_eq!(rl.readline("==> ").unwrap(), "q_x");
+    assert_eq!(rl.readline("==> ").unwrap(), "q_y");
+    assert!(rl.take_argument().is_none());
+
+    // Without arguments nor scripts, nothing is left to read
+    assert!(rl.readline("==> ").is_err());
+}
+
+#[test]
+fn transition_reads_the_arguments_left() {
+    let mut rl = ReplInput::new(false).unwrap();
+    rl.set_argument("q_1 {0, _ -> R, 0, R} q_2".to_string());
+    assert_eq!(
+        rl.read_transition("==> ", "").unwrap(),
+        "q_1 {0, _ -> R, 0, R} q_2"
+    );
+    assert!(rl.take_argument().is_none());
+}
+
+#[test]
+fn unread_arguments_are_returned() {
+    let mut rl = ReplInput::new(false).unwrap();
+    rl.set_argument("0110 extra words".to_string());
+    assert_eq!(rl.readline("==> ").unwrap(), "0110");
+    assert_eq!(rl.take_argument().unwrap(), "extra words");
+}
@@ -0,0 +1,168 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    sync::{Arc, atomic::AtomicBool, mpsc},
+    time::{Duration, SystemTime},
+};
+
+use ritm_core::{
+    turing_history::GraphEdit,
+    turing_machine::{Mode, TuringMachines},
+    turing_parser::parse_turing_graph_string,
+};
+use ritm_repl::{
+    DataStorage,
+    watch::{FileWatcher, reload_watched_machines, toggle_watch},
+};
+
+const TM_START_0: &str = "q_i {ç, ç -> R, ç, R} q_1;
+q_1 {0, _ -> N, _, N} q_a;";
+
+const TM_START_1: &str = "q_i {ç, ç -> R, ç, R} q_1;
+q_1 {1, _ -> N, _, N} q_a;";
+
+/// Writes a machine in a temporary file, named after the test so that the tests can run in parallel.
+fn write_machine(name: &str, content: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("ritm_repl_{}_{name}.tm", std::process::id()));
+    fs::write(&path, content).unwrap();
+    path
+}
+
+/// Creates a storage holding the machine stored at the given path, with its file watched.
+fn watched_storage(path: &Path, content: &str) -> DataStorage {
+    let mut storage = DataStorage {
+        slots: vec![],
+        active_slot: 0,
+        is_running: Arc::new(AtomicBool::new(true)),
+        curr_path: None,
+        clear_after_step: false,
+        exec_mode: Mode::SaveAll,
+    };
+    storage.add_slot(
+        parse_turing_graph_string(content.to_string()).unwrap(),
+        Some(path.to_path_buf()),
+    );
+    assert!(toggle_watch(storage.active_mut().unwrap()).unwrap());
+    storage
+}
+
+/// Makes the slot believe its file was loaded long ago, since the modification times may not be precise enough to see a change.
+fn mark_outdated(storage: &mut DataStorage) {
+    storage.active_mut().unwrap().watch = Some(SystemTime::UNIX_EPOCH);
+}
+
+#[test]
+fn reload_can_be_undone() {
+    let path = write_machine("undo", TM_START_0);
+    let mut storage = watched_storage(&path, TM_START_0);
+
+    // A modification made in the REPL, that was not saved
+    let slot = storage.active_mut().unwrap();
+    slot.history
+        .apply(
+            &mut slot.graph,
+            GraphEdit::AddState {
+                name: "2".to_string(),
+            },
+        )
+        .unwrap();
+    let edited = slot.graph.clone();
+
+    // Nothing happens while the version of the file that was loaded is the last one
+    fs::write(&path, TM_START_1).unwrap();
+    storage.active_mut().unwrap().watch = fs::metadata(&path).unwrap().modified().ok();
+    reload_watched_machines(&mut storage);
+    assert_eq!(storage.active().unwrap().graph, edited);
+
+    mark_outdated(&mut storage);
+    reload_watched_machines(&mut storage);
+    let slot = storage.active_mut().unwrap();
+    assert_eq!(
+        slot.graph,
+        parse_turing_graph_string(TM_START_1.to_string()).unwrap()
+    );
+    assert_ne!(slot.watch, Some(SystemTime::UNIX_EPOCH));
+
+    // The unsaved modification is brought back by undoing the reload
+    let undone = slot.history.undo(&mut slot.graph).unwrap().unwrap();
+    assert!(matches!(undone, GraphEdit::ReplaceGraph { .. }));
+    assert_eq!(slot.graph, edited);
+    slot.history.undo(&mut slot.graph).unwrap();
+    assert_eq!(
+        slot.graph,
+        parse_turing_graph_string(TM_START_0.to_string()).unwrap()
+    );
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn reload_keeps_invalid_files_out() {
+    let path = write_machine("invalid", TM_START_0);
+    let mut storage = watched_storage(&path, TM_START_0);
+
+    fs::write(&path, "q_i {ç, ç -> R, ç, R q_1;").unwrap();
+    mark_outdated(&mut storage);
+    reload_watched_machines(&mut storage);
+
+    let slot = storage.active().unwrap();
+    assert_eq!(
+        slot.graph,
+        parse_turing_graph_string(TM_START_0.to_string()).unwrap()
+    );
+    assert!(!slot.history.can_undo());
+    // The same version is not loaded again before the next change
+    assert_ne!(slot.watch, Some(SystemTime::UNIX_EPOCH));
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn reload_restarts_the_execution() {
+    let path = write_machine("execution", TM_START_0);
+    let mut storage = watched_storage(&path, TM_START_0);
+    let slot = storage.active_mut().unwrap();
+    let mut iterator =
+        TuringMachines::new(slot.graph.clone(), "10".to_string(), Mode::StopFirstReject).unwrap();
+    (&mut iterator).next();
+    slot.iterator = Some(iterator);
+
+    fs::write(&path, TM_START_1).unwrap();
+    mark_outdated(&mut storage);
+    reload_watched_machines(&mut storage);
+
+    let slot = storage.active().unwrap();
+    let iterator = slot.iterator.as_ref().unwrap();
+    assert_eq!(iterator.get_word(), "10");
+    assert_eq!(*iterator.get_mode(), Mode::StopFirstReject);
+    assert_eq!(*iterator.graph_ref(), slot.graph);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn watcher_reports_changes_once() {
+    let path = write_machine("watcher", TM_START_0);
+    let mut storage = watched_storage(&path, TM_START_0);
+    let (sender, receiver) = mpsc::channel();
+    let watcher = FileWatcher::new(Duration::from_millis(10), move |message| {
+        let _ = sender.send(message);
+    });
+
+    // The file did not change since it was loaded
+    watcher.update(&storage);
+    assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+
+    mark_outdated(&mut storage);
+    watcher.update(&storage);
+    let message = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert!(message.contains(&storage.active().unwrap().name));
+    assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+
+    // Once reloaded, the machine is up to date
+    reload_watched_machines(&mut storage);
+    watcher.update(&storage);
+    assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+
+    fs::remove_file(&path).unwrap();
+}